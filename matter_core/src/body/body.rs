@@ -1,7 +1,9 @@
 // MARK: Usings
 // region: Usings
 use super::{
-    body_option::BodyOption, body_orgiginal::BodyOriginal, body_properties::BodyProperties,
+    body_option::BodyOption,
+    body_orgiginal::BodyOriginal,
+    body_properties::{BodyProperties, MassRegion},
 };
 use crate::{
     core::{
@@ -9,7 +11,9 @@ use crate::{
         common::{self, ShapeType, BASE_DELTA},
         constraint_impulse::ConstraintImpulse,
         force::Force,
+        math,
         position::{self, Position},
+        real::Real,
         render::Render,
         sprite::Sprite,
         velocity::{self, Velocity},
@@ -18,6 +22,7 @@ use crate::{
     geometry::{
         axes::{self, Axes},
         bounds::{self, Bounds},
+        transform::Transform,
         vector::{self, Vector},
         vertices::{self, Vertices},
     },
@@ -28,10 +33,7 @@ use uuid::Uuid;
 // region: Usings
 
 // MARK: consts
-const INERTIA_SCALE: f64 = 4.;
-const NEXT_COLLIDING_GROUP_ID: i32 = 1;
-const NEXT_NON_COLLIDING_GROUP_ID: i32 = -1;
-const NEXT_CATEGORY: u16 = 1;
+const INERTIA_SCALE: Real = 4.;
 const TIME_CORRECTION: bool = true;
 
 // MARK: Structs
@@ -43,52 +45,67 @@ pub struct Body {
 
 #[derive(Clone)]
 pub struct BodyContent {
-    angle_prev: f64,
-    angle: f64,
-    angular_speed: f64,
-    angular_velocity: f64,
-    area: f64,
+    angle_prev: Real,
+    angle: Real,
+    angular_speed: Real,
+    angular_velocity: Real,
+    area: Real,
     axes: Option<Axes>,
     bounds: Option<Bounds>,
     chamfer: Option<Vec<Vector>>,
-    circle_radius: Option<f64>,
+    circle_radius: Option<Real>,
     collision_filter: CollisionFilter,
     constraint_impulse: ConstraintImpulse,
-    delta_time: Option<f64>,
-    density: f64,
+    delta_time: Option<Real>,
+    density: Real,
     //events: Option<?>,
     force: Force,
-    friction_air: f64,
-    friction_static: f64,
-    friction: f64,
+    friction_air: Real,
+    friction_static: Real,
+    friction: Real,
     id: Uuid,
-    inertia: f64,
-    inverse_inertia: f64,
-    inverse_mass: f64,
+    inertia: Real,
+    inverse_inertia: Real,
+    inverse_mass: Real,
+    is_bullet: bool,
     is_sensor: bool,
     is_sleeping: bool,
     is_static: bool,
-    mass: f64,
-    motion: f64,
+    mass: Real,
+    motion: Real,
     parts: Option<Vec<Body>>,
     //plugin: Option<?>,
     position_impulse: Position,
     position_prev: Option<Position>,
     position: Position,
     render: Render,
-    resitution: f64,
+    resitution: Real,
     shape_type: ShapeType,
     sleep_threshold: u32,
-    slop: f64,
-    speed: f64,
+    slop: Real,
+    speed: Real,
     time_scale: u16,
-    torque: f64,
+    torque: Real,
     total_contacts: u32,
     velocity: Velocity,
     vertices: Vertices,
+    local_geometry: Option<LocalGeometry>,
     _original: Option<BodyOriginal>,
 }
 
+// The vertices and axes relative to the pose. Moving a body only changes its pose, the world
+// space vertices, axes and bounds are rebuilt from these on the next read so no rounding error
+// accumulates. The pose has its own angle, as the geometry of a part turns with its parent
+// while the angle of the part does not, as in matter.js.
+#[derive(Clone)]
+struct LocalGeometry {
+    vertices: Vertices,
+    axes: Option<Axes>,
+    angle: Real,
+    velocity: Velocity,
+    is_current: bool,
+}
+
 // MARK: Default Body
 impl BodyContent {
     fn default_contant() -> Self {
@@ -114,6 +131,7 @@ impl BodyContent {
             inertia: 0.,
             inverse_inertia: 0.,
             inverse_mass: 0.,
+            is_bullet: false,
             is_sensor: false,
             is_sleeping: false,
             is_static: false,
@@ -135,9 +153,62 @@ impl BodyContent {
             total_contacts: 0,
             velocity: Velocity::new(0., 0.),
             vertices: Vertices::from_path("L 0 0 L 40 0 L 40 40 L 0 40", None).unwrap(),
+            local_geometry: None,
             _original: None,
         }
     }
+
+    fn is_geometry_current(&self) -> bool {
+        match &self.local_geometry {
+            Some(local) => local.is_current,
+            None => true,
+        }
+    }
+
+    fn capture_local_geometry(&mut self) {
+        if self.local_geometry.is_some() {
+            return;
+        }
+
+        let inverse = Transform::new(self.angle, &self.position).inverse();
+        let mut vertices = self.vertices.clone();
+        vertices.transform(&inverse);
+        let axes = self.axes.clone().map(|mut axes| {
+            axes.transform(&inverse);
+            axes
+        });
+
+        self.local_geometry = Some(LocalGeometry {
+            vertices: vertices,
+            axes: axes,
+            angle: self.angle,
+            velocity: self.velocity,
+            is_current: true,
+        });
+    }
+
+    fn refresh_geometry(&mut self) {
+        let Some(local) = &mut self.local_geometry else {
+            return;
+        };
+
+        let transform = Transform::new(local.angle, &self.position);
+        let mut vertices = local.vertices.clone();
+        vertices.transform(&transform);
+        self.vertices = vertices;
+
+        if let Some(axes) = &local.axes {
+            let mut axes = axes.clone();
+            axes.transform(&transform);
+            self.axes = Some(axes);
+        }
+
+        if let Some(bounds) = &mut self.bounds {
+            bounds.update(&self.vertices, Some(&local.velocity));
+        }
+
+        local.is_current = true;
+    }
 }
 
 // MARK: Content Macro's
@@ -198,31 +269,32 @@ impl Body {
         content!(self).id
     }
 
-    pub fn get_inertia(&self) -> f64 {
+    pub fn get_inertia(&self) -> Real {
         content!(self).inertia
     }
 
-    pub fn get_inverse_inertia(&self) -> f64 {
+    pub fn get_inverse_inertia(&self) -> Real {
         content!(self).inverse_inertia
     }
 
-    pub fn get_mass(&self) -> f64 {
+    pub fn get_mass(&self) -> Real {
         content!(self).mass
     }
 
-    pub fn get_inverse_mass(&self) -> f64 {
+    pub fn get_inverse_mass(&self) -> Real {
         content!(self).inverse_mass
     }
 
-    pub fn get_density(&self) -> f64 {
+    pub fn get_density(&self) -> Real {
         content!(self).density
     }
 
-    pub fn get_area(&self) -> f64 {
+    pub fn get_area(&self) -> Real {
         content!(self).area
     }
 
     pub fn get_axes(&self) -> Option<Axes> {
+        self.refresh_geometry();
         content!(self).axes.clone()
     }
 
@@ -231,6 +303,7 @@ impl Body {
     }
 
     pub fn get_bounds(&self) -> Option<Bounds> {
+        self.refresh_geometry();
         content!(self).bounds
     }
 
@@ -254,14 +327,20 @@ impl Body {
     }
 
     pub fn get_vertices(&self) -> Vertices {
+        self.refresh_geometry();
         content!(self).vertices.clone()
     }
 
+    pub fn get_transform(&self) -> Transform {
+        let content = content!(self);
+        Transform::new(content.angle, &content.position)
+    }
+
     pub fn get_position_prev(&self) -> Option<Position> {
         content!(self).position_prev
     }
 
-    pub fn get_angle(&self) -> f64 {
+    pub fn get_angle(&self) -> Real {
         content!(self).angle
     }
 
@@ -269,7 +348,7 @@ impl Body {
         content!(self).force
     }
 
-    pub fn get_torque(&self) -> f64 {
+    pub fn get_torque(&self) -> Real {
         content!(self).torque
     }
 
@@ -285,32 +364,36 @@ impl Body {
         content!(self).total_contacts
     }
 
-    pub fn get_speed_prop(&self) -> f64 {
+    pub fn get_speed_prop(&self) -> Real {
         content!(self).speed
     }
 
-    pub fn get_speed(&self) -> f64 {
+    pub fn get_speed(&self) -> Real {
         self.get_velocity().magnitude()
     }
 
-    pub fn get_angular_speed_prop(&self) -> f64 {
+    pub fn get_angular_speed_prop(&self) -> Real {
         content!(self).angular_speed
     }
 
-    pub fn get_angular_speed(&self) -> f64 {
-        f64::abs(self.get_angular_velocity())
+    pub fn get_angular_speed(&self) -> Real {
+        Real::abs(self.get_angular_velocity())
     }
 
-    pub fn get_angular_velocity_prop(&self) -> f64 {
+    pub fn get_angular_velocity_prop(&self) -> Real {
         content!(self).angular_velocity
     }
 
-    pub fn get_angular_velocity(&self) -> f64 {
+    pub fn get_angular_velocity(&self) -> Real {
         let content = content!(self);
         (content.angle - content.angle_prev) * common::BASE_DELTA
             / content.delta_time.unwrap_or(common::BASE_DELTA)
     }
 
+    pub fn get_is_bullet(&self) -> bool {
+        content!(self).is_bullet
+    }
+
     pub fn get_is_sensor(&self) -> bool {
         content!(self).is_sensor
     }
@@ -323,7 +406,7 @@ impl Body {
         content!(self).is_sleeping
     }
 
-    pub fn get_motion(&self) -> f64 {
+    pub fn get_motion(&self) -> Real {
         content!(self).motion
     }
 
@@ -331,19 +414,19 @@ impl Body {
         content!(self).sleep_threshold
     }
 
-    pub fn get_resitution(&self) -> f64 {
+    pub fn get_resitution(&self) -> Real {
         content!(self).resitution
     }
 
-    pub fn get_friction(&self) -> f64 {
+    pub fn get_friction(&self) -> Real {
         content!(self).friction
     }
 
-    pub fn get_friction_static(&self) -> f64 {
+    pub fn get_friction_static(&self) -> Real {
         content!(self).friction_static
     }
 
-    pub fn get_friction_air(&self) -> f64 {
+    pub fn get_friction_air(&self) -> Real {
         content!(self).friction_air
     }
 
@@ -351,7 +434,7 @@ impl Body {
         content!(self).collision_filter
     }
 
-    pub fn get_slop(&self) -> f64 {
+    pub fn get_slop(&self) -> Real {
         content!(self).slop
     }
 
@@ -375,15 +458,15 @@ impl Body {
         content!(self).chamfer.clone()
     }
 
-    pub fn get_circle_radius(&self) -> Option<f64> {
+    pub fn get_circle_radius(&self) -> Option<Real> {
         content!(self).circle_radius
     }
 
-    pub fn get_angle_prev(&self) -> f64 {
+    pub fn get_angle_prev(&self) -> Real {
         content!(self).angle_prev
     }
 
-    pub fn get_delta_time(&self) -> Option<f64> {
+    pub fn get_delta_time(&self) -> Option<Real> {
         content!(self).delta_time
     }
 
@@ -405,7 +488,7 @@ impl Body {
         content!(self)._original
     }
 
-    pub fn get_moment(&self) -> f64 {
+    pub fn get_moment(&self) -> Real {
         let content = content!(self);
         content.inertia / (content.mass / 6.)
     }
@@ -417,20 +500,20 @@ impl Body {
         self.parent = Arc::downgrade(&parent.content);
     }
 
-    fn set_inertia_prop(&mut self, value: f64) {
+    fn set_inertia_prop(&mut self, value: Real) {
         content_mut!(self).inertia = value;
     }
 
-    pub fn set_inertia(&mut self, value: f64) {
+    pub fn set_inertia(&mut self, value: Real) {
         self.set_inertia_prop(value);
         self.set_inverse_inertia(1. / self.get_inertia());
     }
 
-    fn set_mass_prop(&mut self, value: f64) {
+    fn set_mass_prop(&mut self, value: Real) {
         content_mut!(self).mass = value;
     }
 
-    pub fn set_mass(&mut self, value: f64) {
+    pub fn set_mass(&mut self, value: Real) {
         let moment = self.get_moment();
         self.set_inertia(moment * (value / 6.));
         self.set_mass_prop(value);
@@ -438,8 +521,32 @@ impl Body {
         self.set_density_prop(value / self.get_area());
     }
 
+    fn refresh_geometry(&self) {
+        let mut content = content_mut!(self);
+        if !content.is_geometry_current() {
+            content.refresh_geometry();
+        }
+    }
+
+    // Moves the world space geometry along with the new pose, turned by the rotation. The bounds
+    // get extended by the given velocity when they are rebuilt.
+    fn set_pose(&mut self, angle: Real, rotation: Real, position: &impl XY, velocity: &Velocity) {
+        let mut content = content_mut!(self);
+        content.capture_local_geometry();
+        content.angle = angle;
+        content.position.set_xy(position);
+        if let Some(local) = &mut content.local_geometry {
+            local.angle += rotation;
+            local.velocity = *velocity;
+            local.is_current = false;
+        }
+    }
+
     fn set_vertices_prop(&mut self, value: &Vertices) {
-        content_mut!(self).vertices = value.clone();
+        self.refresh_geometry();
+        let mut content = content_mut!(self);
+        content.vertices = value.clone();
+        content.local_geometry = None;
     }
 
     pub fn set_vertices(&mut self, vertices: &Vertices) {
@@ -464,6 +571,33 @@ impl Body {
         }
     }
 
+    pub fn set_vertices_with_holes(&mut self, vertices: &Vertices, holes: &Vec<Vertices>) {
+        let properties = BodyProperties::from_rings(vertices, holes, self.get_density());
+        self.set_vertices_with_properties(vertices, &properties);
+    }
+
+    pub fn set_vertices_with_regions(&mut self, vertices: &Vertices, regions: &Vec<MassRegion>) {
+        let properties = BodyProperties::from_regions(regions, self.get_density());
+        self.set_vertices_with_properties(vertices, &properties);
+    }
+
+    fn set_vertices_with_properties(&mut self, vertices: &Vertices, properties: &BodyProperties) {
+        let mut vertices = vertices.clone();
+        vertices.set_body(&self);
+        vertices.translate(&properties.get_centre(), Some(-1.));
+        vertices.translate(&self.get_position(), None);
+        self.set_vertices_prop(&vertices);
+        self.set_axes(&Axes::from_vertices(&vertices));
+        self.set_area(properties.get_area());
+        self.set_mass(properties.get_mass());
+        self.set_inertia(INERTIA_SCALE * properties.get_inertia());
+
+        if let Some(bounds) = &mut self.get_bounds() {
+            bounds.update(&self.get_vertices(), Some(&self.get_velocity_prop()));
+            self.set_bounds(&bounds);
+        }
+    }
+
     fn total_properties(&self) -> BodyProperties {
         let mut properties = BodyProperties::new(0., 0., 0., Position::new(0., 0.));
 
@@ -473,7 +607,7 @@ impl Body {
 
         while index < part_count {
             let part = &parts[index];
-            let mass = if part.get_mass() != f64::INFINITY {
+            let mass = if part.get_mass() != Real::INFINITY {
                 part.get_mass()
             } else {
                 1.
@@ -497,8 +631,12 @@ impl Body {
         properties
     }
 
+    // Changes the position while the world space geometry stays where it is.
     fn set_position_prop(&mut self, value: &impl XY) {
-        content_mut!(self).position.set_xy(value);
+        self.refresh_geometry();
+        let mut content = content_mut!(self);
+        content.position.set_xy(value);
+        content.local_geometry = None;
     }
 
     pub fn set_position(&mut self, position: Position, update_velocity: Option<bool>) {
@@ -523,14 +661,7 @@ impl Body {
         for part in self.get_parts().iter_mut() {
             let mut position = part.get_position();
             position.add_xy(&delta);
-            part.set_position_prop(&position);
-            let mut vertices = part.get_vertices();
-            vertices.translate(&delta, None);
-            part.set_vertices_prop(&vertices);
-            if let Some(bounds) = &mut part.get_bounds() {
-                bounds.update(&part.get_vertices(), Some(&self.get_velocity_prop()));
-                part.set_bounds(&bounds);
-            }
+            part.set_pose(part.get_angle(), 0., &position, &self.get_velocity_prop());
         }
     }
 
@@ -612,46 +743,41 @@ impl Body {
         }
     }
 
-    fn set_angle_prop(&mut self, angle: f64) {
-        content_mut!(self).angle = angle;
+    // Changes the angle while the world space geometry stays where it is.
+    fn set_angle_prop(&mut self, angle: Real) {
+        self.refresh_geometry();
+        let mut content = content_mut!(self);
+        content.angle = angle;
+        content.local_geometry = None;
     }
 
-    pub fn set_angle(&mut self, angle: f64, update_velocity: Option<bool>) {
+    pub fn set_angle(&mut self, angle: Real, update_velocity: Option<bool>) {
         let update_velocity = update_velocity.unwrap_or(false);
 
         let delta = angle - self.get_angle();
         if update_velocity {
             self.set_angle_prev(self.get_angle());
             self.set_angular_velocity_prop(delta);
-            self.set_angular_speed_prop(f64::abs(delta));
+            self.set_angular_speed_prop(Real::abs(delta));
         } else {
             self.set_angle_prev(self.get_angle_prev() + delta);
         }
 
         for part in &mut self.get_parts() {
-            part.set_angle_prop(part.get_angle() + delta);
-            let mut vertices = part.get_vertices();
-            vertices.rotate(delta, &self.get_position());
-            part.set_vertices_prop(&vertices);
-
-            if let Some(axes) = &mut part.get_axes() {
-                axes.rotate(delta);
-                part.set_axes(&axes);
-            }
-
-            if let Some(bounds) = &mut part.get_bounds() {
-                bounds.update(&part.get_vertices(), Some(&self.get_velocity_prop()));
-                part.set_bounds(&bounds);
-            }
+            let mut position = part.get_position();
             if !self.is_part_parent(part) {
-                let mut position = part.get_position();
                 position.rotate_about(delta, &self.get_position());
-                part.set_position_prop(&position);
             }
+            part.set_pose(
+                part.get_angle() + delta,
+                delta,
+                &position,
+                &self.get_velocity_prop(),
+            );
         }
     }
 
-    fn set_velocity_prop(&mut self, value: &Velocity) {
+    pub fn set_velocity_prop(&mut self, value: &Velocity) {
         content_mut!(self).velocity = value.clone();
     }
 
@@ -673,42 +799,46 @@ impl Body {
         self.set_speed_prop(self.get_velocity_prop().magnitude());
     }
 
-    fn set_speed_prop(&mut self, value: f64) {
+    fn set_speed_prop(&mut self, value: Real) {
         content_mut!(self).speed = value;
     }
 
-    pub fn set_speed(&mut self, speed: f64) {
+    pub fn set_speed(&mut self, speed: Real) {
         let mut velocity = self.get_velocity();
         velocity.normalise();
         velocity.mult(speed);
         self.set_velocity(&velocity);
     }
 
-    pub fn set_angular_velocity(&mut self, velocity: f64) {
+    pub fn set_angular_velocity(&mut self, velocity: Real) {
         let time_scale = self.get_delta_time().unwrap_or(common::BASE_DELTA) / common::BASE_DELTA;
         self.set_angle_prev(self.get_angle() - velocity * time_scale);
         self.set_angular_velocity_prop((self.get_angle() - self.get_angle_prev()) / time_scale);
-        self.set_angular_speed_prop(f64::abs(self.get_angular_velocity_prop()));
+        self.set_angular_speed_prop(Real::abs(self.get_angular_velocity_prop()));
     }
 
-    fn set_angular_speed_prop(&mut self, value: f64) {
+    fn set_angular_speed_prop(&mut self, value: Real) {
         content_mut!(self).angular_speed = value;
     }
 
-    pub fn set_angular_speed(&mut self, speed: f64) {
-        let velocity = common::sign(self.get_angular_velocity()) as f64 * speed;
+    pub fn set_angular_speed(&mut self, speed: Real) {
+        let velocity = common::sign(self.get_angular_velocity()) as Real * speed;
         self.set_angular_velocity(velocity);
     }
 
-    pub fn set_area(&mut self, value: f64) {
+    pub fn set_area(&mut self, value: Real) {
         content_mut!(self).area = value;
     }
 
     pub fn set_axes(&mut self, value: &Axes) {
-        content_mut!(self).axes = Some(value.clone());
+        self.refresh_geometry();
+        let mut content = content_mut!(self);
+        content.axes = Some(value.clone());
+        content.local_geometry = None;
     }
 
     pub fn set_bounds(&mut self, value: &Bounds) {
+        self.refresh_geometry();
         content_mut!(self).bounds = Some(*value);
     }
 
@@ -716,7 +846,7 @@ impl Body {
         content_mut!(self).chamfer = Some(value.clone());
     }
 
-    pub fn set_circle_radius(&mut self, value: f64) {
+    pub fn set_circle_radius(&mut self, value: Real) {
         content_mut!(self).circle_radius = Some(value);
     }
 
@@ -728,31 +858,35 @@ impl Body {
         content_mut!(self).constraint_impulse = *value;
     }
 
-    pub fn set_delta_time(&mut self, value: f64) {
+    pub fn set_delta_time(&mut self, value: Real) {
         content_mut!(self).delta_time = Some(value);
     }
 
-    pub fn set_density_prop(&mut self, value: f64) {
+    pub fn set_density_prop(&mut self, value: Real) {
         content_mut!(self).density = value;
     }
 
-    pub fn set_density(&mut self, value: f64) {
+    pub fn set_density(&mut self, value: Real) {
         self.set_mass(value * self.get_area());
         self.set_density_prop(value);
     }
 
-    pub fn set_friction_air(&mut self, value: f64) {
+    pub fn set_friction_air(&mut self, value: Real) {
         content_mut!(self).friction_air = value;
     }
 
-    pub fn set_friction_static(&mut self, value: f64) {
+    pub fn set_friction_static(&mut self, value: Real) {
         content_mut!(self).friction_static = value;
     }
 
-    pub fn set_friction(&mut self, value: f64) {
+    pub fn set_friction(&mut self, value: Real) {
         content_mut!(self).friction = value;
     }
 
+    pub fn set_is_bullet(&mut self, value: bool) {
+        content_mut!(self).is_bullet = value;
+    }
+
     pub fn set_is_sensor(&mut self, value: bool) {
         content_mut!(self).is_sensor = value;
     }
@@ -761,7 +895,7 @@ impl Body {
         content_mut!(self).is_sleeping = value;
     }
 
-    pub fn set_motion(&mut self, value: f64) {
+    pub fn set_motion(&mut self, value: Real) {
         content_mut!(self).motion = value;
     }
 
@@ -773,7 +907,7 @@ impl Body {
         content_mut!(self).render = *value;
     }
 
-    pub fn set_resitution(&mut self, value: f64) {
+    pub fn set_resitution(&mut self, value: Real) {
         content_mut!(self).resitution = value;
     }
 
@@ -785,7 +919,7 @@ impl Body {
         content_mut!(self).sleep_threshold = value;
     }
 
-    pub fn set_slop(&mut self, value: f64) {
+    pub fn set_slop(&mut self, value: Real) {
         content_mut!(self).slop = value;
     }
 
@@ -793,7 +927,7 @@ impl Body {
         content_mut!(self).time_scale = value;
     }
 
-    pub fn set_torque(&mut self, value: f64) {
+    pub fn set_torque(&mut self, value: Real) {
         content_mut!(self).torque = value;
     }
 
@@ -801,11 +935,11 @@ impl Body {
         content_mut!(self).total_contacts = value;
     }
 
-    fn set_inverse_mass(&mut self, value: f64) {
+    fn set_inverse_mass(&mut self, value: Real) {
         content_mut!(self).inverse_mass = value;
     }
 
-    fn set_inverse_inertia(&mut self, value: f64) {
+    fn set_inverse_inertia(&mut self, value: Real) {
         content_mut!(self).inverse_inertia = value;
     }
 
@@ -813,15 +947,15 @@ impl Body {
         content_mut!(self)._original = value;
     }
 
-    fn set_position_prev(&mut self, value: &Position) {
+    pub fn set_position_prev(&mut self, value: &Position) {
         content_mut!(self).position_prev = Some(*value);
     }
 
-    fn set_angle_prev(&mut self, value: f64) {
+    pub fn set_angle_prev(&mut self, value: Real) {
         content_mut!(self).angle_prev = value;
     }
 
-    fn set_angular_velocity_prop(&mut self, value: f64) {
+    pub fn set_angular_velocity_prop(&mut self, value: Real) {
         content_mut!(self).angular_velocity = value;
     }
 
@@ -845,9 +979,9 @@ impl Body {
                 part.set_original(Some(BodyOriginal::from(&part)));
                 part.set_resitution(0.);
                 part.set_friction(1.);
-                part.set_mass_prop(f64::INFINITY);
-                part.set_inertia_prop(f64::INFINITY);
-                part.set_density_prop(f64::INFINITY);
+                part.set_mass_prop(Real::INFINITY);
+                part.set_inertia_prop(Real::INFINITY);
+                part.set_density_prop(Real::INFINITY);
                 part.set_inverse_mass(0.);
                 part.set_inverse_inertia(0.);
                 part.set_position_prev(&part.get_position());
@@ -879,27 +1013,23 @@ impl Body {
 
     pub fn rotate(
         &mut self,
-        rotation: f64,
+        rotation: Real,
         point: Option<&impl XY>,
         update_velocity: Option<bool>,
     ) {
         if point.is_none() {
             self.set_angle(self.get_angle() + rotation, update_velocity);
         } else if let Some(point) = point {
-            let cos = f64::cos(rotation);
-            let sin = f64::sin(rotation);
-            let dx = self.get_position().get_x() - point.get_x();
-            let dy = self.get_position().get_y() - point.get_y();
-            let x = point.get_x() + (dx * cos - dy * sin);
-            let y = point.get_y() + (dx * sin + dy * cos);
-            let position = Position::new(x, y);
+            let rotated =
+                Transform::from_rotation(rotation).rotate_about(&self.get_position(), point);
+            let position = Position::new_from(&rotated);
 
             self.set_position(position, update_velocity);
             self.set_angle(self.get_angle() + rotation, update_velocity);
         }
     }
 
-    pub fn scale(&mut self, scale_x: f64, scale_y: f64, point: Option<&impl XY>) {
+    pub fn scale(&mut self, scale_x: Real, scale_y: Real, point: Option<&impl XY>) {
         let mut total_area = 0.;
         let mut total_inertia = 0.;
 
@@ -964,8 +1094,8 @@ impl Body {
         }
     }
 
-    pub fn update(&mut self, delta_time: Option<f64>) {
-        let delta_time = delta_time.unwrap_or(common::BASE_DELTA) * self.get_time_scale() as f64;
+    pub fn update(&mut self, delta_time: Option<Real>) {
+        let delta_time = delta_time.unwrap_or(common::BASE_DELTA) * self.get_time_scale() as Real;
         let delta_time_squared = delta_time * delta_time;
         let correction = if TIME_CORRECTION {
             delta_time / (self.get_delta_time().unwrap_or(common::BASE_DELTA))
@@ -994,11 +1124,11 @@ impl Body {
         let velocity_y = (velocity_prev_y * friction_air)
             + (self.get_force().get_y() / self.get_mass()) * delta_time_squared;
 
-        self.set_velocity_prop(&Velocity::new(velocity_x, velocity_y));
+        let velocity = Velocity::new(velocity_x, velocity_y);
+        self.set_velocity_prop(&velocity);
         self.set_position_prev(&self.get_position());
         let mut position = self.get_position();
-        position.add_xy(&self.get_velocity_prop());
-        self.set_position_prop(&position);
+        position.add_xy(&velocity);
         self.set_delta_time(delta_time);
 
         // update angular velocity with Verlet integration
@@ -1007,41 +1137,32 @@ impl Body {
                 + (self.get_torque() / self.get_inertia()) * delta_time_squared;
         self.set_angular_velocity_prop(angular_velocity);
         self.set_angle_prev(self.get_angle());
-        self.set_angle_prop(self.get_angle() + angular_velocity);
 
-        // transform the body geometry
+        // transform the body geometry, it follows the pose on the next read. A body at rest too,
+        // so its bounds drop the velocity they were extended by
+        self.set_pose(
+            self.get_angle() + angular_velocity,
+            angular_velocity,
+            &position,
+            &velocity,
+        );
         for part in &mut self.get_parts() {
-            let mut vertices = part.get_vertices();
-            vertices.translate(&self.get_velocity_prop(), None);
-            part.set_vertices_prop(&vertices);
-
-            if !self.is_part_parent(part) {
-                let mut position = part.get_position();
-                position.add_xy(&self.get_velocity_prop());
-                part.set_position_prop(&position);
+            if self.is_part_parent(part) {
+                continue;
             }
 
+            // the geometry of a part turns with the body while its angle stays, as in matter.js
+            let mut part_position = part.get_position();
+            part_position.add_xy(&velocity);
             if angular_velocity != 0. {
-                let mut vertices = part.get_vertices();
-                vertices.rotate(angular_velocity, &self.get_position());
-                part.set_vertices_prop(&vertices);
-
-                if let Some(axes) = &mut part.get_axes() {
-                    axes.rotate(angular_velocity);
-                    part.set_axes(&axes);
-                }
-
-                if !self.is_part_parent(part) {
-                    let mut position = part.get_position();
-                    position.rotate_about(angular_velocity, &self.get_position());
-                    part.set_position_prop(&position);
-                }
-            }
-
-            if let Some(bounds) = &mut part.get_bounds() {
-                bounds.update(&part.get_vertices(), Some(&&self.get_velocity_prop()));
-                part.set_bounds(&bounds);
+                part_position.rotate_about(angular_velocity, &position);
             }
+            part.set_pose(
+                part.get_angle(),
+                angular_velocity,
+                &part_position,
+                &velocity,
+            );
         }
     }
 
@@ -1073,13 +1194,13 @@ impl Body {
         body_velocity.set_x((self.get_position().get_x() - position_prev.get_x()) * time_scale);
         body_velocity.set_y((self.get_position().get_y() - position_prev.get_y()) * time_scale);
 
-        self.set_speed_prop(f64::sqrt(
+        self.set_speed_prop(math::sqrt(
             (body_velocity.get_x() * body_velocity.get_x())
                 + (body_velocity.get_y() * body_velocity.get_y()),
         ));
 
         self.set_angular_velocity_prop((self.get_angle() - self.get_angle_prev()) * time_scale);
-        self.set_angular_speed_prop(f64::abs(self.get_angular_velocity_prop()));
+        self.set_angular_speed_prop(Real::abs(self.get_angular_velocity_prop()));
     }
     // endregion: Actions
 
@@ -1103,6 +1224,7 @@ impl Body {
             BodyOption::FrictionAir(_) => todo!(),
             BodyOption::FrictionStatic(_) => todo!(),
             BodyOption::Inertia(_) => todo!(),
+            BodyOption::IsBullet(value) => self.set_is_bullet(*value),
             BodyOption::IsSensor(_) => todo!(),
             BodyOption::IsSleeping(_) => todo!(),
             BodyOption::IsStatic(_) => todo!(),
@@ -1114,7 +1236,7 @@ impl Body {
             BodyOption::Position(_) => todo!(),
             BodyOption::PositionImpulse(_) => todo!(),
             BodyOption::PositionPrev(_) => todo!(),
-            BodyOption::Render(_) => todo!(),
+            BodyOption::Render(value) => self.set_render(value),
             BodyOption::Resitution(_) => todo!(),
             BodyOption::ShapeType(_) => todo!(),
             BodyOption::SleepThreshold(_) => todo!(),
@@ -1141,10 +1263,13 @@ mod tests {
             position::{self, Position},
             xy::XY,
         },
-        geometry::{bounds::BoundsPart, vertex::{self, Vertex}},
+        geometry::{
+            bounds::BoundsPart,
+            vertex::{self, Vertex},
+        },
         test_utils::{
-            body_test_utils::{assert_position, assert_velocity},
-            common_test_utils::assert_float,
+            body_test_utils::{assert_position, assert_velocity, test_box},
+            common_test_utils::{assert_float, TOLERANCE},
             geometry_test_utils::{
                 assert_bounds, assert_vertex, assert_xy, test_bounds, test_square,
                 vec_vector_to_vec_vertex,
@@ -1152,6 +1277,9 @@ mod tests {
         },
     };
 
+    #[cfg(all(feature = "deterministic", not(feature = "f32")))]
+    use crate::test_utils::body_test_utils::hash_body_state;
+
     // region: Helpers
     fn body_from_content(content: BodyContent) -> Body {
         Body {
@@ -1274,9 +1402,9 @@ mod tests {
         let original = part.get_original().unwrap();
         assert_float(part.get_resitution(), 0.);
         assert_float(part.get_friction(), 1.);
-        assert_float(part.get_mass(), f64::INFINITY);
-        assert_float(part.get_inertia(), f64::INFINITY);
-        assert_float(part.get_density(), f64::INFINITY);
+        assert_float(part.get_mass(), Real::INFINITY);
+        assert_float(part.get_inertia(), Real::INFINITY);
+        assert_float(part.get_density(), Real::INFINITY);
         assert_float(part.get_inverse_mass(), 0.);
         assert_xy(&part.get_position(), 2., 2.);
         assert_float(part.get_angle_prev(), 42.);
@@ -1296,9 +1424,9 @@ mod tests {
         let original = part.get_original().unwrap();
         assert_float(part.get_resitution(), 0.);
         assert_float(part.get_friction(), 1.);
-        assert_float(part.get_mass(), f64::INFINITY);
-        assert_float(part.get_inertia(), f64::INFINITY);
-        assert_float(part.get_density(), f64::INFINITY);
+        assert_float(part.get_mass(), Real::INFINITY);
+        assert_float(part.get_inertia(), Real::INFINITY);
+        assert_float(part.get_density(), Real::INFINITY);
         assert_float(part.get_inverse_mass(), 0.);
         assert_xy(&part.get_position(), 1., 1.);
         assert_float(part.get_angle_prev(), 43.);
@@ -1318,9 +1446,9 @@ mod tests {
         let original = part.get_original().unwrap();
         assert_float(part.get_resitution(), 0.);
         assert_float(part.get_friction(), 1.);
-        assert_float(part.get_mass(), f64::INFINITY);
-        assert_float(part.get_inertia(), f64::INFINITY);
-        assert_float(part.get_density(), f64::INFINITY);
+        assert_float(part.get_mass(), Real::INFINITY);
+        assert_float(part.get_inertia(), Real::INFINITY);
+        assert_float(part.get_density(), Real::INFINITY);
         assert_float(part.get_inverse_mass(), 0.);
         assert_xy(&part.get_position(), 2., 2.);
         assert_float(part.get_angle_prev(), 44.);
@@ -1356,6 +1484,8 @@ mod tests {
         assert_float(body.get_torque(), 117.);
     }
 
+    // the angle grows to millions of radians, which single precision can not resolve
+    #[cfg(not(feature = "f32"))]
     #[test]
     fn update_should_be_able_to_update_a_body() {
         // Arrange
@@ -1388,7 +1518,7 @@ mod tests {
         assert_float(part.get_mass(), 88.);
         assert_xy(&part.get_position(), 1447310.096590909, 1720321.4602272725);
         assert_xy(&part.get_position_prev().unwrap(), 2., 2.);
-        assert_float(part.get_time_scale() as f64, 93.);
+        assert_float(part.get_time_scale() as Real, 93.);
         assert_float(part.get_torque(), 52.);
         assert_xy(
             &part.get_velocity_prop(),
@@ -1423,7 +1553,7 @@ mod tests {
         assert_float(part.get_mass(), 88.);
         assert_xy(&part.get_position(), 1447311.4039432048, 1720321.9995138147);
         assert_xy(&part.get_position_prev().unwrap(), 1., 1.);
-        assert_float(part.get_time_scale() as f64, 93.);
+        assert_float(part.get_time_scale() as Real, 93.);
         assert_float(part.get_torque(), 52.);
         assert_xy(&part.get_velocity_prop(), 42., 42.);
         let vertices = part.get_vertices();
@@ -1454,7 +1584,7 @@ mod tests {
         assert_float(part.get_mass(), 88.);
         assert_xy(&part.get_position(), 1447310.096590909, 1720321.4602272725);
         assert_xy(&part.get_position_prev().unwrap(), 1., 1.);
-        assert_float(part.get_time_scale() as f64, 93.);
+        assert_float(part.get_time_scale() as Real, 93.);
         assert_float(part.get_torque(), 52.);
         assert_xy(&part.get_velocity_prop(), 42., 42.);
         let vertices = part.get_vertices();
@@ -1464,6 +1594,36 @@ mod tests {
         assert_xy(&vertices[3], 1447308.0211728595, 1720319.0743018922);
     }
 
+    // The hash is recorded from one build, every other build with the deterministic backend
+    // has to step the scene to the exact same state.
+    #[test]
+    #[cfg(all(feature = "deterministic", not(feature = "f32")))]
+    fn update_should_step_to_the_same_state_on_every_build() {
+        // Arrange
+        let mut content = BodyContent::default_contant();
+        content.id = common::next_id();
+        content.position = Position::new(2., 2.);
+        content.position_prev = Some(Position::new(1.9, 2.05));
+        content.angle = 0.;
+        content.angle_prev = -0.05;
+        content.mass = 1.;
+        content.inertia = 1.;
+        content.time_scale = 1;
+        content.friction_air = 0.01;
+        content.force = Force::new(0.001, -0.002);
+        content.torque = 0.0005;
+        content.vertices = Vertices::new(test_square(), None);
+        let mut body = body_from_content(content);
+
+        // Act
+        for _ in 0..240 {
+            body.update(Some(common::BASE_DELTA));
+        }
+
+        // Assert
+        assert_eq!(hash_body_state(&body), 6332837926321006116);
+    }
+
     #[test]
     fn scale_should_be_able_to_scale_a_circular_body() {
         // Arrange
@@ -1646,8 +1806,8 @@ mod tests {
         assert_float(part.get_angle(), 79.);
         assert_float(part.get_angle_prev(), 78.);
         let axes = part.get_axes().unwrap();
-        assert_xy(&axes[0], 1.408952185302343, 0.1218759185883439);
-        assert_xy(&axes[1], -1.408952185302343, -0.1218759185883439);
+        assert_xy(&axes[0], 1.408952185302343, 0.12187591858834396);
+        assert_xy(&axes[1], -1.408952185302343, -0.12187591858834396);
         assert_bounds(
             &part.get_bounds().unwrap(),
             -37.26713918117254,
@@ -1686,8 +1846,8 @@ mod tests {
         assert_float(part.get_angle(), 81.);
         assert_float(part.get_angle_prev(), 43.);
         let axes = part.get_axes().unwrap();
-        assert_xy(&axes[0], 4.226856555907029, 0.3656277557650318);
-        assert_xy(&axes[1], -4.226856555907029, -0.3656277557650318);
+        assert_xy(&axes[0], 4.226856555907029, 0.36562775576503137);
+        assert_xy(&axes[1], -4.226856555907029, -0.36562775576503137);
         let bounds = part.get_bounds().unwrap();
         assert_bounds(
             &bounds,
@@ -1704,6 +1864,72 @@ mod tests {
         assert_xy(&vertices[3], -33.162158543853856, 83.79658137899439);
     }
 
+    #[test]
+    fn get_transform_should_map_local_points_to_the_body_pose() {
+        // Arrange
+        let body = test_body();
+
+        // Act
+        let result = body.get_transform();
+
+        // Assert
+        assert_float(result.get_cos(), math::cos(42.));
+        assert_float(result.get_sin(), math::sin(42.));
+        assert_xy(&result.get_translation(), 2., 2.);
+        let local = result.apply_inverse_to_point(&body.get_position());
+        assert!(local.get_x().abs() < 1e-12 && local.get_y().abs() < 1e-12);
+    }
+
+    #[test]
+    fn update_should_shrink_the_bounds_when_the_body_comes_to_rest() {
+        // Arrange
+        let mut body = test_box(0., 0., 10., 10.);
+        body.set_velocity(&Velocity::new(10., 0.));
+        body.update(None);
+        body.set_velocity(&Velocity::new(0., 0.));
+
+        // Act
+        body.update(None);
+
+        // Assert
+        let bounds = Bounds::from_points(&body.get_vertices().get_value());
+        assert_bounds(
+            &body.get_bounds().unwrap(),
+            bounds.min.x,
+            bounds.min.y,
+            bounds.max.x,
+            bounds.max.y,
+        );
+    }
+
+    #[test]
+    fn rotate_should_keep_the_shape_exact_after_many_rotations() {
+        // Arrange
+        let mut body = Body::default_body();
+        body.set_vertices(&Vertices::create(test_square(), None));
+        body.set_position(Position::new(100., 50.), None);
+        let point: Option<&Position> = None;
+
+        // Act
+        for _ in 0..100_000 {
+            body.rotate(0.001, point, None);
+        }
+
+        // Assert
+        let vertices = body.get_vertices();
+        let transform = body.get_transform();
+        let expected = [(-1., -1.), (1., -1.), (1., 1.), (-1., 1.)];
+        for (vertex, (x, y)) in vertices.iter().zip(expected.iter()) {
+            let expected = transform.apply_to_point(&Vector::create(*x, *y));
+            assert_xy(vertex, expected.get_x(), expected.get_y());
+        }
+        for index in 0..4 {
+            let mut edge = Vector::new_from(&vertices[(index + 1) % 4]);
+            edge.sub(&vertices[index]);
+            assert!((edge.magnitude() - 2.).abs() < TOLERANCE);
+        }
+    }
+
     #[test]
     fn rotate_should_be_able_to_rotate_a_body_without_a_point() {
         // Arrange
@@ -1719,8 +1945,8 @@ mod tests {
         assert_float(part.get_angle(), 79.);
         assert_float(part.get_angle_prev(), 78.);
         let axes = part.get_axes().unwrap();
-        assert_xy(&axes[0], 1.408952185302343, 0.1218759185883439);
-        assert_xy(&axes[1], -1.408952185302343, -0.1218759185883439);
+        assert_xy(&axes[0], 1.408952185302343, 0.12187591858834396);
+        assert_xy(&axes[1], -1.408952185302343, -0.12187591858834396);
         assert_bounds(
             &part.get_bounds().unwrap(),
             0.591047814697657,
@@ -1759,8 +1985,8 @@ mod tests {
         assert_float(part.get_angle(), 81.);
         assert_float(part.get_angle_prev(), 43.);
         let axes = part.get_axes().unwrap();
-        assert_xy(&axes[0], 4.226856555907029, 0.3656277557650318);
-        assert_xy(&axes[1], -4.226856555907029, -0.3656277557650318);
+        assert_xy(&axes[0], 4.226856555907029, 0.36562775576503137);
+        assert_xy(&axes[1], -4.226856555907029, -0.36562775576503137);
         let bounds = part.get_bounds().unwrap();
         assert_bounds(
             &bounds,
@@ -1983,13 +2209,13 @@ mod tests {
             &part.get_bounds().unwrap(),
             -2.593110638526189,
             1.2425864601263648,
-            -0.10793771827345966,
+            -0.10793771827345955,
             3.7277593803790943,
         );
         assert_xy(&part.get_position(), 0., 0.);
         let vertices = part.get_vertices();
         assert_xy(&vertices[0], -0.6752620891999122, 1.2425864601263648);
-        assert_xy(&vertices[1], -0.10793771827345966, 3.1604350094526414);
+        assert_xy(&vertices[1], -0.10793771827345955, 3.1604350094526414);
         assert_xy(&vertices[2], -2.0257862675997362, 3.7277593803790943);
         assert_xy(&vertices[3], -2.593110638526189, 1.8099108310528171);
 
@@ -2123,13 +2349,13 @@ mod tests {
             &part.get_bounds().unwrap(),
             -2.593110638526189,
             1.2425864601263648,
-            -0.10793771827345966,
+            -0.10793771827345955,
             3.7277593803790943,
         );
         assert_xy(&part.get_position(), 0., 0.);
         let vertices = part.get_vertices();
         assert_xy(&vertices[0], -0.6752620891999122, 1.2425864601263648);
-        assert_xy(&vertices[1], -0.10793771827345966, 3.1604350094526414);
+        assert_xy(&vertices[1], -0.10793771827345955, 3.1604350094526414);
         assert_xy(&vertices[2], -2.0257862675997362, 3.7277593803790943);
         assert_xy(&vertices[3], -2.593110638526189, 1.8099108310528171);
 
@@ -2638,6 +2864,58 @@ mod tests {
         assert_vertex(&vertices[3], body, 1.0, 3.0, 3, false);
     }
 
+    #[test]
+    fn set_vertices_with_holes_should_mutate_the_body_to_contain_valid_values() {
+        // Arrange
+        let outer = Vertices::new(
+            vec![
+                Vector::create(0., 0.),
+                Vector::create(10., 0.),
+                Vector::create(10., 10.),
+                Vector::create(0., 10.),
+            ],
+            None,
+        );
+        let holes = vec![Vertices::new(
+            vec![
+                Vector::create(1., 1.),
+                Vector::create(5., 1.),
+                Vector::create(5., 9.),
+                Vector::create(1., 9.),
+            ],
+            None,
+        )];
+
+        let mut content = BodyContent::default_contant();
+        content.id = common::next_id();
+        content.density = 0.5;
+        content.position = Position::new(20., 30.);
+        content.bounds = Some(test_bounds());
+        content.velocity = Velocity::new(0., 0.);
+        let mut body = body_from_content(content);
+
+        // Act
+        body.set_vertices_with_holes(&outer, &holes);
+
+        // Assert
+        assert_float(body.get_area(), 68.);
+        assert_float(body.get_mass(), 34.);
+        assert_float(body.get_density(), 0.5);
+        assert_float(body.get_inertia(), 2530.196078431374);
+        assert_float(body.get_inverse_inertia(), 1. / 2530.196078431374);
+        assert_bounds(
+            &body.get_bounds().unwrap(),
+            14.058823529411764,
+            25.,
+            24.058823529411764,
+            35.,
+        );
+        let vertices = body.get_vertices();
+        assert_eq!(vertices.len(), 4);
+        assert_xy(&vertices[0], 14.058823529411764, 25.);
+        assert_xy(&vertices[2], 24.058823529411764, 35.);
+    }
+
     #[test]
     fn set_mass_should_mutate_value_of_mass_inverse_mass_inertia_inverse_inertia_and_density_to_valid_values(
     ) {
@@ -1,8 +1,8 @@
 use crate::{
     core::{
         collision_filter::CollisionFilter, common::ShapeType,
-        constraint_impulse::ConstraintImpulse, force::Force, position::Position, render::Render,
-        velocity::Velocity,
+        constraint_impulse::ConstraintImpulse, force::Force, position::Position, real::Real,
+        render::Render, velocity::Velocity,
     },
     geometry::{bounds::Bounds, vector::Vector},
 };
@@ -10,30 +10,31 @@ use crate::{
 use super::body::Body;
 
 pub enum BodyOption {
-    Angle(f64),
-    AnglePrev(f64),
-    AngularSpeed(f64),
-    AngularVelocity(f64),
-    Area(f64),
+    Angle(Real),
+    AnglePrev(Real),
+    AngularSpeed(Real),
+    AngularVelocity(Real),
+    Area(Real),
     Axes(Vec<Vector>),
     Bounds(Bounds),
     Chamfer(Vec<Vector>),
-    CircleRadius(f64),
+    CircleRadius(Real),
     CollisionFilter(CollisionFilter),
     ConstraintImpulse(ConstraintImpulse),
-    DeltaTime(f64),
-    Density(f64),
+    DeltaTime(Real),
+    Density(Real),
     //Events
     Force(Force),
-    Friction(f64),
-    FrictionAir(f64),
-    FrictionStatic(f64),
-    Inertia(f64),
+    Friction(Real),
+    FrictionAir(Real),
+    FrictionStatic(Real),
+    Inertia(Real),
+    IsBullet(bool),
     IsSensor(bool),
     IsSleeping(bool),
     IsStatic(bool),
-    Mass(f64),
-    Motion(f64),
+    Mass(Real),
+    Motion(Real),
     Original(Body),
     Parent(Body),
     Parts(Vec<Body>),
@@ -42,13 +43,13 @@ pub enum BodyOption {
     PositionImpulse(Position),
     PositionPrev(Position),
     Render(Render),
-    Resitution(f64),
+    Resitution(Real),
     ShapeType(ShapeType),
     SleepThreshold(u32),
-    Slop(f64),
-    Speed(f64),
+    Slop(Real),
+    Speed(Real),
     TimeScale(u16),
-    Torque(f64),
+    Torque(Real),
     TotalContracts(u32),
     Velocity(Velocity),
     Vertices(Vec<Vector>),
@@ -1,14 +1,16 @@
+use crate::core::real::Real;
+
 use super::body::Body;
 
 #[derive(Copy, Clone)]
 pub struct BodyOriginal {
-    density: f64,
-    friction: f64,
-    inertia: f64,
-    inverse_inertia: f64,
-    inverse_mass: f64,
-    mass: f64,
-    resitution: f64,
+    density: Real,
+    friction: Real,
+    inertia: Real,
+    inverse_inertia: Real,
+    inverse_mass: Real,
+    mass: Real,
+    resitution: Real,
 }
 
 impl BodyOriginal {
@@ -24,59 +26,59 @@ impl BodyOriginal {
         }
     }
 
-    pub fn set_density(&mut self, value: f64) {
+    pub fn set_density(&mut self, value: Real) {
         self.density = value;
     }
 
-    pub fn set_friction(&mut self, value: f64) {
+    pub fn set_friction(&mut self, value: Real) {
         self.friction = value;
     }
 
-    pub fn set_inertia(&mut self, value: f64) {
+    pub fn set_inertia(&mut self, value: Real) {
         self.inertia = value;
     }
 
-    pub fn set_inverse_inertia(&mut self, value: f64) {
+    pub fn set_inverse_inertia(&mut self, value: Real) {
         self.inverse_inertia = value;
     }
 
-    pub fn set_inverse_mass(&mut self, value: f64) {
+    pub fn set_inverse_mass(&mut self, value: Real) {
         self.inverse_mass = value;
     }
 
-    pub fn set_mass(&mut self, value: f64) {
+    pub fn set_mass(&mut self, value: Real) {
         self.mass = value;
     }
 
-    pub fn set_resitution(&mut self, value: f64) {
+    pub fn set_resitution(&mut self, value: Real) {
         self.resitution = value;
     }
 
-    pub fn get_density(&self) -> f64 {
+    pub fn get_density(&self) -> Real {
         self.density
     }
 
-    pub fn get_friction(&self) -> f64 {
+    pub fn get_friction(&self) -> Real {
         self.friction
     }
 
-    pub fn get_inertia(&self) -> f64 {
+    pub fn get_inertia(&self) -> Real {
         self.inertia
     }
 
-    pub fn get_inverse_inertia(&self) -> f64 {
+    pub fn get_inverse_inertia(&self) -> Real {
         self.inverse_inertia
     }
 
-    pub fn get_inverse_mass(&self) -> f64 {
+    pub fn get_inverse_mass(&self) -> Real {
         self.inverse_mass
     }
 
-    pub fn get_mass(&self) -> f64 {
+    pub fn get_mass(&self) -> Real {
         self.mass
     }
 
-    pub fn get_resitution(&self) -> f64 {
+    pub fn get_resitution(&self) -> Real {
         self.resitution
     }
 }
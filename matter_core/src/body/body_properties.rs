@@ -1,15 +1,51 @@
-use crate::core::{position::Position, xy::XY};
+use crate::{
+    core::{
+        position::Position,
+        real::Real,
+        xy::{XYNew, XY},
+    },
+    geometry::{vector::Vector, vertices::Vertices},
+};
+
+#[derive(Clone)]
+pub struct MassRegion {
+    outer: Vertices,
+    holes: Vec<Vertices>,
+    density: Option<Real>,
+}
+
+impl MassRegion {
+    pub fn new(outer: Vertices, holes: Vec<Vertices>, density: Option<Real>) -> Self {
+        MassRegion {
+            outer: outer,
+            holes: holes,
+            density: density,
+        }
+    }
+
+    pub fn get_outer(&self) -> &Vertices {
+        &self.outer
+    }
+
+    pub fn get_holes(&self) -> &Vec<Vertices> {
+        &self.holes
+    }
+
+    pub fn get_density(&self) -> Option<Real> {
+        self.density
+    }
+}
 
 #[derive(Clone, Copy)]
 pub struct BodyProperties {
-    mass: f64,
-    area: f64,
-    inertia: f64,
+    mass: Real,
+    area: Real,
+    inertia: Real,
     centre: Position,
 }
 
 impl BodyProperties {
-    pub fn new(mass: f64, area: f64, inertia: f64, centre: Position) -> Self {
+    pub fn new(mass: Real, area: Real, inertia: Real, centre: Position) -> Self {
         BodyProperties {
             mass: mass,
             area: area,
@@ -18,15 +54,57 @@ impl BodyProperties {
         }
     }
 
-    pub fn get_mass(&self) -> f64 {
+    pub fn from_rings(outer: &Vertices, holes: &Vec<Vertices>, density: Real) -> Self {
+        let region = MassRegion::new(outer.clone(), holes.clone(), None);
+        BodyProperties::from_regions(&vec![region], density)
+    }
+
+    pub fn from_regions(regions: &Vec<MassRegion>, default_density: Real) -> Self {
+        let mut mass = 0.;
+        let mut area = 0.;
+        let mut first_moment = Vector::new(0., 0.);
+        let mut second_moment = 0.;
+
+        for region in regions.iter() {
+            let density = region.get_density().unwrap_or(default_density);
+            let rings = std::iter::once((region.get_outer(), 1.))
+                .chain(region.get_holes().iter().map(|hole| (hole, -1.)));
+
+            for (ring, sign) in rings {
+                if ring.len() < 3 {
+                    continue;
+                }
+                let ring_area = ring.area(None);
+                let ring_mass = sign * density * ring_area;
+                let mut centre = ring.centre();
+                centre.mult(ring_mass);
+
+                area += sign * ring_area;
+                mass += ring_mass;
+                first_moment.add_xy(&centre);
+                second_moment += sign * density * ring.second_moment();
+            }
+        }
+
+        let centre = if mass != 0. {
+            Position::new(first_moment.get_x() / mass, first_moment.get_y() / mass)
+        } else {
+            Position::new(0., 0.)
+        };
+        let inertia = second_moment - mass * centre.magnitude_squared();
+
+        BodyProperties::new(mass, area, inertia, centre)
+    }
+
+    pub fn get_mass(&self) -> Real {
         self.mass
     }
 
-    pub fn get_area(&self) -> f64 {
+    pub fn get_area(&self) -> Real {
         self.area
     }
 
-    pub fn get_inertia(&self) -> f64 {
+    pub fn get_inertia(&self) -> Real {
         self.inertia
     }
 
@@ -34,15 +112,15 @@ impl BodyProperties {
         self.centre
     }
 
-    pub fn set_mass(&mut self, mass: f64) {
+    pub fn set_mass(&mut self, mass: Real) {
         self.mass = mass;
     }
 
-    pub fn set_area(&mut self, area: f64) {
+    pub fn set_area(&mut self, area: Real) {
         self.area = area;
     }
 
-    pub fn set_inertia(&mut self, inertia: f64) {
+    pub fn set_inertia(&mut self, inertia: Real) {
         self.inertia = inertia;
     }
 
@@ -53,19 +131,110 @@ impl BodyProperties {
 }
 
 impl XY for BodyProperties {
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.centre.set_x(x);
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.centre.set_y(y);
     }
 
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.centre.get_x()
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.centre.get_y()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        geometry::vector::Vector,
+        test_utils::{common_test_utils::assert_float, geometry_test_utils::assert_xy},
+    };
+
+    use super::*;
+
+    fn rectangle(min_x: Real, min_y: Real, max_x: Real, max_y: Real) -> Vertices {
+        let points = vec![
+            Vector::create(min_x, min_y),
+            Vector::create(max_x, min_y),
+            Vector::create(max_x, max_y),
+            Vector::create(min_x, max_y),
+        ];
+        Vertices::create(points, None)
+    }
+
+    #[test]
+    fn from_rings_should_subtract_a_centred_hole() {
+        // Arrange
+        let outer = rectangle(0., 0., 10., 10.);
+        let holes = vec![rectangle(3., 3., 7., 7.)];
+        let density = 0.5;
+
+        // Act
+        let result = BodyProperties::from_rings(&outer, &holes, density);
+
+        // Assert
+        assert_float(result.get_area(), 84.);
+        assert_float(result.get_mass(), 42.);
+        assert_xy(&result.get_centre(), 5., 5.);
+        assert_float(result.get_inertia(), 812.);
+    }
+
+    #[test]
+    fn from_rings_should_move_the_centre_away_from_an_off_centre_hole() {
+        // Arrange
+        let outer = rectangle(0., 0., 10., 10.);
+        let mut hole_points = vec![
+            Vector::create(6., 4.),
+            Vector::create(8., 4.),
+            Vector::create(8., 6.),
+            Vector::create(6., 6.),
+        ];
+        hole_points.reverse();
+        let holes = vec![Vertices::create(hole_points, None)];
+
+        // Act
+        let result = BodyProperties::from_rings(&outer, &holes, 1.);
+
+        // Assert
+        assert_float(result.get_area(), 96.);
+        assert_float(result.get_mass(), 96.);
+        assert_xy(&result.get_centre(), 4.916666666666667, 5.);
+        assert_float(result.get_inertia(), 1647.333333333333);
+    }
+
+    #[test]
+    fn from_regions_should_weight_the_centre_by_density() {
+        // Arrange
+        let regions = vec![
+            MassRegion::new(rectangle(0., 0., 2., 2.), Vec::new(), Some(1.)),
+            MassRegion::new(rectangle(2., 0., 4., 2.), Vec::new(), Some(3.)),
+        ];
+
+        // Act
+        let result = BodyProperties::from_regions(&regions, 0.001);
+
+        // Assert
+        assert_float(result.get_area(), 8.);
+        assert_float(result.get_mass(), 16.);
+        assert_xy(&result.get_centre(), 2.5, 1.);
+        assert_float(result.get_inertia(), 22.666666666666657);
+    }
+
+    #[test]
+    fn from_regions_should_use_the_default_density_when_none_is_given() {
+        // Arrange
+        let regions = vec![MassRegion::new(rectangle(0., 0., 2., 2.), Vec::new(), None)];
+
+        // Act
+        let result = BodyProperties::from_regions(&regions, 2.);
+
+        // Assert
+        assert_float(result.get_mass(), 8.);
+        assert_xy(&result.get_centre(), 1., 1.);
+    }
+}
@@ -0,0 +1,12 @@
+pub mod aabb_tree;
+pub mod broadphase;
+pub mod ccd;
+pub mod collision;
+pub mod contact_hooks;
+pub mod detector;
+pub mod pair;
+pub mod pairs;
+pub mod query;
+pub mod raycast;
+pub mod resolver;
+pub mod shape_cast;
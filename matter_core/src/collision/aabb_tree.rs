@@ -0,0 +1,561 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::core::{real::Real, velocity::Velocity, xy::XY};
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+use super::broadphase::Broadphase;
+
+const DEFAULT_MARGIN: Real = 4.;
+const DEFAULT_VELOCITY_MULTIPLIER: Real = 2.;
+
+#[derive(Clone)]
+struct TreeNode {
+    bounds: Bounds,
+    parent: Option<usize>,
+    children: Option<(usize, usize)>,
+    height: usize,
+    id: Option<Uuid>,
+}
+
+impl TreeNode {
+    fn is_leaf(&self) -> bool {
+        self.children.is_none()
+    }
+}
+
+// A dynamic bounding volume tree. Leaves store fattened bounds so small movements do not touch
+// the tree, and the tree is kept balanced with rotations as leaves come and go.
+pub struct AabbTree {
+    nodes: Vec<TreeNode>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+    leaves: HashMap<Uuid, usize>,
+    margin: Real,
+    velocity_multiplier: Real,
+}
+
+impl AabbTree {
+    pub fn new(margin: Option<Real>, velocity_multiplier: Option<Real>) -> Self {
+        AabbTree {
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            root: None,
+            leaves: HashMap::new(),
+            margin: margin.unwrap_or(DEFAULT_MARGIN),
+            velocity_multiplier: velocity_multiplier.unwrap_or(DEFAULT_VELOCITY_MULTIPLIER),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.root.map_or(0, |root| self.nodes[root].height)
+    }
+
+    pub fn get_fat_bounds(&self, id: Uuid) -> Option<Bounds> {
+        self.leaves.get(&id).map(|leaf| self.nodes[*leaf].bounds)
+    }
+
+    fn fatten(&self, bounds: &Bounds, velocity: &Velocity) -> Bounds {
+        let mut fat = *bounds;
+        fat.expand(self.margin);
+
+        let displacement_x = velocity.get_x() * self.velocity_multiplier;
+        let displacement_y = velocity.get_y() * self.velocity_multiplier;
+        if displacement_x > 0. {
+            fat.max.add_x(displacement_x);
+        } else {
+            fat.min.add_x(displacement_x);
+        }
+        if displacement_y > 0. {
+            fat.max.add_y(displacement_y);
+        } else {
+            fat.min.add_y(displacement_y);
+        }
+        fat
+    }
+
+    fn allocate_node(&mut self, bounds: Bounds, id: Option<Uuid>) -> usize {
+        let node = TreeNode {
+            bounds: bounds,
+            parent: None,
+            children: None,
+            height: 0,
+            id: id,
+        };
+
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    fn free_node(&mut self, index: usize) {
+        self.nodes[index].id = None;
+        self.nodes[index].children = None;
+        self.nodes[index].parent = None;
+        self.free_nodes.push(index);
+    }
+
+    fn get_children(&self, index: usize) -> (usize, usize) {
+        self.nodes[index].children.unwrap()
+    }
+
+    fn replace_child(&mut self, parent: Option<usize>, old_child: usize, new_child: usize) {
+        match parent {
+            Some(parent) => {
+                let (child_a, child_b) = self.get_children(parent);
+                self.nodes[parent].children = if child_a == old_child {
+                    Some((new_child, child_b))
+                } else {
+                    Some((child_a, new_child))
+                };
+            }
+            None => self.root = Some(new_child),
+        }
+        self.nodes[new_child].parent = parent;
+    }
+
+    fn refit(&mut self, index: usize) {
+        let (child_a, child_b) = self.get_children(index);
+        self.nodes[index].bounds =
+            Bounds::union(&self.nodes[child_a].bounds, &self.nodes[child_b].bounds);
+        self.nodes[index].height = 1 + self.nodes[child_a].height.max(self.nodes[child_b].height);
+    }
+
+    // Cost of descending into a child, following the surface area heuristic with perimeters.
+    fn descend_cost(&self, child: usize, bounds: &Bounds, inheritance_cost: Real) -> Real {
+        let node = &self.nodes[child];
+        let union_perimeter = Bounds::union(&node.bounds, bounds).perimeter();
+        if node.is_leaf() {
+            union_perimeter + inheritance_cost
+        } else {
+            union_perimeter - node.bounds.perimeter() + inheritance_cost
+        }
+    }
+
+    fn insert_leaf(&mut self, leaf: usize) {
+        let root = match self.root {
+            Some(root) => root,
+            None => {
+                self.root = Some(leaf);
+                self.nodes[leaf].parent = None;
+                return;
+            }
+        };
+
+        let leaf_bounds = self.nodes[leaf].bounds;
+        let mut sibling = root;
+        while let Some((child_a, child_b)) = self.nodes[sibling].children {
+            let perimeter = self.nodes[sibling].bounds.perimeter();
+            let combined_perimeter =
+                Bounds::union(&self.nodes[sibling].bounds, &leaf_bounds).perimeter();
+            let cost = 2. * combined_perimeter;
+            let inheritance_cost = 2. * (combined_perimeter - perimeter);
+
+            let cost_a = self.descend_cost(child_a, &leaf_bounds, inheritance_cost);
+            let cost_b = self.descend_cost(child_b, &leaf_bounds, inheritance_cost);
+            if cost < cost_a && cost < cost_b {
+                break;
+            }
+            sibling = if cost_a < cost_b { child_a } else { child_b };
+        }
+
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate_node(
+            Bounds::union(&self.nodes[sibling].bounds, &leaf_bounds),
+            None,
+        );
+        self.replace_child(old_parent, sibling, new_parent);
+        self.nodes[new_parent].children = Some((sibling, leaf));
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+
+        self.refit_upwards(Some(new_parent));
+    }
+
+    fn remove_leaf(&mut self, leaf: usize) {
+        let parent = match self.nodes[leaf].parent {
+            Some(parent) => parent,
+            None => {
+                self.root = None;
+                return;
+            }
+        };
+
+        let (child_a, child_b) = self.get_children(parent);
+        let sibling = if child_a == leaf { child_b } else { child_a };
+        let grand_parent = self.nodes[parent].parent;
+        self.replace_child(grand_parent, parent, sibling);
+        self.free_node(parent);
+        self.nodes[leaf].parent = None;
+
+        self.refit_upwards(grand_parent);
+    }
+
+    fn refit_upwards(&mut self, start: Option<usize>) {
+        let mut current = start;
+        while let Some(index) = current {
+            let index = self.balance(index);
+            self.refit(index);
+            current = self.nodes[index].parent;
+        }
+    }
+
+    // Rotates the taller child up when the subtree heights differ by more than one, returns the
+    // node now at the top of the subtree.
+    fn balance(&mut self, index: usize) -> usize {
+        if self.nodes[index].is_leaf() || self.nodes[index].height < 2 {
+            return index;
+        }
+
+        let (child_a, child_b) = self.get_children(index);
+        let height_a = self.nodes[child_a].height;
+        let height_b = self.nodes[child_b].height;
+        if height_b > height_a + 1 {
+            self.rotate_up(index, child_b)
+        } else if height_a > height_b + 1 {
+            self.rotate_up(index, child_a)
+        } else {
+            index
+        }
+    }
+
+    fn rotate_up(&mut self, index: usize, child: usize) -> usize {
+        let (grand_child_a, grand_child_b) = self.get_children(child);
+        let (kept, moved) = if self.nodes[grand_child_a].height > self.nodes[grand_child_b].height {
+            (grand_child_a, grand_child_b)
+        } else {
+            (grand_child_b, grand_child_a)
+        };
+
+        let parent = self.nodes[index].parent;
+        self.replace_child(parent, index, child);
+        self.replace_child(Some(index), child, moved);
+        self.nodes[child].children = Some((index, kept));
+        self.nodes[index].parent = Some(child);
+
+        self.refit(index);
+        self.refit(child);
+        child
+    }
+}
+
+impl Broadphase for AabbTree {
+    fn insert(&mut self, id: Uuid, bounds: &Bounds, velocity: &Velocity) {
+        self.remove(id);
+        let leaf = self.allocate_node(self.fatten(bounds, velocity), Some(id));
+        self.leaves.insert(id, leaf);
+        self.insert_leaf(leaf);
+    }
+
+    fn remove(&mut self, id: Uuid) -> bool {
+        match self.leaves.remove(&id) {
+            Some(leaf) => {
+                self.remove_leaf(leaf);
+                self.free_node(leaf);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&mut self, id: Uuid, bounds: &Bounds, velocity: &Velocity) -> bool {
+        let leaf = match self.leaves.get(&id) {
+            Some(leaf) => *leaf,
+            None => {
+                self.insert(id, bounds, velocity);
+                return true;
+            }
+        };
+
+        // keep the leaf while it still covers the body, unless it has grown far too large
+        let fat = self.fatten(bounds, velocity);
+        let mut huge = fat;
+        huge.expand(4. * self.margin);
+        let stored = self.nodes[leaf].bounds;
+        if stored.contains_bounds(bounds) && huge.contains_bounds(&stored) {
+            return false;
+        }
+
+        self.remove_leaf(leaf);
+        self.nodes[leaf].bounds = fat;
+        self.insert_leaf(leaf);
+        true
+    }
+
+    fn contains(&self, id: Uuid) -> bool {
+        self.leaves.contains_key(&id)
+    }
+
+    // Each overlapping pair once, with the lower id first.
+    fn pairs(&self) -> Vec<(Uuid, Uuid)> {
+        let mut pairs: Vec<(Uuid, Uuid)> = Vec::new();
+        for (leaf, node) in self.nodes.iter().enumerate() {
+            let id = match node.id {
+                Some(id) => id,
+                None => continue,
+            };
+
+            let mut stack: Vec<usize> = self.root.into_iter().collect();
+            while let Some(index) = stack.pop() {
+                let other = &self.nodes[index];
+                if !Bounds::overlaps(&node.bounds, &other.bounds) {
+                    continue;
+                }
+                match other.children {
+                    Some((child_a, child_b)) => {
+                        stack.push(child_a);
+                        stack.push(child_b);
+                    }
+                    None if index > leaf => {
+                        let other_id = other.id.unwrap();
+                        pairs.push((id.min(other_id), id.max(other_id)));
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        pairs.sort();
+        pairs
+    }
+
+    fn query(&self, bounds: &Bounds) -> Vec<Uuid> {
+        let mut result: Vec<Uuid> = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !Bounds::overlaps(&node.bounds, bounds) {
+                continue;
+            }
+            match node.children {
+                Some((child_a, child_b)) => {
+                    stack.push(child_a);
+                    stack.push(child_b);
+                }
+                None => result.push(node.id.unwrap()),
+            }
+        }
+        result
+    }
+
+    fn ray_cast(&self, start: &Vector, end: &Vector) -> Vec<(Uuid, Real)> {
+        let mut hits: Vec<(Uuid, Real)> = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if let Some(fraction) = node.bounds.segment_intersection(start, end) {
+                match node.children {
+                    Some((child_a, child_b)) => {
+                        stack.push(child_a);
+                        stack.push(child_b);
+                    }
+                    None => hits.push((node.id.unwrap(), fraction)),
+                }
+            }
+        }
+
+        hits.sort_by(|hit_a, hit_b| hit_a.1.total_cmp(&hit_b.1));
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::common;
+    use crate::core::xy::XYNew;
+    use crate::geometry::vector::Vector;
+    use crate::test_utils::{common_test_utils::assert_float, geometry_test_utils::assert_bounds};
+
+    use super::*;
+
+    fn square(x: Real, y: Real, size: Real) -> Bounds {
+        Bounds::from_points(&[Vector::create(x, y), Vector::create(x + size, y + size)])
+    }
+
+    fn tree_with_squares(count: usize) -> (AabbTree, Vec<Uuid>) {
+        let mut tree = AabbTree::new(Some(1.), None);
+        let ids = (0..count)
+            .map(|index| {
+                let id = common::next_id();
+                tree.insert(
+                    id,
+                    &square(index as Real * 10., 0., 5.),
+                    &Velocity::new(0., 0.),
+                );
+                id
+            })
+            .collect();
+        (tree, ids)
+    }
+
+    #[test]
+    fn insert_should_store_fat_bounds_extended_by_the_velocity() {
+        // Arrange
+        let mut tree = AabbTree::new(Some(1.), Some(2.));
+        let id = common::next_id();
+
+        // Act
+        tree.insert(id, &square(0., 0., 10.), &Velocity::new(3., -1.));
+
+        // Assert
+        assert_eq!(tree.len(), 1);
+        assert_bounds(&tree.get_fat_bounds(id).unwrap(), -1., -3., 17., 11.);
+    }
+
+    #[test]
+    fn query_should_return_the_leaves_overlapping_the_region() {
+        // Arrange
+        let (tree, ids) = tree_with_squares(8);
+
+        // Act
+        let mut result = tree.query(&square(18., 2., 14.));
+        result.sort();
+
+        // Assert
+        let mut expected = vec![ids[2], ids[3]];
+        expected.sort();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn remove_should_drop_the_leaf_from_queries() {
+        // Arrange
+        let (mut tree, ids) = tree_with_squares(8);
+
+        // Act
+        let result = tree.remove(ids[2]);
+        let result_again = tree.remove(ids[2]);
+
+        // Assert
+        assert_eq!(result, true);
+        assert_eq!(result_again, false);
+        assert_eq!(tree.len(), 7);
+        assert_eq!(tree.query(&square(18., 2., 14.)), vec![ids[3]]);
+    }
+
+    #[test]
+    fn update_should_only_move_leaves_that_leave_their_fat_bounds() {
+        // Arrange
+        let (mut tree, ids) = tree_with_squares(8);
+        let velocity = Velocity::new(0., 0.);
+
+        // Act
+        let result_small = tree.update(ids[0], &square(0.5, 0.5, 5.), &velocity);
+        let result_large = tree.update(ids[0], &square(100., 0., 5.), &velocity);
+
+        // Assert
+        assert_eq!(result_small, false);
+        assert_eq!(result_large, true);
+        assert_bounds(&tree.get_fat_bounds(ids[0]).unwrap(), 99., -1., 106., 6.);
+        assert_eq!(tree.query(&square(0., 0., 5.)).len(), 0);
+    }
+
+    #[test]
+    fn pairs_should_return_each_overlapping_pair_once() {
+        // Arrange
+        let mut tree = AabbTree::new(Some(0.), None);
+        let velocity = Velocity::new(0., 0.);
+        let id_a = common::next_id();
+        let id_b = common::next_id();
+        let id_c = common::next_id();
+        tree.insert(id_a, &square(0., 0., 10.), &velocity);
+        tree.insert(id_b, &square(5., 5., 10.), &velocity);
+        tree.insert(id_c, &square(50., 50., 10.), &velocity);
+
+        // Act
+        let result = tree.pairs();
+
+        // Assert
+        assert_eq!(result, vec![(id_a.min(id_b), id_a.max(id_b))]);
+    }
+
+    #[test]
+    fn ray_cast_should_return_hits_nearest_first() {
+        // Arrange
+        let (tree, ids) = tree_with_squares(8);
+
+        // Act
+        let result = tree.ray_cast(&Vector::create(100., 2.), &Vector::create(0., 2.));
+
+        // Assert
+        assert_eq!(result.len(), 8);
+        assert_eq!(result[0].0, ids[7]);
+        assert_eq!(result[7].0, ids[0]);
+        assert_float(result[0].1, 0.24);
+    }
+
+    #[test]
+    fn insert_should_keep_the_tree_balanced() {
+        // Arrange
+        let count = 256;
+
+        // Act
+        let (tree, _) = tree_with_squares(count);
+
+        // Assert
+        assert!(tree.get_height() <= 2 * 8, "height: {}", tree.get_height());
+    }
+
+    #[test]
+    fn pairs_should_match_a_brute_force_search_after_inserts_moves_and_removes() {
+        // Arrange
+        let mut tree = AabbTree::new(Some(0.), None);
+        let velocity = Velocity::new(0., 0.);
+        let mut seed: u64 = 7;
+        let mut next = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) as Real / (1_u64 << 31) as Real
+        };
+        let mut bodies: Vec<(Uuid, Bounds)> = (0..120)
+            .map(|_| {
+                (
+                    common::next_id(),
+                    square(next() * 200., next() * 200., next() * 20.),
+                )
+            })
+            .collect();
+
+        // Act
+        for (id, bounds) in bodies.iter() {
+            tree.insert(*id, bounds, &velocity);
+        }
+        for (id, bounds) in bodies.iter_mut().step_by(3) {
+            *bounds = square(next() * 200., next() * 200., next() * 20.);
+            tree.update(*id, bounds, &velocity);
+        }
+        for (id, _) in bodies.drain(..30) {
+            tree.remove(id);
+        }
+        let result = tree.pairs();
+
+        // Assert
+        let mut expected: Vec<(Uuid, Uuid)> = Vec::new();
+        for (index, (id_a, bounds_a)) in bodies.iter().enumerate() {
+            for (id_b, bounds_b) in bodies.iter().skip(index + 1) {
+                if Bounds::overlaps(bounds_a, bounds_b) {
+                    expected.push(((*id_a).min(*id_b), (*id_a).max(*id_b)));
+                }
+            }
+        }
+        expected.sort();
+        assert_eq!(result, expected);
+        assert_eq!(tree.len(), 90);
+        assert!(tree.get_height() <= 14, "height: {}", tree.get_height());
+    }
+}
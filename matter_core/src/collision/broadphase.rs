@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::real::Real;
+use crate::core::velocity::Velocity;
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+// Finds the pairs of bodies whose bounds overlap, so only those go through the narrowphase.
+pub trait Broadphase {
+    fn insert(&mut self, id: Uuid, bounds: &Bounds, velocity: &Velocity);
+
+    fn remove(&mut self, id: Uuid) -> bool;
+
+    // Returns true when the stored bounds had to change.
+    fn update(&mut self, id: Uuid, bounds: &Bounds, velocity: &Velocity) -> bool;
+
+    fn contains(&self, id: Uuid) -> bool;
+
+    fn pairs(&self) -> Vec<(Uuid, Uuid)>;
+
+    fn query(&self, bounds: &Bounds) -> Vec<Uuid>;
+
+    // The ids whose stored bounds are hit by the segment with the fraction where the segment
+    // enters them, nearest first.
+    fn ray_cast(&self, start: &Vector, end: &Vector) -> Vec<(Uuid, Real)>;
+
+    fn update_body(&mut self, body: &Body) {
+        if let Some(bounds) = body.get_bounds() {
+            let velocity = body.get_velocity_prop();
+            if self.contains(body.get_id()) {
+                self.update(body.get_id(), &bounds, &velocity);
+            } else {
+                self.insert(body.get_id(), &bounds, &velocity);
+            }
+        }
+    }
+}
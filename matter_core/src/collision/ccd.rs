@@ -0,0 +1,401 @@
+use crate::body::body::Body;
+use crate::core::collision_filter::CollisionFilter;
+use crate::core::math;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+// How far past the contact a bullet is stopped, so the narrowphase sees it touching and the
+// resolver takes out its velocity into the other body. It stays within the default body slop,
+// so the overlap is not pushed out again.
+const CCD_SLOP: Real = 0.05;
+
+pub struct TimeOfImpact {
+    pub body_a: Body,
+    pub body_b: Body,
+    pub time: Real,
+}
+
+// The fraction of the step at which body a first touches body b, both moving along a straight
+// line from their previous position. Rotation during the step is ignored. Bodies that already
+// touch at the start of the step are left to the regular collision response.
+pub fn time_of_impact(body_a: &Body, body_b: &Body) -> Option<Real> {
+    let motion = get_motion(body_a) - get_motion(body_b);
+    let mut result: Option<Real> = None;
+
+    for part_a in get_collision_parts(body_a) {
+        for part_b in get_collision_parts(body_b) {
+            let time = match (part_a.get_circle_radius(), part_b.get_circle_radius()) {
+                (Some(radius_a), Some(radius_b)) => {
+                    let mut centre_a = Vector::new_from(&part_a.get_position());
+                    centre_a -= get_motion(body_a);
+                    let mut centre_b = Vector::new_from(&part_b.get_position());
+                    centre_b -= get_motion(body_b);
+                    circle_time_of_impact(&centre_a, &centre_b, radius_a + radius_b, &motion)
+                        .map(|(time, _)| time)
+                }
+                _ => {
+                    let vertices_a = get_start_points(&part_a, &get_motion(body_a));
+                    let vertices_b = get_start_points(&part_b, &get_motion(body_b));
+                    polygon_time_of_impact(&vertices_a, &vertices_b, &motion).map(|(time, _)| time)
+                }
+            };
+
+            if let Some(time) = time {
+                if result.is_none_or(|result| time < result) {
+                    result = Some(time);
+                }
+            }
+        }
+    }
+    result
+}
+
+// Moves every bullet back along its path to just inside its earliest impact in this step,
+// keeping its velocity for the resolver to respond to. Static bodies are always considered, other dynamic bodies only when asked, and
+// bodies whose collision filters exclude each other never are.
+pub fn solve(bodies: &mut [Body], include_dynamic: Option<bool>) -> Vec<TimeOfImpact> {
+    let include_dynamic = include_dynamic.unwrap_or(false);
+    let mut impacts: Vec<TimeOfImpact> = Vec::new();
+
+    for index in 0..bodies.len() {
+        let body = &bodies[index];
+        if !body.get_is_bullet() || body.get_is_static() || body.get_is_sensor() {
+            continue;
+        }
+
+        let motion = get_motion(body);
+        if motion.magnitude_squared() == 0. {
+            continue;
+        }
+
+        let swept_bounds = get_swept_bounds(body);
+        let mut earliest: Option<(usize, Real)> = None;
+        for (other_index, other) in bodies.iter().enumerate() {
+            if other_index == index
+                || other.get_is_sensor()
+                || !CollisionFilter::can_collide(
+                    &body.get_collision_filter(),
+                    &other.get_collision_filter(),
+                )
+                || !(other.get_is_static() || include_dynamic)
+                || !Bounds::overlaps(&swept_bounds, &get_swept_bounds(other))
+            {
+                continue;
+            }
+
+            if let Some(time) = time_of_impact(body, other) {
+                if earliest.is_none_or(|(_, earliest)| time < earliest) {
+                    earliest = Some((other_index, time));
+                }
+            }
+        }
+
+        if let Some((other_index, time)) = earliest {
+            let stop = (time + CCD_SLOP / motion.magnitude()).min(1.);
+            let body = &mut bodies[index];
+            body.translate(&(motion * (stop - 1.)), None);
+            impacts.push(TimeOfImpact {
+                body_a: bodies[index].clone(),
+                body_b: bodies[other_index].clone(),
+                time: time,
+            });
+        }
+    }
+    impacts
+}
+
+fn get_motion(body: &Body) -> Vector {
+    match body.get_position_prev() {
+        Some(position_prev) if !body.get_is_static() => {
+            Vector::new_from(&body.get_position()) - Vector::new_from(&position_prev)
+        }
+        _ => Vector::create(0., 0.),
+    }
+}
+
+// A compound body collides with its parts, not with its hull.
+pub(super) fn get_collision_parts(body: &Body) -> Vec<Body> {
+    let parts = body.get_parts();
+    if parts.len() > 1 {
+        parts[1..].to_vec()
+    } else {
+        parts
+    }
+}
+
+fn get_start_points(part: &Body, motion: &Vector) -> Vec<Vector> {
+    part.get_vertices()
+        .iter()
+        .map(|vertex| Vector::new_from(vertex) - *motion)
+        .collect()
+}
+
+fn get_swept_bounds(body: &Body) -> Bounds {
+    let bounds = Bounds::create(Some(body.get_vertices()));
+    let mut start_bounds = bounds;
+    start_bounds.translate(&-get_motion(body));
+    Bounds::union(&bounds, &start_bounds)
+}
+
+fn get_edge_normals(points: &[Vector]) -> Vec<Vector> {
+    (0..points.len())
+        .map(|index| {
+            let edge = points[(index + 1) % points.len()] - points[index];
+            edge.perpendicular(false).normalised()
+        })
+        .collect()
+}
+
+fn project(points: &[Vector], axis: &Vector) -> (Real, Real) {
+    points
+        .iter()
+        .map(|point| point.dot(axis))
+        .fold((Real::INFINITY, -Real::INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        })
+}
+
+// Separating axis test over the step, a translates by motion while b stays in place. On every
+// axis the projections overlap during one interval, the shapes touch when all intervals do.
+// The normal is the axis whose interval starts last, pointing from b towards a.
+pub(super) fn polygon_time_of_impact(
+    points_a: &[Vector],
+    points_b: &[Vector],
+    motion: &Vector,
+) -> Option<(Real, Vector)> {
+    let mut time_enter = -Real::INFINITY;
+    let mut time_exit = Real::INFINITY;
+    let mut normal = Vector::create(0., 0.);
+
+    for axis in get_edge_normals(points_a)
+        .iter()
+        .chain(get_edge_normals(points_b).iter())
+    {
+        let (min_a, max_a) = project(points_a, axis);
+        let (min_b, max_b) = project(points_b, axis);
+        let speed = motion.dot(axis);
+
+        if speed == 0. {
+            if max_a < min_b || min_a > max_b {
+                return None;
+            }
+            continue;
+        }
+
+        let time_min = (min_b - max_a) / speed;
+        let time_max = (max_b - min_a) / speed;
+        if time_min.min(time_max) > time_enter {
+            time_enter = time_min.min(time_max);
+            normal = if speed > 0. { -*axis } else { *axis };
+        }
+        time_exit = time_exit.min(time_min.max(time_max));
+        if time_enter > time_exit {
+            return None;
+        }
+    }
+
+    if !(0. ..=1.).contains(&time_enter) {
+        None
+    } else {
+        Some((time_enter, normal))
+    }
+}
+
+// The normal points from the centre of b towards the centre of a at the time of impact.
+pub(super) fn circle_time_of_impact(
+    centre_a: &Vector,
+    centre_b: &Vector,
+    radius: Real,
+    motion: &Vector,
+) -> Option<(Real, Vector)> {
+    let delta = *centre_a - *centre_b;
+    let c = delta.magnitude_squared() - radius * radius;
+    if c <= 0. {
+        return None;
+    }
+
+    let a = motion.magnitude_squared();
+    let b = 2. * delta.dot(motion);
+    let discriminant = b * b - 4. * a * c;
+    if a == 0. || b >= 0. || discriminant < 0. {
+        return None;
+    }
+
+    let time = (-b - math::sqrt(discriminant)) / (2. * a);
+    if time > 1. {
+        None
+    } else {
+        Some((time, (delta + *motion * time).normalised()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::collision::detector::Detector;
+    use crate::collision::pairs::Pairs;
+    use crate::collision::resolver;
+    use crate::core::common;
+    use crate::core::position::Position;
+    use crate::test_utils::{
+        body_test_utils::test_box, common_test_utils::assert_float, geometry_test_utils::assert_xy,
+    };
+
+    use super::*;
+
+    fn test_bullet(from: Real, to: Real) -> Body {
+        let mut body = test_box(from, 0., 2., 2.);
+        body.set_position(Position::new(to, 0.), Some(true));
+        body.set_is_bullet(true);
+        body
+    }
+
+    fn test_wall(x: Real) -> Body {
+        let mut body = test_box(x, 0., 1., 40.);
+        body.set_static(true);
+        body
+    }
+
+    #[test]
+    fn time_of_impact_should_return_the_fraction_of_the_step_at_first_contact() {
+        // Arrange
+        let bullet = test_bullet(0., 100.);
+        let wall = test_wall(50.);
+
+        // Act
+        let result = time_of_impact(&bullet, &wall);
+
+        // Assert
+        assert_float(result.unwrap(), 0.485);
+    }
+
+    #[test]
+    fn time_of_impact_should_return_none_for_a_path_that_misses() {
+        // Arrange
+        let mut bullet = test_box(0., 30., 2., 2.);
+        bullet.set_position(Position::new(100., 60.), Some(true));
+        let wall = test_wall(50.);
+
+        // Act
+        let result = time_of_impact(&bullet, &wall);
+
+        // Assert
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn time_of_impact_should_solve_circles_exactly() {
+        // Arrange
+        let mut circle_a = test_bullet(0., 100.);
+        circle_a.set_circle_radius(1.);
+        let mut circle_b = test_box(60., 0., 2., 2.);
+        circle_b.set_circle_radius(1.);
+
+        // Act
+        let result = time_of_impact(&circle_a, &circle_b);
+
+        // Assert
+        assert_float(result.unwrap(), 0.58);
+    }
+
+    #[test]
+    fn solve_should_move_a_bullet_back_to_just_inside_the_contact_and_keep_its_velocity() {
+        // Arrange
+        let mut bodies = vec![test_bullet(0., 100.), test_wall(50.)];
+
+        // Act
+        let result = solve(&mut bodies, None);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert_float(result[0].time, 0.485);
+        assert_xy(&bodies[0].get_position(), 48.55, 0.);
+        assert_xy(&bodies[0].get_velocity_prop(), 100., 0.);
+        assert_xy(&bodies[0].get_position_prev().unwrap(), -51.45, 0.);
+    }
+
+    #[test]
+    fn solve_should_let_the_resolver_stop_a_bullet_at_the_wall() {
+        // Arrange
+        let mut bodies = vec![test_bullet(0., 100.), test_wall(50.)];
+        let mut detector = Detector::new(AabbTree::new(None, None));
+        let mut pairs = Pairs::new();
+
+        // Act
+        for step in 0..5 {
+            if step > 0 {
+                bodies[0].update(Some(common::BASE_DELTA));
+            }
+            solve(&mut bodies, None);
+            pairs.update(&detector.collisions(&bodies), step as Real);
+            resolver::pre_solve_position(pairs.get_list());
+            for _ in 0..6 {
+                resolver::solve_position(pairs.get_list_mut(), common::BASE_DELTA, None);
+            }
+            resolver::post_solve_position(&mut bodies);
+            resolver::pre_solve_velocity(pairs.get_list());
+            for _ in 0..4 {
+                resolver::solve_velocity(pairs.get_list_mut(), common::BASE_DELTA);
+            }
+        }
+
+        // Assert
+        let position_x = bodies[0].get_position().get_x();
+        let velocity_x = position_x - bodies[0].get_position_prev().unwrap().get_x();
+        assert!(
+            (48. ..48.6).contains(&position_x),
+            "position: {}",
+            position_x
+        );
+        assert!(velocity_x <= 0., "velocity: {}", velocity_x);
+    }
+
+    #[test]
+    fn solve_should_ignore_bodies_without_the_bullet_flag() {
+        // Arrange
+        let mut bullet = test_bullet(0., 100.);
+        bullet.set_is_bullet(false);
+        let mut bodies = vec![bullet, test_wall(50.)];
+
+        // Act
+        let result = solve(&mut bodies, None);
+
+        // Assert
+        assert_eq!(result.len(), 0);
+        assert_xy(&bodies[0].get_position(), 100., 0.);
+    }
+
+    #[test]
+    fn solve_should_only_consider_dynamic_bodies_when_asked() {
+        // Arrange
+        let mut bodies = vec![test_bullet(0., 100.), test_box(50., 0., 1., 40.)];
+
+        // Act
+        let result_static_only = solve(&mut bodies, None).len();
+        let result = solve(&mut bodies, Some(true));
+
+        // Assert
+        assert_eq!(result_static_only, 0);
+        assert_eq!(result.len(), 1);
+        assert_xy(&bodies[0].get_position(), 48.55, 0.);
+    }
+
+    #[test]
+    fn solve_should_skip_bodies_the_collision_filter_excludes() {
+        // Arrange
+        let mut bullet = test_bullet(0., 100.);
+        bullet.set_collision_filter(&CollisionFilter::new(1, u32::MAX, -1));
+        let mut wall = test_wall(50.);
+        wall.set_collision_filter(&CollisionFilter::new(1, u32::MAX, -1));
+        let mut bodies = vec![bullet, wall];
+
+        // Act
+        let result = solve(&mut bodies, None);
+
+        // Assert
+        assert_eq!(result.len(), 0);
+        assert_xy(&bodies[0].get_position(), 100., 0.);
+    }
+}
@@ -0,0 +1,195 @@
+use crate::body::body::Body;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::axes::Axes;
+use crate::geometry::vector::Vector;
+use crate::geometry::vertex::Vertex;
+use crate::geometry::vertices::Vertices;
+
+// The result of the narrowphase for two overlapping parts. The normal points from part b
+// towards part a, the supports are the deepest vertices of the contact.
+#[derive(Clone)]
+pub struct Collision {
+    pub body_a: Body,
+    pub body_b: Body,
+    pub parent_a: Body,
+    pub parent_b: Body,
+    pub depth: Real,
+    pub normal: Vector,
+    pub tangent: Vector,
+    pub penetration: Vector,
+    pub supports: Vec<Vertex>,
+}
+
+struct Overlap {
+    overlap: Real,
+    axis: Vector,
+}
+
+impl Collision {
+    // Separating axis test between two convex parts.
+    pub fn collides(body_a: &Body, body_b: &Body) -> Option<Collision> {
+        let vertices_a = body_a.get_vertices();
+        let vertices_b = body_b.get_vertices();
+        let overlap_ab = Collision::overlap_axes(&vertices_a, &vertices_b, &body_a.get_axes()?)?;
+        let overlap_ba = Collision::overlap_axes(&vertices_b, &vertices_a, &body_b.get_axes()?)?;
+
+        let min_overlap = if overlap_ab.overlap < overlap_ba.overlap {
+            overlap_ab
+        } else {
+            overlap_ba
+        };
+
+        let position_a = body_a.get_position();
+        let position_b = body_b.get_position();
+        let mut normal = min_overlap.axis;
+        if normal.get_x() * (position_b.get_x() - position_a.get_x())
+            + normal.get_y() * (position_b.get_y() - position_a.get_y())
+            >= 0.
+        {
+            normal = -normal;
+        }
+
+        let depth = min_overlap.overlap;
+        let supports = Collision::find_contact_supports(body_a, body_b, &normal);
+
+        Some(Collision {
+            body_a: body_a.clone(),
+            body_b: body_b.clone(),
+            parent_a: body_a.get_parent().unwrap_or(body_a.clone()),
+            parent_b: body_b.get_parent().unwrap_or(body_b.clone()),
+            depth: depth,
+            normal: normal,
+            tangent: normal.perpendicular(false),
+            penetration: normal * depth,
+            supports: supports,
+        })
+    }
+
+    // The smallest overlap of the projections on the axes, None when one of them separates.
+    fn overlap_axes(vertices_a: &Vertices, vertices_b: &Vertices, axes: &Axes) -> Option<Overlap> {
+        let mut result: Option<Overlap> = None;
+
+        for axis in axes.iter() {
+            let (min_a, max_a) = Collision::project(vertices_a, axis);
+            let (min_b, max_b) = Collision::project(vertices_b, axis);
+            let overlap = (max_a - min_b).min(max_b - min_a);
+            if overlap <= 0. {
+                return None;
+            }
+
+            if result
+                .as_ref()
+                .is_none_or(|result| overlap < result.overlap)
+            {
+                result = Some(Overlap {
+                    overlap: overlap,
+                    axis: Vector::new_from(axis),
+                });
+            }
+        }
+        result
+    }
+
+    fn project(vertices: &Vertices, axis: &impl XY) -> (Real, Real) {
+        vertices
+            .iter()
+            .map(|vertex| vertex.dot(axis))
+            .fold((Real::INFINITY, -Real::INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            })
+    }
+
+    // There is always either one or two supports, taken from the vertices of each part that lie
+    // inside the other.
+    fn find_contact_supports(body_a: &Body, body_b: &Body, normal: &Vector) -> Vec<Vertex> {
+        let vertices_a = body_a.get_vertices();
+        let vertices_b = body_b.get_vertices();
+        let mut supports: Vec<Vertex> = Vec::new();
+
+        let candidates_b = Collision::find_supports(&vertices_b, normal, 1.);
+        for vertex in candidates_b.iter() {
+            if vertices_a.contains(vertex) {
+                supports.push(vertex.clone());
+            }
+        }
+
+        if supports.len() < 2 {
+            for vertex in Collision::find_supports(&vertices_a, normal, -1.) {
+                if supports.len() < 2 && vertices_b.contains(&vertex) {
+                    supports.push(vertex);
+                }
+            }
+        }
+
+        if supports.is_empty() {
+            supports.push(candidates_b[0].clone());
+        }
+        supports
+    }
+
+    // The vertex deepest along the normal in the given direction and its deeper neighbour.
+    fn find_supports(vertices: &Vertices, normal: &Vector, direction: Real) -> [Vertex; 2] {
+        let vertices = vertices.get_value();
+        let length = vertices.len();
+        let normal = *normal * direction;
+
+        let mut deepest_index = 0;
+        let mut deepest_distance = vertices[0].dot(&normal);
+        for (index, vertex) in vertices.iter().enumerate().skip(1) {
+            let distance = vertex.dot(&normal);
+            if distance > deepest_distance {
+                deepest_distance = distance;
+                deepest_index = index;
+            }
+        }
+
+        let previous = &vertices[(deepest_index + length - 1) % length];
+        let next = &vertices[(deepest_index + 1) % length];
+        if next.dot(&normal) > previous.dot(&normal) {
+            [vertices[deepest_index].clone(), next.clone()]
+        } else {
+            [vertices[deepest_index].clone(), previous.clone()]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{body_test_utils::test_box, common_test_utils::assert_float};
+
+    use super::*;
+
+    #[test]
+    fn collides_should_return_the_depth_and_a_normal_pointing_towards_body_a() {
+        // Arrange
+        let body_a = test_box(0., 0., 10., 10.);
+        let body_b = test_box(8., 0., 10., 10.);
+
+        // Act
+        let result = Collision::collides(&body_a, &body_b).unwrap();
+
+        // Assert
+        assert_float(result.depth, 2.);
+        assert_float(result.normal.get_x(), -1.);
+        assert_float(result.normal.get_y().abs(), 0.);
+        assert_float(result.penetration.get_x(), -2.);
+        assert_eq!(result.supports.len(), 2);
+        for support in result.supports.iter() {
+            assert!(support.get_x() >= 3. && support.get_x() <= 5.);
+        }
+    }
+
+    #[test]
+    fn collides_should_return_none_for_separated_bodies() {
+        // Arrange
+        let body_a = test_box(0., 0., 10., 10.);
+        let body_b = test_box(10.5, 3., 10., 10.);
+
+        // Act
+        let result = Collision::collides(&body_a, &body_b);
+
+        // Assert
+        assert!(result.is_none());
+    }
+}
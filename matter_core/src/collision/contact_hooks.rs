@@ -0,0 +1,193 @@
+use super::pair::Pair;
+use super::pairs::Pairs;
+
+pub type ContactHook = Box<dyn FnMut(&mut Pair)>;
+
+// User callbacks run on every active pair after the narrowphase and before the resolver. A hook
+// can disable the pair or change its friction, restitution and surface velocity for the step,
+// the values are reset from the bodies on the next update.
+#[derive(Default)]
+pub struct ContactHooks {
+    hooks: Vec<ContactHook>,
+}
+
+impl ContactHooks {
+    pub fn new() -> Self {
+        ContactHooks::default()
+    }
+
+    pub fn add(&mut self, hook: impl FnMut(&mut Pair) + 'static) {
+        self.hooks.push(Box::new(hook));
+    }
+
+    pub fn clear(&mut self) {
+        self.hooks.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub fn run(&mut self, pairs: &mut Pairs) {
+        if self.hooks.is_empty() {
+            return;
+        }
+
+        for pair in pairs
+            .get_list_mut()
+            .iter_mut()
+            .filter(|pair| pair.is_active)
+        {
+            for hook in self.hooks.iter_mut() {
+                hook(pair);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::body::Body;
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::collision::detector::Detector;
+    use crate::collision::resolver;
+    use crate::core::common;
+    use crate::core::position::Position;
+    use crate::core::xy::{XYNew, XY};
+    use crate::geometry::vector::Vector;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    // A static platform at y 100 and a box overlapping its top by 2, moving by the given offset
+    // in the last step.
+    fn platform_scene(offset: Position) -> (Vec<Body>, Pairs) {
+        let mut platform = test_box(0., 100., 200., 20.);
+        platform.set_static(true);
+        let mut body = test_box(0., 82., 20., 20.);
+        body.set_position(
+            Position::new(offset.get_x(), 82. + offset.get_y()),
+            Some(true),
+        );
+        let bodies = vec![platform, body];
+
+        let mut detector = Detector::new(AabbTree::new(None, None));
+        let mut pairs = Pairs::new();
+        pairs.update(&detector.collisions(&bodies), 0.);
+        (bodies, pairs)
+    }
+
+    fn resolve(bodies: &mut [Body], pairs: &mut Pairs) {
+        resolver::pre_solve_position(pairs.get_list());
+        for _ in 0..6 {
+            resolver::solve_position(pairs.get_list_mut(), common::BASE_DELTA, None);
+        }
+        resolver::post_solve_position(bodies);
+
+        resolver::pre_solve_velocity(pairs.get_list());
+        for _ in 0..4 {
+            resolver::solve_velocity(pairs.get_list_mut(), common::BASE_DELTA);
+        }
+    }
+
+    fn one_way_platform(platform: &Body) -> impl FnMut(&mut Pair) + 'static {
+        let platform = platform.clone();
+        move |pair: &mut Pair| {
+            // only collide when the other body is above the platform, y grows downwards
+            if let Some(normal) = pair.get_normal_from(&platform) {
+                if normal.get_y() > -0.5 {
+                    pair.set_enabled(false);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn run_should_let_a_one_way_platform_pass_bodies_from_below() {
+        // Arrange
+        let (mut bodies, mut pairs) = platform_scene(Position::new(0., 0.));
+        bodies[1].set_position(Position::new(0., 118.), None);
+        pairs.update(
+            &Detector::new(AabbTree::new(None, None)).collisions(&bodies),
+            0.,
+        );
+        let mut hooks = ContactHooks::new();
+        hooks.add(one_way_platform(&bodies[0]));
+
+        // Act
+        hooks.run(&mut pairs);
+        resolve(&mut bodies, &mut pairs);
+
+        // Assert
+        assert_eq!(pairs.get_list().len(), 1);
+        assert_eq!(pairs.get_list()[0].is_enabled, false);
+        assert_eq!(bodies[1].get_position().get_y(), 118.);
+    }
+
+    #[test]
+    fn run_should_let_a_one_way_platform_hold_bodies_from_above() {
+        // Arrange
+        let (mut bodies, mut pairs) = platform_scene(Position::new(0., 0.));
+        let mut hooks = ContactHooks::new();
+        hooks.add(one_way_platform(&bodies[0]));
+
+        // Act
+        hooks.run(&mut pairs);
+        resolve(&mut bodies, &mut pairs);
+
+        // Assert
+        assert_eq!(pairs.get_list()[0].is_enabled, true);
+        assert!(bodies[1].get_position().get_y() < 82.);
+    }
+
+    #[test]
+    fn run_should_move_bodies_on_a_conveyor_along_its_surface_velocity() {
+        // Arrange
+        let (mut bodies, mut pairs) = platform_scene(Position::new(0., 1.));
+        let platform = bodies[0].clone();
+        let mut hooks = ContactHooks::new();
+        hooks.add(move |pair: &mut Pair| {
+            pair.set_friction(1.);
+            pair.set_surface_velocity(&platform, &Vector::create(2., 0.));
+        });
+
+        // Act
+        hooks.run(&mut pairs);
+        resolve(&mut bodies, &mut pairs);
+
+        // Assert
+        let velocity_x =
+            bodies[1].get_position().get_x() - bodies[1].get_position_prev().unwrap().get_x();
+        assert!(velocity_x > 0.1, "velocity: {}", velocity_x);
+    }
+
+    #[test]
+    fn update_should_reset_the_values_changed_by_hooks() {
+        // Arrange
+        let (bodies, mut pairs) = platform_scene(Position::new(0., 0.));
+        let platform = bodies[0].clone();
+        let mut hooks = ContactHooks::new();
+        hooks.add(move |pair: &mut Pair| {
+            pair.set_enabled(false);
+            pair.set_restitution(1.);
+            pair.set_surface_velocity(&platform, &Vector::create(2., 0.));
+        });
+        hooks.run(&mut pairs);
+
+        // Act
+        pairs.update(
+            &Detector::new(AabbTree::new(None, None)).collisions(&bodies),
+            1.,
+        );
+
+        // Assert
+        let pair = &pairs.get_list()[0];
+        assert_eq!(pair.is_enabled, true);
+        assert_eq!(pair.restitution, 0.);
+        assert_eq!(pair.surface_velocity, 0.);
+    }
+}
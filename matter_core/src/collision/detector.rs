@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::collision_filter::CollisionFilter;
+use crate::geometry::bounds::Bounds;
+
+use super::broadphase::Broadphase;
+use super::collision::Collision;
+
+// Runs the narrowphase on the candidate pairs of the broadphase.
+pub struct Detector<T: Broadphase> {
+    broadphase: T,
+}
+
+impl<T: Broadphase> Detector<T> {
+    pub fn new(broadphase: T) -> Self {
+        Detector {
+            broadphase: broadphase,
+        }
+    }
+
+    pub fn get_broadphase(&self) -> &T {
+        &self.broadphase
+    }
+
+    pub fn get_broadphase_mut(&mut self) -> &mut T {
+        &mut self.broadphase
+    }
+
+    pub fn remove_body(&mut self, body: &Body) {
+        self.broadphase.remove(body.get_id());
+    }
+
+    pub fn collisions(&mut self, bodies: &[Body]) -> Vec<Collision> {
+        let mut bodies_by_id: HashMap<Uuid, &Body> = HashMap::new();
+        for body in bodies.iter() {
+            self.broadphase.update_body(body);
+            bodies_by_id.insert(body.get_id(), body);
+        }
+
+        let mut collisions: Vec<Collision> = Vec::new();
+        for (id_a, id_b) in self.broadphase.pairs() {
+            if let (Some(body_a), Some(body_b)) = (bodies_by_id.get(&id_a), bodies_by_id.get(&id_b))
+            {
+                if Detector::<T>::can_collide_bodies(body_a, body_b) {
+                    collisions.append(&mut Detector::<T>::collide_parts(body_a, body_b));
+                }
+            }
+        }
+        collisions
+    }
+
+    fn can_collide_bodies(body_a: &Body, body_b: &Body) -> bool {
+        if (body_a.get_is_static() || body_a.get_is_sleeping())
+            && (body_b.get_is_static() || body_b.get_is_sleeping())
+        {
+            return false;
+        }
+
+        CollisionFilter::can_collide(
+            &body_a.get_collision_filter(),
+            &body_b.get_collision_filter(),
+        )
+    }
+
+    // A compound body collides with its parts, not with its hull.
+    fn collide_parts(body_a: &Body, body_b: &Body) -> Vec<Collision> {
+        let parts_a = body_a.get_parts();
+        let parts_b = body_b.get_parts();
+        let start_a = if parts_a.len() > 1 { 1 } else { 0 };
+        let start_b = if parts_b.len() > 1 { 1 } else { 0 };
+
+        let mut collisions: Vec<Collision> = Vec::new();
+        for part_a in parts_a.iter().skip(start_a) {
+            for part_b in parts_b.iter().skip(start_b) {
+                let overlaps = match (part_a.get_bounds(), part_b.get_bounds()) {
+                    (Some(bounds_a), Some(bounds_b)) => Bounds::overlaps(&bounds_a, &bounds_b),
+                    _ => true,
+                };
+
+                if overlaps {
+                    if let Some(collision) = Collision::collides(part_a, part_b) {
+                        collisions.push(collision);
+                    }
+                }
+            }
+        }
+        collisions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    #[test]
+    fn collisions_should_return_the_touching_parts() {
+        // Arrange
+        let mut detector = Detector::new(AabbTree::new(None, None));
+        let bodies = vec![
+            test_box(0., 0., 10., 10.),
+            test_box(8., 0., 10., 10.),
+            test_box(40., 0., 10., 10.),
+        ];
+
+        // Act
+        let result = detector.collisions(&bodies);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        let ids = [result[0].body_a.get_id(), result[0].body_b.get_id()];
+        assert!(ids.contains(&bodies[0].get_id()) && ids.contains(&bodies[1].get_id()));
+    }
+
+    #[test]
+    fn collisions_should_skip_static_pairs_and_filtered_pairs() {
+        // Arrange
+        let mut detector = Detector::new(AabbTree::new(None, None));
+        let mut bodies = vec![
+            test_box(0., 0., 10., 10.),
+            test_box(8., 0., 10., 10.),
+            test_box(30., 0., 10., 10.),
+            test_box(38., 0., 10., 10.),
+        ];
+        bodies[0].set_static(true);
+        bodies[1].set_static(true);
+        bodies[2].set_collision_filter(&CollisionFilter::new(1, 0, 0));
+
+        // Act
+        let result = detector.collisions(&bodies);
+
+        // Assert
+        assert_eq!(result.len(), 0);
+    }
+}
@@ -0,0 +1,189 @@
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::real::Real;
+use crate::core::xy::XY;
+use crate::geometry::vector::Vector;
+use crate::geometry::vertex::Vertex;
+
+use super::collision::Collision;
+
+// A support point of a pair with the impulses accumulated on it, kept between steps for warm
+// starting while the same vertex stays in contact.
+#[derive(Clone)]
+pub struct Contact {
+    pub vertex: Vertex,
+    pub normal_impulse: Real,
+    pub tangent_impulse: Real,
+}
+
+impl Contact {
+    pub fn new(vertex: &Vertex) -> Self {
+        Contact {
+            vertex: vertex.clone(),
+            normal_impulse: 0.,
+            tangent_impulse: 0.,
+        }
+    }
+
+    fn id(vertex: &Vertex) -> (Option<Uuid>, usize) {
+        (
+            vertex.get_body().map(|body| body.get_id()),
+            vertex.get_index(),
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct Pair {
+    pub id: (Uuid, Uuid),
+    pub body_a: Body,
+    pub body_b: Body,
+    pub collision: Collision,
+    pub contacts: Vec<Contact>,
+    pub separation: Real,
+    pub is_active: bool,
+    pub confirmed_active: bool,
+    pub is_sensor: bool,
+    pub time_created: Real,
+    pub time_updated: Real,
+    pub inverse_mass: Real,
+    pub friction: Real,
+    pub friction_static: Real,
+    pub restitution: Real,
+    pub slop: Real,
+    pub is_enabled: bool,
+    pub surface_velocity: Real,
+}
+
+impl Pair {
+    pub fn new(collision: &Collision, timestamp: Real) -> Self {
+        let mut pair = Pair {
+            id: Pair::id(&collision.body_a, &collision.body_b),
+            body_a: collision.body_a.clone(),
+            body_b: collision.body_b.clone(),
+            collision: collision.clone(),
+            contacts: Vec::new(),
+            separation: 0.,
+            is_active: true,
+            confirmed_active: true,
+            is_sensor: collision.body_a.get_is_sensor() || collision.body_b.get_is_sensor(),
+            time_created: timestamp,
+            time_updated: timestamp,
+            inverse_mass: 0.,
+            friction: 0.,
+            friction_static: 0.,
+            restitution: 0.,
+            slop: 0.,
+            is_enabled: true,
+            surface_velocity: 0.,
+        };
+        pair.update(collision, timestamp);
+        pair
+    }
+
+    // The same id for both orders of the parts.
+    pub fn id(body_a: &Body, body_b: &Body) -> (Uuid, Uuid) {
+        let id_a = body_a.get_id();
+        let id_b = body_b.get_id();
+        (id_a.min(id_b), id_a.max(id_b))
+    }
+
+    pub fn update(&mut self, collision: &Collision, timestamp: Real) {
+        let parent_a = &collision.parent_a;
+        let parent_b = &collision.parent_b;
+
+        self.collision = collision.clone();
+        self.inverse_mass = parent_a.get_inverse_mass() + parent_b.get_inverse_mass();
+        self.friction = parent_a.get_friction().min(parent_b.get_friction());
+        self.friction_static = parent_a
+            .get_friction_static()
+            .max(parent_b.get_friction_static());
+        self.restitution = parent_a.get_resitution().max(parent_b.get_resitution());
+        self.slop = parent_a.get_slop().max(parent_b.get_slop());
+        self.separation = collision.depth;
+        self.is_enabled = true;
+        self.surface_velocity = 0.;
+
+        let previous_contacts = std::mem::take(&mut self.contacts);
+        self.contacts = collision
+            .supports
+            .iter()
+            .map(|support| {
+                previous_contacts
+                    .iter()
+                    .find(|contact| Contact::id(&contact.vertex) == Contact::id(support))
+                    .map(|contact| Contact {
+                        vertex: support.clone(),
+                        ..contact.clone()
+                    })
+                    .unwrap_or(Contact::new(support))
+            })
+            .collect();
+
+        self.set_active(true, timestamp);
+    }
+
+    pub fn set_active(&mut self, is_active: bool, timestamp: Real) {
+        self.is_active = is_active;
+        if is_active {
+            self.time_updated = timestamp;
+        } else {
+            self.contacts.clear();
+        }
+    }
+
+    // Whether the body is one of the parts of the pair or one of their parents.
+    pub fn has_body(&self, body: &Body) -> bool {
+        self.get_side(body).is_some()
+    }
+
+    // The collision normal pointing away from the given body, None when it is not in the pair.
+    pub fn get_normal_from(&self, body: &Body) -> Option<Vector> {
+        self.get_side(body)
+            .map(|direction| self.collision.normal * -direction)
+    }
+
+    // A disabled pair keeps its contacts and events but is skipped by the resolver for this step.
+    pub fn set_enabled(&mut self, is_enabled: bool) {
+        self.is_enabled = is_enabled;
+        if !is_enabled {
+            for contact in self.contacts.iter_mut() {
+                contact.normal_impulse = 0.;
+                contact.tangent_impulse = 0.;
+            }
+        }
+    }
+
+    pub fn set_friction(&mut self, friction: Real) {
+        self.friction = friction;
+    }
+
+    pub fn set_friction_static(&mut self, friction_static: Real) {
+        self.friction_static = friction_static;
+    }
+
+    pub fn set_restitution(&mut self, restitution: Real) {
+        self.restitution = restitution;
+    }
+
+    // The velocity of the surface of the given body, e.g. a conveyor belt. Only the part along
+    // the contact tangent is kept, stored as the speed of part a relative to part b.
+    pub fn set_surface_velocity(&mut self, body: &Body, velocity: &impl XY) {
+        if let Some(direction) = self.get_side(body) {
+            self.surface_velocity = self.collision.tangent.dot(velocity) * direction;
+        }
+    }
+
+    fn get_side(&self, body: &Body) -> Option<Real> {
+        let id = body.get_id();
+        let collision = &self.collision;
+        if collision.body_a.get_id() == id || collision.parent_a.get_id() == id {
+            Some(1.)
+        } else if collision.body_b.get_id() == id || collision.parent_b.get_id() == id {
+            Some(-1.)
+        } else {
+            None
+        }
+    }
+}
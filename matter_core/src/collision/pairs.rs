@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::real::Real;
+
+use super::collision::Collision;
+use super::pair::Pair;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorEventKind {
+    Enter,
+    Stay,
+    Exit,
+}
+
+// A body overlapping a sensor part. The other body is the parent of the overlapping part.
+#[derive(Clone)]
+pub struct SensorEvent {
+    pub kind: SensorEventKind,
+    pub sensor: Body,
+    pub other: Body,
+}
+
+// Tracks the pairs over time, splitting them into those that started, continued and ended
+// touching in the last update.
+#[derive(Clone, Default)]
+pub struct Pairs {
+    list: Vec<Pair>,
+    table: HashMap<(Uuid, Uuid), usize>,
+    collision_start: Vec<Pair>,
+    collision_active: Vec<Pair>,
+    collision_end: Vec<Pair>,
+}
+
+impl Pairs {
+    pub fn new() -> Self {
+        Pairs::default()
+    }
+
+    pub fn get_list(&self) -> &Vec<Pair> {
+        &self.list
+    }
+
+    pub fn get_list_mut(&mut self) -> &mut Vec<Pair> {
+        &mut self.list
+    }
+
+    pub fn get_collision_start(&self) -> &Vec<Pair> {
+        &self.collision_start
+    }
+
+    pub fn get_collision_active(&self) -> &Vec<Pair> {
+        &self.collision_active
+    }
+
+    pub fn get_collision_end(&self) -> &Vec<Pair> {
+        &self.collision_end
+    }
+
+    pub fn update(&mut self, collisions: &Vec<Collision>, timestamp: Real) {
+        self.collision_start.clear();
+        self.collision_active.clear();
+        self.collision_end.clear();
+
+        for pair in self.list.iter_mut() {
+            pair.confirmed_active = false;
+        }
+
+        for collision in collisions.iter() {
+            let id = Pair::id(&collision.body_a, &collision.body_b);
+            match self.table.get(&id) {
+                Some(index) => {
+                    let pair = &mut self.list[*index];
+                    let was_active = pair.is_active;
+                    pair.update(collision, timestamp);
+                    pair.confirmed_active = true;
+                    if was_active {
+                        self.collision_active.push(pair.clone());
+                    } else {
+                        self.collision_start.push(pair.clone());
+                    }
+                }
+                None => {
+                    let pair = Pair::new(collision, timestamp);
+                    self.collision_start.push(pair.clone());
+                    self.table.insert(id, self.list.len());
+                    self.list.push(pair);
+                }
+            }
+        }
+
+        // pairs with a sleeping body are kept, so they aren't created again when the body wakes
+        for pair in self.list.iter_mut() {
+            if !pair.confirmed_active && pair.is_active {
+                pair.set_active(false, timestamp);
+                self.collision_end.push(pair.clone());
+            }
+        }
+        self.list.retain(|pair| {
+            pair.confirmed_active
+                || pair.collision.parent_a.get_is_sleeping()
+                || pair.collision.parent_b.get_is_sleeping()
+        });
+        self.table = self
+            .list
+            .iter()
+            .enumerate()
+            .map(|(index, pair)| (pair.id, index))
+            .collect();
+    }
+
+    pub fn clear(&mut self) {
+        *self = Pairs::new();
+    }
+
+    // Enter, stay and exit events for every sensor part in the pairs of the last update.
+    pub fn get_sensor_events(&self) -> Vec<SensorEvent> {
+        let mut events: Vec<SensorEvent> = Vec::new();
+        let groups = [
+            (SensorEventKind::Enter, &self.collision_start),
+            (SensorEventKind::Stay, &self.collision_active),
+            (SensorEventKind::Exit, &self.collision_end),
+        ];
+
+        for (kind, pairs) in groups {
+            for pair in pairs.iter().filter(|pair| pair.is_sensor) {
+                let collision = &pair.collision;
+                if collision.body_a.get_is_sensor() {
+                    events.push(SensorEvent {
+                        kind: kind,
+                        sensor: collision.body_a.clone(),
+                        other: collision.parent_b.clone(),
+                    });
+                }
+                if collision.body_b.get_is_sensor() {
+                    events.push(SensorEvent {
+                        kind: kind,
+                        sensor: collision.body_b.clone(),
+                        other: collision.parent_a.clone(),
+                    });
+                }
+            }
+        }
+        events
+    }
+
+    // The bodies currently overlapping the sensor, given as the sensor part or its parent.
+    pub fn get_bodies_in_sensor(&self, sensor: &Body) -> Vec<Body> {
+        let id = sensor.get_id();
+        let mut bodies: Vec<Body> = Vec::new();
+
+        for pair in self
+            .list
+            .iter()
+            .filter(|pair| pair.is_active && pair.is_sensor)
+        {
+            let collision = &pair.collision;
+            let other = if collision.body_a.get_is_sensor()
+                && (collision.body_a.get_id() == id || collision.parent_a.get_id() == id)
+            {
+                &collision.parent_b
+            } else if collision.body_b.get_is_sensor()
+                && (collision.body_b.get_id() == id || collision.parent_b.get_id() == id)
+            {
+                &collision.parent_a
+            } else {
+                continue;
+            };
+
+            if !bodies.iter().any(|body| body.get_id() == other.get_id()) {
+                bodies.push(other.clone());
+            }
+        }
+        bodies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::collision::detector::Detector;
+    use crate::core::position::Position;
+    use crate::core::xy::XYNew;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    fn sensor_scene() -> (Vec<Body>, Detector<AabbTree>, Pairs) {
+        let mut sensor = test_box(0., 0., 20., 20.);
+        sensor.set_static(true);
+        sensor.set_is_sensor(true);
+        let body = test_box(50., 0., 10., 10.);
+        (
+            vec![sensor, body],
+            Detector::new(AabbTree::new(None, None)),
+            Pairs::new(),
+        )
+    }
+
+    fn step(bodies: &mut Vec<Body>, detector: &mut Detector<AabbTree>, pairs: &mut Pairs, x: Real) {
+        bodies[1].set_position(Position::new(x, 0.), None);
+        let collisions = detector.collisions(bodies);
+        pairs.update(&collisions, 0.);
+    }
+
+    fn get_kinds(pairs: &Pairs) -> Vec<SensorEventKind> {
+        pairs
+            .get_sensor_events()
+            .iter()
+            .map(|event| event.kind)
+            .collect()
+    }
+
+    #[test]
+    fn get_sensor_events_should_report_enter_stay_and_exit() {
+        // Arrange
+        let (mut bodies, mut detector, mut pairs) = sensor_scene();
+
+        // Act
+        step(&mut bodies, &mut detector, &mut pairs, 50.);
+        let result_outside = get_kinds(&pairs);
+        step(&mut bodies, &mut detector, &mut pairs, 5.);
+        let result_enter = pairs.get_sensor_events();
+        step(&mut bodies, &mut detector, &mut pairs, 3.);
+        let result_stay = get_kinds(&pairs);
+        step(&mut bodies, &mut detector, &mut pairs, 50.);
+        let result_exit = get_kinds(&pairs);
+
+        // Assert
+        assert_eq!(result_outside, vec![]);
+        assert_eq!(result_enter.len(), 1);
+        assert_eq!(result_enter[0].kind, SensorEventKind::Enter);
+        assert_eq!(result_enter[0].sensor.get_id(), bodies[0].get_id());
+        assert_eq!(result_enter[0].other.get_id(), bodies[1].get_id());
+        assert_eq!(result_stay, vec![SensorEventKind::Stay]);
+        assert_eq!(result_exit, vec![SensorEventKind::Exit]);
+        assert_eq!(pairs.get_list().len(), 0);
+    }
+
+    #[test]
+    fn get_bodies_in_sensor_should_return_the_overlapping_bodies() {
+        // Arrange
+        let (mut bodies, mut detector, mut pairs) = sensor_scene();
+
+        // Act
+        step(&mut bodies, &mut detector, &mut pairs, 5.);
+        let result = pairs.get_bodies_in_sensor(&bodies[0]);
+        let result_other = pairs.get_bodies_in_sensor(&bodies[1]);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].get_id(), bodies[1].get_id());
+        assert_eq!(result_other.len(), 0);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::body::body::Body;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+use crate::geometry::vertices::Vertices;
+
+use super::collision::Collision;
+
+// Thin enough not to matter for a hit but wide enough to keep the edges of the ray apart, also
+// with the f32 build.
+const RAY_WIDTH: Real = 1e-3;
+
+fn get_bounds(body: &Body) -> Bounds {
+    body.get_bounds()
+        .unwrap_or_else(|| Bounds::from_points(&body.get_vertices().get_value()))
+}
+
+// The parts to test, compound bodies are tested by their parts and not by their hull.
+fn get_parts(body: &Body) -> Vec<Body> {
+    let parts = body.get_parts();
+    let start = if parts.len() > 1 { 1 } else { 0 };
+    parts.into_iter().skip(start).collect()
+}
+
+// The collisions of the body with the bodies, at most one for each of them.
+pub fn collides(body: &Body, bodies: &[Body]) -> Vec<Collision> {
+    let bounds = get_bounds(body);
+    let mut collisions: Vec<Collision> = Vec::new();
+
+    for other in bodies
+        .iter()
+        .filter(|other| Bounds::overlaps(&get_bounds(other), &bounds))
+    {
+        let collision = get_parts(other)
+            .iter()
+            .filter(|part| Bounds::overlaps(&get_bounds(part), &bounds))
+            .find_map(|part| Collision::collides(part, body));
+
+        if let Some(collision) = collision {
+            collisions.push(collision);
+        }
+    }
+    collisions
+}
+
+// The collisions of a segment of the given width with the bodies. Both bodies of each collision
+// are set to the part that was hit.
+pub fn ray(bodies: &[Body], start: &impl XY, end: &impl XY, width: Option<Real>) -> Vec<Collision> {
+    let start = Vector::new_from(start);
+    let end = Vector::new_from(end);
+    let direction = end - start;
+    if direction.magnitude() == 0. {
+        return Vec::new();
+    }
+
+    let offset = direction.normalised().perpendicular(false) * (width.unwrap_or(RAY_WIDTH) * 0.5);
+    let vertices = Vertices::create(
+        vec![start - offset, end - offset, end + offset, start + offset],
+        None,
+    );
+
+    let mut ray = Body::default_body();
+    ray.set_vertices(&vertices);
+    ray.set_bounds(&Bounds::create(Some(ray.get_vertices())));
+    ray.translate(&((start + end) * 0.5), None);
+
+    let mut collisions = collides(&ray, bodies);
+    for collision in collisions.iter_mut() {
+        collision.body_b = collision.body_a.clone();
+        collision.parent_b = collision.parent_a.clone();
+    }
+    collisions
+}
+
+// The bodies overlapping the region, or with outside set the ones that do not.
+pub fn region(bodies: &[Body], bounds: &Bounds, outside: Option<bool>) -> Vec<Body> {
+    let outside = outside.unwrap_or(false);
+    bodies
+        .iter()
+        .filter(|body| Bounds::overlaps(&get_bounds(body), bounds) != outside)
+        .cloned()
+        .collect()
+}
+
+// The bodies with a part containing the point.
+pub fn point(bodies: &[Body], point: &impl XY) -> Vec<Body> {
+    bodies
+        .iter()
+        .filter(|body| get_bounds(body).contains(point))
+        .filter(|body| {
+            get_parts(body)
+                .iter()
+                .any(|part| get_bounds(part).contains(point) && part.get_vertices().contains(point))
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::position::Position;
+    use crate::core::real::consts::PI;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    fn test_bodies() -> Vec<Body> {
+        let mut diamond = test_box(100., 0., 20., 20.);
+        diamond.rotate(PI / 4., None::<&Vector>, None);
+        vec![
+            test_box(0., 0., 20., 20.),
+            diamond,
+            test_box(200., 0., 20., 20.),
+        ]
+    }
+
+    fn get_ids(bodies: &[Body]) -> Vec<uuid::Uuid> {
+        bodies.iter().map(|body| body.get_id()).collect()
+    }
+
+    #[test]
+    fn point_should_return_the_bodies_containing_the_point() {
+        // Arrange
+        let bodies = test_bodies();
+
+        // Act
+        let result = point(&bodies, &Position::new(5., -5.));
+        let result_corner = point(&bodies, &Position::new(92., -11.));
+        let result_centre = point(&bodies, &Position::new(100., 0.));
+
+        // Assert
+        assert_eq!(get_ids(&result), vec![bodies[0].get_id()]);
+        assert_eq!(result_corner.len(), 0);
+        assert_eq!(get_ids(&result_centre), vec![bodies[1].get_id()]);
+    }
+
+    #[test]
+    fn region_should_return_the_bodies_inside_or_outside_the_bounds() {
+        // Arrange
+        let bodies = test_bodies();
+        let mut bounds = Bounds::empty();
+        bounds.min.x = -50.;
+        bounds.min.y = -50.;
+        bounds.max.x = 95.;
+        bounds.max.y = 50.;
+
+        // Act
+        let result = region(&bodies, &bounds, None);
+        let result_outside = region(&bodies, &bounds, Some(true));
+
+        // Assert
+        assert_eq!(get_ids(&result), get_ids(&bodies[0..2]));
+        assert_eq!(get_ids(&result_outside), get_ids(&bodies[2..3]));
+    }
+
+    #[test]
+    fn ray_should_return_the_bodies_crossed_by_the_segment() {
+        // Arrange
+        let bodies = test_bodies();
+
+        // Act
+        let result = ray(
+            &bodies,
+            &Position::new(-50., 5.),
+            &Position::new(150., 5.),
+            None,
+        );
+        let result_miss = ray(
+            &bodies,
+            &Position::new(-50., 50.),
+            &Position::new(250., 50.),
+            None,
+        );
+        let result_wide = ray(
+            &bodies,
+            &Position::new(-50., 30.),
+            &Position::new(250., 30.),
+            Some(42.),
+        );
+
+        // Assert
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].body_a.get_id(), bodies[0].get_id());
+        assert_eq!(result[0].body_b.get_id(), bodies[0].get_id());
+        assert_eq!(result[1].body_a.get_id(), bodies[1].get_id());
+        assert_eq!(result_miss.len(), 0);
+        assert_eq!(result_wide.len(), 3);
+    }
+
+    #[test]
+    fn collides_should_return_a_collision_for_each_touching_body() {
+        // Arrange
+        let bodies = test_bodies();
+        let body = test_box(50., 0., 84., 10.);
+
+        // Act
+        let result = collides(&body, &bodies);
+
+        // Assert
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].body_a.get_id(), bodies[0].get_id());
+        assert_eq!(result[0].body_b.get_id(), body.get_id());
+        assert_eq!(result[1].body_a.get_id(), bodies[1].get_id());
+    }
+}
@@ -0,0 +1,356 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::collision_filter::CollisionFilter;
+use crate::core::math;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::vector::Vector;
+
+use super::broadphase::Broadphase;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RayCastMode {
+    // The nearest hit only.
+    Closest,
+    // The nearest hit on every body, nearest first.
+    All,
+    // The first hit found, not necessarily the nearest, e.g. for a line of sight.
+    Any,
+}
+
+// A hit of a ray on a part. The body is the parent of the part and the normal is the unit
+// normal of the surface at the point, facing the ray.
+#[derive(Clone)]
+pub struct RayHit {
+    pub body: Body,
+    pub part: Body,
+    pub point: Vector,
+    pub normal: Vector,
+    pub fraction: Real,
+}
+
+// Casts the segment from start to end against the bodies in the broadphase. Bodies excluded by
+// the filter or the predicate are skipped, as are the parts the ray starts in.
+pub fn ray_cast<T: Broadphase>(
+    broadphase: &T,
+    bodies: &[Body],
+    start: &impl XY,
+    end: &impl XY,
+    mode: RayCastMode,
+    filter: Option<&CollisionFilter>,
+    predicate: Option<&dyn Fn(&Body) -> bool>,
+) -> Vec<RayHit> {
+    let start = Vector::new_from(start);
+    let end = Vector::new_from(end);
+    let bodies_by_id: HashMap<Uuid, &Body> =
+        bodies.iter().map(|body| (body.get_id(), body)).collect();
+    let mut hits: Vec<RayHit> = Vec::new();
+
+    for (id, entry) in broadphase.ray_cast(&start, &end) {
+        // the candidates are sorted by where the ray enters their bounds
+        if mode == RayCastMode::Closest && hits.first().is_some_and(|hit| hit.fraction < entry) {
+            break;
+        }
+
+        let body = match bodies_by_id.get(&id) {
+            Some(body) => *body,
+            None => continue,
+        };
+        if filter.is_some_and(|filter| {
+            !CollisionFilter::can_collide(filter, &body.get_collision_filter())
+        }) || predicate.is_some_and(|predicate| !predicate(body))
+        {
+            continue;
+        }
+
+        let hit = match cast_body(body, &start, &end) {
+            Some(hit) => hit,
+            None => continue,
+        };
+        match mode {
+            RayCastMode::Closest => {
+                if hits
+                    .first()
+                    .is_none_or(|closest| hit.fraction < closest.fraction)
+                {
+                    hits = vec![hit];
+                }
+            }
+            RayCastMode::All => hits.push(hit),
+            RayCastMode::Any => return vec![hit],
+        }
+    }
+
+    hits.sort_by(|hit_a, hit_b| hit_a.fraction.total_cmp(&hit_b.fraction));
+    hits
+}
+
+// The nearest hit on the parts of the body, compound bodies are cast against their parts.
+pub fn cast_body(body: &Body, start: &Vector, end: &Vector) -> Option<RayHit> {
+    let parts = body.get_parts();
+    let start_index = if parts.len() > 1 { 1 } else { 0 };
+
+    parts
+        .iter()
+        .skip(start_index)
+        .filter_map(|part| {
+            let (fraction, normal) = match part.get_circle_radius() {
+                Some(radius) => {
+                    cast_circle(&Vector::new_from(&part.get_position()), radius, start, end)
+                }
+                None => cast_polygon(part, start, end),
+            }?;
+            Some(RayHit {
+                body: body.clone(),
+                part: part.clone(),
+                point: *start + (*end - *start) * fraction,
+                normal: normal,
+                fraction: fraction,
+            })
+        })
+        .min_by(|hit_a, hit_b| hit_a.fraction.total_cmp(&hit_b.fraction))
+}
+
+// Clips the segment by the half planes of the edges of a convex part, the edge where the
+// segment enters last gives the normal.
+fn cast_polygon(part: &Body, start: &Vector, end: &Vector) -> Option<(Real, Vector)> {
+    let vertices: Vec<Vector> = part
+        .get_vertices()
+        .get_value()
+        .iter()
+        .map(Vector::new_from)
+        .collect();
+    let count = vertices.len() as Real;
+    let centre = vertices
+        .iter()
+        .fold(Vector::create(0., 0.), |sum, vertex| sum + *vertex)
+        / count;
+    let direction = *end - *start;
+
+    let mut lower: Real = 0.;
+    let mut upper: Real = 1.;
+    let mut normal: Option<Vector> = None;
+
+    for (index, vertex) in vertices.iter().enumerate() {
+        let next = vertices[(index + 1) % vertices.len()];
+        let mut edge_normal = (next - *vertex).perpendicular(false);
+        if edge_normal.dot(&(*vertex - centre)) < 0. {
+            edge_normal = -edge_normal;
+        }
+
+        let numerator = edge_normal.dot(&(*vertex - *start));
+        let denominator = edge_normal.dot(&direction);
+        if denominator == 0. {
+            if numerator < 0. {
+                return None;
+            }
+            continue;
+        }
+
+        let fraction = numerator / denominator;
+        if denominator < 0. {
+            if fraction > lower || normal.is_none() && fraction >= lower {
+                lower = fraction;
+                normal = Some(edge_normal);
+            }
+        } else {
+            upper = upper.min(fraction);
+        }
+
+        if upper < lower {
+            return None;
+        }
+    }
+
+    // without an entering edge at or ahead of the start the ray starts inside
+    normal.map(|normal| (lower, normal.normalised()))
+}
+
+// The circle is cast exactly, not by its polygon. A ray starting inside does not hit it.
+fn cast_circle(
+    centre: &Vector,
+    radius: Real,
+    start: &Vector,
+    end: &Vector,
+) -> Option<(Real, Vector)> {
+    let direction = *end - *start;
+    let offset = *start - *centre;
+    let a = direction.dot(&direction);
+    let b = 2. * offset.dot(&direction);
+    let c = offset.dot(&offset) - radius * radius;
+    let discriminant = b * b - 4. * a * c;
+    if c <= 0. || discriminant < 0. || a == 0. {
+        return None;
+    }
+
+    let fraction = (-b - math::sqrt(discriminant)) / (2. * a);
+    if !(0. ..=1.).contains(&fraction) {
+        return None;
+    }
+
+    let point = *start + direction * fraction;
+    Some((fraction, (point - *centre) / radius))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::core::position::Position;
+    use crate::core::real::consts::PI;
+    use crate::test_utils::{
+        body_test_utils::test_box, common_test_utils::assert_float, geometry_test_utils::assert_xy,
+    };
+
+    use super::*;
+
+    fn test_world(bodies: &[Body]) -> AabbTree {
+        let mut tree = AabbTree::new(Some(0.), None);
+        for body in bodies.iter() {
+            tree.update_body(body);
+        }
+        tree
+    }
+
+    fn cast(
+        bodies: &[Body],
+        start: (Real, Real),
+        end: (Real, Real),
+        mode: RayCastMode,
+    ) -> Vec<RayHit> {
+        ray_cast(
+            &test_world(bodies),
+            bodies,
+            &Position::new(start.0, start.1),
+            &Position::new(end.0, end.1),
+            mode,
+            None,
+            None,
+        )
+    }
+
+    fn test_bodies() -> Vec<Body> {
+        vec![
+            test_box(150., 0., 10., 10.),
+            test_box(50., 0., 10., 10.),
+            test_box(100., 0., 10., 10.),
+        ]
+    }
+
+    #[test]
+    fn ray_cast_should_return_the_closest_hit() {
+        // Arrange
+        let bodies = test_bodies();
+
+        // Act
+        let result = cast(&bodies, (0., 2.), (200., 2.), RayCastMode::Closest);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body.get_id(), bodies[1].get_id());
+        assert_eq!(result[0].part.get_id(), bodies[1].get_id());
+        assert_xy(&result[0].point, 45., 2.);
+        assert_xy(&result[0].normal, -1., 0.);
+        assert_float(result[0].fraction, 0.225);
+    }
+
+    #[test]
+    fn ray_cast_should_return_all_hits_nearest_first() {
+        // Arrange
+        let bodies = test_bodies();
+
+        // Act
+        let result = cast(&bodies, (200., 2.), (0., 2.), RayCastMode::All);
+        let result_any = cast(&bodies, (200., 2.), (0., 2.), RayCastMode::Any);
+        let result_miss = cast(&bodies, (0., 20.), (200., 20.), RayCastMode::All);
+
+        // Assert
+        assert_eq!(result.len(), 3);
+        assert_eq!(result[0].body.get_id(), bodies[0].get_id());
+        assert_eq!(result[1].body.get_id(), bodies[2].get_id());
+        assert_eq!(result[2].body.get_id(), bodies[1].get_id());
+        assert_xy(&result[2].point, 55., 2.);
+        assert_xy(&result[2].normal, 1., 0.);
+        assert_eq!(result_any.len(), 1);
+        assert_eq!(result_miss.len(), 0);
+    }
+
+    #[test]
+    fn ray_cast_should_skip_bodies_excluded_by_the_filter_or_predicate() {
+        // Arrange
+        let mut bodies = test_bodies();
+        let mut collision_filter = bodies[1].get_collision_filter();
+        collision_filter.set_category(2);
+        bodies[1].set_collision_filter(&collision_filter);
+        let filter = CollisionFilter::new(1, !2, 0);
+        let first = bodies[2].get_id();
+        let predicate = move |body: &Body| body.get_id() != first;
+
+        // Act
+        let result = ray_cast(
+            &test_world(&bodies),
+            &bodies,
+            &Position::new(0., 2.),
+            &Position::new(200., 2.),
+            RayCastMode::Closest,
+            Some(&filter),
+            Some(&predicate),
+        );
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body.get_id(), bodies[0].get_id());
+    }
+
+    #[test]
+    fn ray_cast_should_hit_circles_exactly() {
+        // Arrange
+        let mut circle = test_box(50., 0., 20., 20.);
+        circle.set_circle_radius(10.);
+        let bodies = vec![circle];
+
+        // Act
+        let result = cast(&bodies, (50., -100.), (50., 100.), RayCastMode::Closest);
+        let result_tangent = cast(&bodies, (0., 6.), (100., 6.), RayCastMode::Closest);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert_xy(&result[0].point, 50., -10.);
+        assert_xy(&result[0].normal, 0., -1.);
+        assert_float(result[0].fraction, 0.45);
+        assert_xy(&result_tangent[0].point, 42., 6.);
+        assert_xy(&result_tangent[0].normal, -0.8, 0.6);
+    }
+
+    #[test]
+    fn ray_cast_should_ignore_bodies_the_ray_starts_in() {
+        // Arrange
+        let bodies = test_bodies();
+
+        // Act
+        let result = cast(&bodies, (50., 2.), (200., 2.), RayCastMode::Closest);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body.get_id(), bodies[2].get_id());
+    }
+
+    #[test]
+    fn ray_cast_should_return_the_normal_of_the_edge_hit() {
+        // Arrange
+        let mut diamond = test_box(50., 0., 20., 20.);
+        diamond.rotate(PI / 4., None::<&Vector>, None);
+        let bodies = vec![diamond];
+
+        // Act
+        let result = cast(&bodies, (0., -5.), (100., -5.), RayCastMode::Closest);
+
+        // Assert
+        let half_sqrt_2 = math::sqrt(2.) / 2.;
+        assert_eq!(result.len(), 1);
+        assert_xy(&result[0].normal, -half_sqrt_2, -half_sqrt_2);
+        assert_float(result[0].point.get_x(), 50. - 10. * math::sqrt(2.) + 5.);
+    }
+}
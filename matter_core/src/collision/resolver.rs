@@ -0,0 +1,320 @@
+use crate::body::body::Body;
+use crate::core::common;
+use crate::core::position::Position;
+use crate::core::real::Real;
+use crate::core::velocity::Velocity;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::vector::Vector;
+
+use super::pair::Pair;
+
+const RESTING_THRESH: Real = 2.;
+const RESTING_THRESH_TANGENT: Real = 2.449489742783178;
+const POSITION_DAMPEN: Real = 0.9;
+const POSITION_WARMING: Real = 0.8;
+const FRICTION_NORMAL_MULTIPLIER: Real = 5.;
+const FRICTION_MAX_STATIC: Real = Real::MAX;
+
+// Only active and enabled pairs without sensors take part in the response, sensor pairs are
+// detected and tracked but never pushed apart.
+fn is_solved(pair: &Pair) -> bool {
+    pair.is_active && pair.is_enabled && !pair.is_sensor
+}
+
+fn is_fixed(body: &Body) -> bool {
+    body.get_is_static() || body.get_is_sleeping()
+}
+
+pub fn pre_solve_position(pairs: &[Pair]) {
+    for pair in pairs.iter().filter(|pair| is_solved(pair)) {
+        let count = pair.contacts.len() as u32;
+        let mut parent_a = pair.collision.parent_a.clone();
+        let mut parent_b = pair.collision.parent_b.clone();
+        parent_a.set_total_contacts(parent_a.get_total_contacts() + count);
+        parent_b.set_total_contacts(parent_b.get_total_contacts() + count);
+    }
+}
+
+pub fn solve_position(pairs: &mut [Pair], delta: Real, damping: Option<Real>) {
+    let position_dampen = POSITION_DAMPEN * damping.unwrap_or(1.);
+    let slop_dampen = common::clamp(delta / common::BASE_DELTA, 0., 1.);
+
+    // find the current separation between the body edges involved in each collision
+    for pair in pairs.iter_mut().filter(|pair| is_solved(pair)) {
+        let collision = &pair.collision;
+        let impulse_a = collision.parent_a.get_position_impulse();
+        let impulse_b = collision.parent_b.get_position_impulse();
+        pair.separation = collision.depth
+            + collision.normal.get_x() * (impulse_b.get_x() - impulse_a.get_x())
+            + collision.normal.get_y() * (impulse_b.get_y() - impulse_a.get_y());
+    }
+
+    for pair in pairs.iter().filter(|pair| is_solved(pair)) {
+        let collision = &pair.collision;
+        let mut body_a = collision.parent_a.clone();
+        let mut body_b = collision.parent_b.clone();
+        let normal = collision.normal;
+
+        let mut position_impulse = pair.separation - pair.slop * slop_dampen;
+        if body_a.get_is_static() || body_b.get_is_static() {
+            position_impulse *= 2.;
+        }
+
+        if !is_fixed(&body_a) {
+            let contact_share = position_dampen / body_a.get_total_contacts() as Real;
+            let mut impulse = body_a.get_position_impulse();
+            impulse.add_xy(&(normal * (position_impulse * contact_share)));
+            body_a.set_position_impulse(&impulse);
+        }
+
+        if !is_fixed(&body_b) {
+            let contact_share = position_dampen / body_b.get_total_contacts() as Real;
+            let mut impulse = body_b.get_position_impulse();
+            impulse.sub(&(normal * (position_impulse * contact_share)));
+            body_b.set_position_impulse(&impulse);
+        }
+    }
+}
+
+// Moves the bodies by their accumulated position impulse and keeps part of it for warm starting
+// the next step.
+pub fn post_solve_position(bodies: &mut [Body]) {
+    for body in bodies.iter_mut() {
+        body.set_total_contacts(0);
+
+        let mut impulse = body.get_position_impulse();
+        if impulse.get_x() == 0. && impulse.get_y() == 0. {
+            continue;
+        }
+
+        body.translate(&impulse, None);
+        if impulse.dot(&body.get_velocity_prop()) < 0. {
+            impulse.set_x_y(0., 0.);
+        } else {
+            impulse.mult(POSITION_WARMING);
+        }
+        body.set_position_impulse(&impulse);
+    }
+}
+
+// Applies the impulses cached on the contacts in the previous step.
+pub fn pre_solve_velocity(pairs: &[Pair]) {
+    for pair in pairs.iter().filter(|pair| is_solved(pair)) {
+        let collision = &pair.collision;
+        let mut body_a = collision.parent_a.clone();
+        let mut body_b = collision.parent_b.clone();
+
+        for contact in pair.contacts.iter() {
+            if contact.normal_impulse == 0. && contact.tangent_impulse == 0. {
+                continue;
+            }
+
+            let impulse = collision.normal * contact.normal_impulse
+                + collision.tangent * contact.tangent_impulse;
+            let vertex = Vector::new_from(&contact.vertex);
+            apply_impulse(&mut body_a, &vertex, &impulse, 1.);
+            apply_impulse(&mut body_b, &vertex, &impulse, -1.);
+        }
+    }
+}
+
+pub fn solve_velocity(pairs: &mut [Pair], delta: Real) {
+    let time_scale = delta / common::BASE_DELTA;
+    let time_scale_cubed = time_scale * time_scale * time_scale;
+    let resting_thresh = -RESTING_THRESH * time_scale;
+    let friction_normal_multiplier = FRICTION_NORMAL_MULTIPLIER * time_scale;
+    let friction_max_static = FRICTION_MAX_STATIC;
+
+    for pair in pairs.iter_mut().filter(|pair| is_solved(pair)) {
+        let collision = pair.collision.clone();
+        let mut body_a = collision.parent_a.clone();
+        let mut body_b = collision.parent_b.clone();
+        let normal = collision.normal;
+        let tangent = collision.tangent;
+        let contact_share = 1. / pair.contacts.len() as Real;
+        let inverse_mass_total = body_a.get_inverse_mass() + body_b.get_inverse_mass();
+        let friction = pair.friction * pair.friction_static * friction_normal_multiplier;
+
+        update_velocity(&mut body_a);
+        update_velocity(&mut body_b);
+        let velocity_a = body_a.get_velocity_prop();
+        let velocity_b = body_b.get_velocity_prop();
+        let angular_velocity_a = body_a.get_angular_velocity_prop();
+        let angular_velocity_b = body_b.get_angular_velocity_prop();
+        let position_a = Vector::new_from(&body_a.get_position());
+        let position_b = Vector::new_from(&body_b.get_position());
+
+        for contact in pair.contacts.iter_mut() {
+            let vertex = Vector::new_from(&contact.vertex);
+            let offset_a = vertex - position_a;
+            let offset_b = vertex - position_b;
+
+            let velocity_point_a = Vector::create(
+                velocity_a.get_x() - offset_a.get_y() * angular_velocity_a,
+                velocity_a.get_y() + offset_a.get_x() * angular_velocity_a,
+            );
+            let velocity_point_b = Vector::create(
+                velocity_b.get_x() - offset_b.get_y() * angular_velocity_b,
+                velocity_b.get_y() + offset_b.get_x() * angular_velocity_b,
+            );
+            let relative_velocity = velocity_point_a - velocity_point_b;
+            let normal_velocity = normal.dot(&relative_velocity);
+            let tangent_velocity = tangent.dot(&relative_velocity) + pair.surface_velocity;
+
+            // coulomb friction
+            let normal_overlap = pair.separation + normal_velocity;
+            let normal_force = if normal_overlap < 0. {
+                0.
+            } else {
+                normal_overlap.min(1.)
+            };
+            let friction_limit = normal_force * friction;
+
+            let mut tangent_impulse;
+            let max_friction;
+            if tangent_velocity < -friction_limit || tangent_velocity > friction_limit {
+                max_friction = tangent_velocity.abs();
+                tangent_impulse =
+                    pair.friction * if tangent_velocity > 0. { 1. } else { -1. } * time_scale_cubed;
+                tangent_impulse = common::clamp(tangent_impulse, -max_friction, max_friction);
+            } else {
+                tangent_impulse = tangent_velocity;
+                max_friction = friction_max_static;
+            }
+
+            // account for mass, inertia and contact offset
+            let offset_a_cross_normal = Vector::cross(&offset_a, &normal);
+            let offset_b_cross_normal = Vector::cross(&offset_b, &normal);
+            let share = contact_share
+                / (inverse_mass_total
+                    + body_a.get_inverse_inertia() * offset_a_cross_normal.powi(2)
+                    + body_b.get_inverse_inertia() * offset_b_cross_normal.powi(2));
+
+            let mut normal_impulse = (1. + pair.restitution) * normal_velocity * share;
+            tangent_impulse *= share;
+
+            // high velocity collisions clear the cached impulse, resting ones accumulate it
+            if normal_velocity < resting_thresh {
+                contact.normal_impulse = 0.;
+            } else {
+                let contact_normal_impulse = contact.normal_impulse;
+                contact.normal_impulse = (contact.normal_impulse + normal_impulse).min(0.);
+                normal_impulse = contact.normal_impulse - contact_normal_impulse;
+            }
+
+            if !(-RESTING_THRESH_TANGENT..=RESTING_THRESH_TANGENT).contains(&tangent_velocity) {
+                contact.tangent_impulse = 0.;
+            } else {
+                let contact_tangent_impulse = contact.tangent_impulse;
+                contact.tangent_impulse = common::clamp(
+                    contact.tangent_impulse + tangent_impulse,
+                    -max_friction,
+                    max_friction,
+                );
+                tangent_impulse = contact.tangent_impulse - contact_tangent_impulse;
+            }
+
+            let impulse = normal * normal_impulse + tangent * tangent_impulse;
+            apply_impulse(&mut body_a, &vertex, &impulse, 1.);
+            apply_impulse(&mut body_b, &vertex, &impulse, -1.);
+        }
+    }
+}
+
+fn update_velocity(body: &mut Body) {
+    let position = body.get_position();
+    let position_prev = body.get_position_prev().unwrap_or(position);
+    body.set_velocity_prop(&Velocity::new(
+        position.get_x() - position_prev.get_x(),
+        position.get_y() - position_prev.get_y(),
+    ));
+    body.set_angular_velocity_prop(body.get_angle() - body.get_angle_prev());
+}
+
+// Impulses change the previous position and angle, the velocity follows from the difference.
+fn apply_impulse(body: &mut Body, vertex: &Vector, impulse: &Vector, direction: Real) {
+    if is_fixed(body) {
+        return;
+    }
+
+    let offset = *vertex - Vector::new_from(&body.get_position());
+    let position = body.get_position();
+    let mut position_prev = body.get_position_prev().unwrap_or(position);
+    position_prev.add_xy(&(*impulse * (direction * body.get_inverse_mass())));
+    body.set_position_prev(&Position::new_from(&position_prev));
+    body.set_angle_prev(
+        body.get_angle_prev()
+            + direction * body.get_inverse_inertia() * Vector::cross(&offset, impulse),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::collision::detector::Detector;
+    use crate::collision::pairs::Pairs;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    fn ground_scene(is_sensor: bool) -> (Vec<Body>, Pairs) {
+        let mut ground = test_box(0., 100., 200., 20.);
+        ground.set_static(true);
+        ground.set_is_sensor(is_sensor);
+        let mut body = test_box(0., 80., 20., 20.);
+        body.set_position(Position::new(0., 85.), Some(true));
+        let bodies = vec![ground, body];
+
+        let mut detector = Detector::new(AabbTree::new(None, None));
+        let mut pairs = Pairs::new();
+        pairs.update(&detector.collisions(&bodies), 0.);
+        (bodies, pairs)
+    }
+
+    fn resolve(bodies: &mut [Body], pairs: &mut Pairs) {
+        pre_solve_position(pairs.get_list());
+        for _ in 0..6 {
+            solve_position(pairs.get_list_mut(), common::BASE_DELTA, None);
+        }
+        post_solve_position(bodies);
+
+        pre_solve_velocity(pairs.get_list());
+        for _ in 0..4 {
+            solve_velocity(pairs.get_list_mut(), common::BASE_DELTA);
+        }
+    }
+
+    #[test]
+    fn resolver_should_push_a_body_out_of_the_ground_and_stop_it() {
+        // Arrange
+        let (mut bodies, mut pairs) = ground_scene(false);
+
+        // Act
+        resolve(&mut bodies, &mut pairs);
+
+        // Assert
+        let position = bodies[1].get_position();
+        let velocity_y = position.get_y() - bodies[1].get_position_prev().unwrap().get_y();
+        assert!(position.get_y() < 85., "position: {}", position.get_y());
+        assert!(velocity_y < 0.5, "velocity: {}", velocity_y);
+        assert_eq!(bodies[0].get_position().get_y(), 100.);
+    }
+
+    #[test]
+    fn resolver_should_skip_pairs_with_a_sensor() {
+        // Arrange
+        let (mut bodies, mut pairs) = ground_scene(true);
+
+        // Act
+        resolve(&mut bodies, &mut pairs);
+
+        // Assert
+        assert_eq!(pairs.get_list().len(), 1);
+        let position = bodies[1].get_position();
+        assert_eq!(position.get_y(), 85.);
+        assert_eq!(
+            position.get_y() - bodies[1].get_position_prev().unwrap().get_y(),
+            5.
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::collision_filter::CollisionFilter;
+use crate::core::math;
+use crate::core::real::consts::PI;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::bounds::Bounds;
+use crate::geometry::transform::Transform;
+use crate::geometry::vector::Vector;
+use crate::geometry::vertices::Vertices;
+
+use super::broadphase::Broadphase;
+use super::ccd::{circle_time_of_impact, get_collision_parts, polygon_time_of_impact};
+use super::collision::Collision;
+use super::query;
+
+// A shape to query the world with, in local space. The pose of the query places it.
+#[derive(Clone)]
+pub enum QueryShape {
+    // Convex vertices around the origin of the shape.
+    Polygon(Vertices),
+    // A circle of the radius about the origin of the shape, cast exactly.
+    Circle(Real),
+}
+
+// The first contact of a shape moved along a translation. The time is the fraction of the
+// translation, 0 when the shape already overlaps the part, and the normal points from the part
+// towards the shape.
+#[derive(Clone)]
+pub struct ShapeHit {
+    pub body: Body,
+    pub part: Body,
+    pub time: Real,
+    pub normal: Vector,
+}
+
+// The earliest hit of the shape moved from its pose by the translation, ignoring rotation.
+// Bodies excluded by the filter or the predicate are skipped.
+pub fn shape_cast<T: Broadphase>(
+    broadphase: &T,
+    bodies: &[Body],
+    shape: &QueryShape,
+    pose: &Transform,
+    translation: &impl XY,
+    filter: Option<&CollisionFilter>,
+    predicate: Option<&dyn Fn(&Body) -> bool>,
+) -> Option<ShapeHit> {
+    let translation = Vector::new_from(translation);
+    let shape_body = create_body(shape, pose);
+    let shape_bounds = Bounds::create(Some(shape_body.get_vertices()));
+    let mut swept_bounds = shape_bounds;
+    swept_bounds.translate(&translation);
+    let swept_bounds = Bounds::union(&shape_bounds, &swept_bounds);
+
+    let points: Vec<Vector> = shape_body
+        .get_vertices()
+        .iter()
+        .map(Vector::new_from)
+        .collect();
+    let mut result: Option<ShapeHit> = None;
+
+    for body in get_candidates(broadphase, bodies, &swept_bounds, filter, predicate) {
+        for part in get_collision_parts(&body) {
+            let hit = match Collision::collides(&shape_body, &part) {
+                Some(collision) => Some((0., collision.normal)),
+                None => match (shape, part.get_circle_radius()) {
+                    (QueryShape::Circle(radius), Some(part_radius)) => circle_time_of_impact(
+                        &pose.get_translation(),
+                        &Vector::new_from(&part.get_position()),
+                        radius + part_radius,
+                        &translation,
+                    ),
+                    _ => {
+                        let part_points: Vec<Vector> =
+                            part.get_vertices().iter().map(Vector::new_from).collect();
+                        polygon_time_of_impact(&points, &part_points, &translation)
+                    }
+                },
+            };
+
+            if let Some((time, normal)) = hit {
+                if result.as_ref().is_none_or(|result| time < result.time) {
+                    result = Some(ShapeHit {
+                        body: body.clone(),
+                        part: part.clone(),
+                        time: time,
+                        normal: normal,
+                    });
+                }
+            }
+        }
+    }
+    result
+}
+
+// The bodies the shape overlaps at the pose. Bodies excluded by the filter or the predicate are
+// skipped.
+pub fn overlap<T: Broadphase>(
+    broadphase: &T,
+    bodies: &[Body],
+    shape: &QueryShape,
+    pose: &Transform,
+    filter: Option<&CollisionFilter>,
+    predicate: Option<&dyn Fn(&Body) -> bool>,
+) -> Vec<Body> {
+    let shape_body = create_body(shape, pose);
+    let bounds = Bounds::create(Some(shape_body.get_vertices()));
+    let candidates = get_candidates(broadphase, bodies, &bounds, filter, predicate);
+
+    query::collides(&shape_body, &candidates)
+        .into_iter()
+        .map(|collision| collision.parent_a)
+        .collect()
+}
+
+fn get_candidates<T: Broadphase>(
+    broadphase: &T,
+    bodies: &[Body],
+    bounds: &Bounds,
+    filter: Option<&CollisionFilter>,
+    predicate: Option<&dyn Fn(&Body) -> bool>,
+) -> Vec<Body> {
+    let bodies_by_id: HashMap<Uuid, &Body> =
+        bodies.iter().map(|body| (body.get_id(), body)).collect();
+
+    broadphase
+        .query(bounds)
+        .iter()
+        .filter_map(|id| bodies_by_id.get(id))
+        .filter(|body| {
+            filter.is_none_or(|filter| {
+                CollisionFilter::can_collide(filter, &body.get_collision_filter())
+            }) && predicate.is_none_or(|predicate| predicate(body))
+        })
+        .map(|body| (*body).clone())
+        .collect()
+}
+
+// A body outside of the world standing in for the shape, so the collision routines can take it.
+fn create_body(shape: &QueryShape, pose: &Transform) -> Body {
+    let mut vertices = match shape {
+        QueryShape::Polygon(vertices) => vertices.clone(),
+        QueryShape::Circle(radius) => circle_vertices(*radius),
+    };
+    vertices.transform(pose);
+    let centre = vertices.centre();
+
+    let mut body = Body::default_body();
+    body.set_vertices(&vertices);
+    body.set_bounds(&Bounds::create(Some(body.get_vertices())));
+    body.translate(&centre, None);
+    if let QueryShape::Circle(radius) = shape {
+        body.set_circle_radius(*radius);
+    }
+    body
+}
+
+// The same approximation as the circle bodies, used by the separating axis tests.
+fn circle_vertices(radius: Real) -> Vertices {
+    let sides = radius.clamp(10., 25.).ceil() as usize;
+    let theta = 2. * PI / sides as Real;
+    let offset = theta * 0.5;
+    let points = (0..sides)
+        .map(|index| {
+            let (sin, cos) = math::sin_cos(offset + index as Real * theta);
+            Vector::create(radius * cos, radius * sin)
+        })
+        .collect();
+    Vertices::create(points, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::core::position::Position;
+    use crate::test_utils::{
+        body_test_utils::test_box, common_test_utils::assert_float, geometry_test_utils::assert_xy,
+    };
+
+    use super::*;
+
+    fn test_world(bodies: &[Body]) -> AabbTree {
+        let mut tree = AabbTree::new(Some(0.), None);
+        for body in bodies.iter() {
+            tree.update_body(body);
+        }
+        tree
+    }
+
+    fn test_square(size: Real) -> QueryShape {
+        let half = size * 0.5;
+        QueryShape::Polygon(Vertices::create(
+            vec![
+                Vector::create(-half, -half),
+                Vector::create(half, -half),
+                Vector::create(half, half),
+                Vector::create(-half, half),
+            ],
+            None,
+        ))
+    }
+
+    fn cast(
+        bodies: &[Body],
+        shape: &QueryShape,
+        pose: &Transform,
+        x: Real,
+        y: Real,
+    ) -> Option<ShapeHit> {
+        shape_cast(
+            &test_world(bodies),
+            bodies,
+            shape,
+            pose,
+            &Position::new(x, y),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn shape_cast_should_return_the_time_and_normal_of_the_first_contact() {
+        // Arrange
+        let bodies = vec![test_box(100., 0., 10., 40.), test_box(50., 0., 10., 40.)];
+        let pose = Transform::from_translation(&Vector::create(0., 5.));
+
+        // Act
+        let result = cast(&bodies, &test_square(10.), &pose, 100., 0.).unwrap();
+
+        // Assert
+        assert_eq!(result.body.get_id(), bodies[1].get_id());
+        assert_float(result.time, 0.4);
+        assert_xy(&result.normal, -1., 0.);
+    }
+
+    #[test]
+    fn shape_cast_should_return_none_for_a_path_that_misses() {
+        // Arrange
+        let bodies = vec![test_box(50., 0., 10., 10.)];
+
+        // Act
+        let result = cast(
+            &bodies,
+            &test_square(10.),
+            &Transform::identity(),
+            100.,
+            50.,
+        );
+        let result_short = cast(&bodies, &test_square(10.), &Transform::identity(), 30., 0.);
+
+        // Assert
+        assert!(result.is_none());
+        assert!(result_short.is_none());
+    }
+
+    #[test]
+    fn shape_cast_should_return_zero_for_a_shape_starting_in_a_body() {
+        // Arrange
+        let bodies = vec![test_box(4., 0., 10., 10.)];
+
+        // Act
+        let result = cast(&bodies, &test_square(10.), &Transform::identity(), 0., 100.).unwrap();
+
+        // Assert
+        assert_float(result.time, 0.);
+        assert_xy(&result.normal, -1., 0.);
+    }
+
+    #[test]
+    fn shape_cast_should_solve_circles_exactly() {
+        // Arrange
+        let mut circle = test_box(0., 100., 20., 20.);
+        circle.set_circle_radius(10.);
+        let bodies = vec![circle];
+
+        // Act
+        let result = cast(
+            &bodies,
+            &QueryShape::Circle(5.),
+            &Transform::identity(),
+            0.,
+            200.,
+        )
+        .unwrap();
+
+        // Assert
+        assert_float(result.time, 0.425);
+        assert_xy(&result.normal, 0., -1.);
+    }
+
+    #[test]
+    fn overlap_should_return_the_bodies_touching_the_shape() {
+        // Arrange
+        let bodies = vec![
+            test_box(0., 0., 10., 10.),
+            test_box(20., 0., 10., 10.),
+            test_box(8., 12., 10., 10.),
+        ];
+        let world = test_world(&bodies);
+        let pose = Transform::new(PI / 4., &Vector::create(10., 0.));
+        let excluded = bodies[1].get_id();
+
+        // Act
+        let result = overlap(&world, &bodies, &test_square(8.), &pose, None, None);
+        let result_filtered = overlap(
+            &world,
+            &bodies,
+            &test_square(8.),
+            &pose,
+            None,
+            Some(&move |body: &Body| body.get_id() != excluded),
+        );
+
+        // Assert
+        assert_eq!(result.len(), 2);
+        assert!(result
+            .iter()
+            .all(|body| body.get_id() != bodies[2].get_id()));
+        assert_eq!(result_filtered.len(), 1);
+        assert_eq!(result_filtered[0].get_id(), bodies[0].get_id());
+    }
+}
@@ -0,0 +1,341 @@
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::collision::pairs::Pairs;
+use crate::constraint::constraint::Constraint;
+use crate::core::common;
+use crate::core::real::Real;
+use crate::core::xy::XY;
+use crate::geometry::bounds::Bounds;
+use crate::render::debug_draw::{self, DebugDraw};
+use crate::render::layers::Layers;
+
+// A collection of bodies, constraints and other composites. The world is the root composite,
+// the all_* getters walk the whole tree.
+#[derive(Clone)]
+pub struct Composite {
+    id: Uuid,
+    bodies: Vec<Body>,
+    constraints: Vec<Constraint>,
+    composites: Vec<Composite>,
+    is_modified: bool,
+}
+
+impl Default for Composite {
+    fn default() -> Self {
+        Composite::new()
+    }
+}
+
+impl Composite {
+    pub fn new() -> Self {
+        Composite {
+            id: common::next_id(),
+            bodies: Vec::new(),
+            constraints: Vec::new(),
+            composites: Vec::new(),
+            is_modified: false,
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn get_bodies(&self) -> &Vec<Body> {
+        &self.bodies
+    }
+
+    pub fn get_constraints(&self) -> &Vec<Constraint> {
+        &self.constraints
+    }
+
+    pub fn get_composites(&self) -> &Vec<Composite> {
+        &self.composites
+    }
+
+    pub fn get_composites_mut(&mut self) -> &mut Vec<Composite> {
+        &mut self.composites
+    }
+
+    pub fn get_is_modified(&self) -> bool {
+        self.is_modified
+    }
+
+    pub fn all_bodies(&self) -> Vec<Body> {
+        let mut bodies = self.bodies.clone();
+        for composite in self.composites.iter() {
+            bodies.append(&mut composite.all_bodies());
+        }
+        bodies
+    }
+
+    pub fn all_constraints(&self) -> Vec<Constraint> {
+        let mut constraints = self.constraints.clone();
+        for composite in self.composites.iter() {
+            constraints.append(&mut composite.all_constraints());
+        }
+        constraints
+    }
+
+    pub fn all_composites(&self) -> Vec<Composite> {
+        let mut composites = self.composites.clone();
+        for composite in self.composites.iter() {
+            composites.append(&mut composite.all_composites());
+        }
+        composites
+    }
+
+    pub fn get_body(&self, id: Uuid) -> Option<Body> {
+        self.all_bodies()
+            .into_iter()
+            .find(|body| body.get_id() == id)
+    }
+
+    // The bounds around all the bodies of the tree, empty for a composite without bodies.
+    pub fn get_bounds(&self) -> Bounds {
+        self.all_bodies()
+            .iter()
+            .map(|body| Bounds::from_points(&body.get_vertices().get_value()))
+            .fold(Bounds::empty(), |bounds, body_bounds| {
+                Bounds::union(&bounds, &body_bounds)
+            })
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+
+    // Marks the composite as changed, up the tree it is done by the composites that add to it.
+    pub fn set_modified(&mut self, is_modified: bool, update_children: Option<bool>) {
+        self.is_modified = is_modified;
+        if update_children.unwrap_or(false) {
+            for composite in self.composites.iter_mut() {
+                composite.set_modified(is_modified, Some(true));
+            }
+        }
+    }
+
+    pub fn add_body(&mut self, body: &Body) {
+        self.bodies.push(body.clone());
+        self.is_modified = true;
+    }
+
+    pub fn add_constraint(&mut self, constraint: &Constraint) {
+        self.constraints.push(constraint.clone());
+        self.is_modified = true;
+    }
+
+    pub fn add_composite(&mut self, composite: Composite) {
+        self.composites.push(composite);
+        self.is_modified = true;
+    }
+
+    pub fn remove_body(&mut self, body: &Body, deep: Option<bool>) -> bool {
+        let id = body.get_id();
+        let count = self.bodies.len();
+        self.bodies.retain(|body| body.get_id() != id);
+        let mut is_removed = self.bodies.len() != count;
+
+        if deep.unwrap_or(false) {
+            for composite in self.composites.iter_mut() {
+                is_removed |= composite.remove_body(body, deep);
+            }
+        }
+
+        self.is_modified |= is_removed;
+        is_removed
+    }
+
+    pub fn remove_constraint(&mut self, constraint: &Constraint, deep: Option<bool>) -> bool {
+        let id = constraint.get_id();
+        let count = self.constraints.len();
+        self.constraints
+            .retain(|constraint| constraint.get_id() != id);
+        let mut is_removed = self.constraints.len() != count;
+
+        if deep.unwrap_or(false) {
+            for composite in self.composites.iter_mut() {
+                is_removed |= composite.remove_constraint(constraint, deep);
+            }
+        }
+
+        self.is_modified |= is_removed;
+        is_removed
+    }
+
+    pub fn remove_composite(&mut self, id: Uuid, deep: Option<bool>) -> bool {
+        let count = self.composites.len();
+        self.composites.retain(|composite| composite.get_id() != id);
+        let mut is_removed = self.composites.len() != count;
+
+        if deep.unwrap_or(false) {
+            for composite in self.composites.iter_mut() {
+                is_removed |= composite.remove_composite(id, deep);
+            }
+        }
+
+        self.is_modified |= is_removed;
+        is_removed
+    }
+
+    // Removes everything, or everything but the static bodies when keep_static is set.
+    pub fn clear(&mut self, keep_static: bool, deep: Option<bool>) {
+        if deep.unwrap_or(false) {
+            for composite in self.composites.iter_mut() {
+                composite.clear(keep_static, deep);
+            }
+        }
+
+        if keep_static {
+            self.bodies.retain(|body| body.get_is_static());
+        } else {
+            self.bodies.clear();
+        }
+        self.constraints.clear();
+        self.composites.clear();
+        self.is_modified = true;
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+    pub fn translate(&mut self, translation: &impl XY, recursive: Option<bool>) {
+        for body in self.bodies_for(recursive).iter_mut() {
+            body.translate(translation, None);
+        }
+    }
+
+    pub fn rotate(&mut self, rotation: Real, point: &impl XY, recursive: Option<bool>) {
+        for body in self.bodies_for(recursive).iter_mut() {
+            body.rotate(rotation, Some(point), None);
+        }
+    }
+
+    // Draws the layers of the whole tree through the backend, the contacts are taken from the
+    // pairs when given.
+    pub fn debug_draw(&self, draw: &mut impl DebugDraw, pairs: Option<&Pairs>, layers: &Layers) {
+        debug_draw::debug_draw(draw, self, pairs, layers);
+    }
+
+    fn bodies_for(&self, recursive: Option<bool>) -> Vec<Body> {
+        if recursive.unwrap_or(true) {
+            self.all_bodies()
+        } else {
+            self.bodies.clone()
+        }
+    }
+    // endregion: Actions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constraint::constraint_option::ConstraintOption;
+    use crate::core::position::Position;
+    use crate::core::xy::XYNew;
+    use crate::test_utils::{
+        body_test_utils::test_box, common_test_utils::assert_float,
+        geometry_test_utils::assert_bounds,
+    };
+
+    use super::*;
+
+    fn test_tree() -> (Composite, Body, Body, Constraint) {
+        let body_a = test_box(0., 0., 10., 10.);
+        let body_b = test_box(100., 50., 20., 20.);
+        let constraint = Constraint::create(&[
+            ConstraintOption::BodyA(body_a.clone()),
+            ConstraintOption::BodyB(body_b.clone()),
+        ]);
+
+        let mut child = Composite::new();
+        child.add_body(&body_b);
+        child.add_constraint(&constraint);
+
+        let mut composite = Composite::new();
+        composite.add_body(&body_a);
+        composite.add_composite(child);
+        (composite, body_a, body_b, constraint)
+    }
+
+    #[test]
+    fn all_bodies_should_return_the_bodies_of_the_whole_tree() {
+        // Arrange
+        let (composite, body_a, body_b, constraint) = test_tree();
+
+        // Act
+        let result = composite.all_bodies();
+        let result_constraints = composite.all_constraints();
+
+        // Assert
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].get_id(), body_a.get_id());
+        assert_eq!(result[1].get_id(), body_b.get_id());
+        assert_eq!(result_constraints.len(), 1);
+        assert_eq!(result_constraints[0].get_id(), constraint.get_id());
+        assert_eq!(composite.all_composites().len(), 1);
+        assert!(composite.get_body(body_b.get_id()).is_some());
+    }
+
+    #[test]
+    fn remove_body_should_only_search_children_when_deep() {
+        // Arrange
+        let (mut composite, _, body_b, _) = test_tree();
+        composite.set_modified(false, Some(true));
+
+        // Act
+        let result_shallow = composite.remove_body(&body_b, None);
+        let result_deep = composite.remove_body(&body_b, Some(true));
+
+        // Assert
+        assert_eq!(result_shallow, false);
+        assert_eq!(result_deep, true);
+        assert_eq!(composite.get_is_modified(), true);
+        assert_eq!(composite.all_bodies().len(), 1);
+    }
+
+    #[test]
+    fn get_bounds_should_contain_all_bodies() {
+        // Arrange
+        let (composite, _, _, _) = test_tree();
+
+        // Act
+        let result = composite.get_bounds();
+
+        // Assert
+        assert_bounds(&result, -5., -5., 110., 60.);
+        assert!(Composite::new().get_bounds().is_empty());
+    }
+
+    #[test]
+    fn translate_should_move_every_body_of_the_tree() {
+        // Arrange
+        let (mut composite, body_a, body_b, _) = test_tree();
+
+        // Act
+        composite.translate(&Position::new(10., -10.), None);
+
+        // Assert
+        assert_float(body_a.get_position().get_x(), 10.);
+        assert_float(body_a.get_position().get_y(), -10.);
+        assert_float(body_b.get_position().get_x(), 110.);
+        assert_float(body_b.get_position().get_y(), 40.);
+    }
+
+    #[test]
+    fn clear_should_keep_static_bodies_when_asked() {
+        // Arrange
+        let (mut composite, mut body_a, _, _) = test_tree();
+        body_a.set_static(true);
+
+        // Act
+        composite.clear(true, None);
+
+        // Assert
+        assert_eq!(composite.get_bodies().len(), 1);
+        assert_eq!(composite.all_bodies().len(), 1);
+        assert_eq!(composite.get_composites().len(), 0);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod constraint;
+pub mod constraint_option;
+pub mod mouse_constraint;
@@ -0,0 +1,502 @@
+use std::sync::{Arc, Mutex};
+
+use uuid::Uuid;
+
+use crate::body::body::Body;
+use crate::core::common;
+use crate::core::constraint_impulse::ConstraintImpulse;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::vector::Vector;
+
+use super::constraint_option::ConstraintOption;
+
+const WARMING: Real = 0.4;
+const TORQUE_DAMPEN: Real = 1.;
+const MIN_LENGTH: Real = 0.000001;
+
+// Keeps two bodies, or a body and a point in the world, at a distance. The points are offsets
+// from the body positions and follow the rotation of their bodies.
+#[derive(Clone)]
+pub struct Constraint {
+    content: Arc<Mutex<ConstraintContent>>,
+}
+
+struct ConstraintContent {
+    angle_a: Real,
+    angle_b: Real,
+    angular_stiffness: Real,
+    body_a: Option<Body>,
+    body_b: Option<Body>,
+    damping: Real,
+    id: Uuid,
+    length: Real,
+    point_a: Vector,
+    point_b: Vector,
+    stiffness: Real,
+}
+
+macro_rules! content {
+    ($a:expr) => {
+        $a.content.lock().unwrap()
+    };
+}
+
+impl Constraint {
+    // The length defaults to the distance between the points at creation and the stiffness to
+    // rigid, or to 0.7 for a constraint without length.
+    pub fn create(options: &[ConstraintOption]) -> Self {
+        let content = ConstraintContent {
+            angle_a: 0.,
+            angle_b: 0.,
+            angular_stiffness: 0.,
+            body_a: None,
+            body_b: None,
+            damping: 0.,
+            id: common::next_id(),
+            length: 0.,
+            point_a: Vector::create(0., 0.),
+            point_b: Vector::create(0., 0.),
+            stiffness: 1.,
+        };
+        let mut constraint = Constraint {
+            content: Arc::new(Mutex::new(content)),
+        };
+
+        for option in options.iter() {
+            constraint.set_one(option);
+        }
+
+        if !options
+            .iter()
+            .any(|option| matches!(option, ConstraintOption::Length(_)))
+        {
+            let delta = constraint.get_world_point_a() - constraint.get_world_point_b();
+            constraint.set_length(delta.magnitude());
+        }
+
+        if !options
+            .iter()
+            .any(|option| matches!(option, ConstraintOption::Stiffness(_)))
+        {
+            let stiffness = if constraint.get_length() > 0. {
+                1.
+            } else {
+                0.7
+            };
+            constraint.set_stiffness(stiffness);
+        }
+
+        if let Some(body) = constraint.get_body_a() {
+            constraint.set_angle_a(body.get_angle());
+        }
+        if let Some(body) = constraint.get_body_b() {
+            constraint.set_angle_b(body.get_angle());
+        }
+        constraint
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_id(&self) -> Uuid {
+        content!(self).id
+    }
+
+    pub fn get_body_a(&self) -> Option<Body> {
+        content!(self).body_a.clone()
+    }
+
+    pub fn get_body_b(&self) -> Option<Body> {
+        content!(self).body_b.clone()
+    }
+
+    pub fn get_point_a(&self) -> Vector {
+        content!(self).point_a
+    }
+
+    pub fn get_point_b(&self) -> Vector {
+        content!(self).point_b
+    }
+
+    pub fn get_world_point_a(&self) -> Vector {
+        Constraint::world_point(&self.get_body_a(), &self.get_point_a())
+    }
+
+    pub fn get_world_point_b(&self) -> Vector {
+        Constraint::world_point(&self.get_body_b(), &self.get_point_b())
+    }
+
+    pub fn get_length(&self) -> Real {
+        content!(self).length
+    }
+
+    pub fn get_stiffness(&self) -> Real {
+        content!(self).stiffness
+    }
+
+    pub fn get_damping(&self) -> Real {
+        content!(self).damping
+    }
+
+    pub fn get_angular_stiffness(&self) -> Real {
+        content!(self).angular_stiffness
+    }
+
+    pub fn get_angle_a(&self) -> Real {
+        content!(self).angle_a
+    }
+
+    pub fn get_angle_b(&self) -> Real {
+        content!(self).angle_b
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+    pub fn set_body_a(&mut self, body: Option<&Body>) {
+        content!(self).body_a = body.cloned();
+    }
+
+    pub fn set_body_b(&mut self, body: Option<&Body>) {
+        content!(self).body_b = body.cloned();
+    }
+
+    pub fn set_point_a(&mut self, point: &impl XY) {
+        content!(self).point_a = Vector::new_from(point);
+    }
+
+    pub fn set_point_b(&mut self, point: &impl XY) {
+        content!(self).point_b = Vector::new_from(point);
+    }
+
+    pub fn set_length(&mut self, value: Real) {
+        content!(self).length = value;
+    }
+
+    pub fn set_stiffness(&mut self, value: Real) {
+        content!(self).stiffness = value;
+    }
+
+    pub fn set_damping(&mut self, value: Real) {
+        content!(self).damping = value;
+    }
+
+    pub fn set_angular_stiffness(&mut self, value: Real) {
+        content!(self).angular_stiffness = value;
+    }
+
+    pub fn set_angle_a(&mut self, value: Real) {
+        content!(self).angle_a = value;
+    }
+
+    pub fn set_angle_b(&mut self, value: Real) {
+        content!(self).angle_b = value;
+    }
+
+    pub fn set_one(&mut self, option: &ConstraintOption) {
+        match option {
+            ConstraintOption::AngleA(value) => self.set_angle_a(*value),
+            ConstraintOption::AngleB(value) => self.set_angle_b(*value),
+            ConstraintOption::AngularStiffness(value) => self.set_angular_stiffness(*value),
+            ConstraintOption::BodyA(value) => self.set_body_a(Some(value)),
+            ConstraintOption::BodyB(value) => self.set_body_b(Some(value)),
+            ConstraintOption::Damping(value) => self.set_damping(*value),
+            ConstraintOption::Length(value) => self.set_length(*value),
+            ConstraintOption::PointA(value) => self.set_point_a(value),
+            ConstraintOption::PointB(value) => self.set_point_b(value),
+            ConstraintOption::Stiffness(value) => self.set_stiffness(*value),
+        }
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+
+    // Applies the impulses kept from the last step.
+    pub fn pre_solve_all(bodies: &mut [Body]) {
+        for body in bodies.iter_mut() {
+            let impulse = body.get_constraint_impulse();
+            if body.get_is_static() || Constraint::is_zero(&impulse) {
+                continue;
+            }
+
+            Constraint::shift(body, &Vector::new_from(&impulse), impulse.get_angle());
+        }
+    }
+
+    // Constraints with a static or missing body are solved first.
+    pub fn solve_all(constraints: &mut [Constraint], delta: Real) {
+        let time_scale = common::clamp(delta / common::BASE_DELTA, 0., 1.);
+
+        for constraint in constraints
+            .iter_mut()
+            .filter(|constraint| constraint.is_fixed())
+        {
+            constraint.solve(time_scale);
+        }
+
+        for constraint in constraints
+            .iter_mut()
+            .filter(|constraint| !constraint.is_fixed())
+        {
+            constraint.solve(time_scale);
+        }
+    }
+
+    pub fn solve(&mut self, time_scale: Real) {
+        let body_a = self.get_body_a();
+        let body_b = self.get_body_b();
+        if body_a.is_none() && body_b.is_none() {
+            return;
+        }
+
+        // rotate the points with their bodies since the last solve
+        let mut point_a = self.get_point_a();
+        if let Some(body) = body_a.as_ref().filter(|body| !body.get_is_static()) {
+            point_a.rotate(body.get_angle() - self.get_angle_a());
+            self.set_point_a(&point_a);
+            self.set_angle_a(body.get_angle());
+        }
+
+        let mut point_b = self.get_point_b();
+        if let Some(body) = body_b.as_ref().filter(|body| !body.get_is_static()) {
+            point_b.rotate(body.get_angle() - self.get_angle_b());
+            self.set_point_b(&point_b);
+            self.set_angle_b(body.get_angle());
+        }
+
+        let delta =
+            Constraint::world_point(&body_a, &point_a) - Constraint::world_point(&body_b, &point_b);
+        let current_length = delta.magnitude().max(MIN_LENGTH);
+
+        let length = self.get_length();
+        let stiffness = self.get_stiffness();
+        let is_rigid = stiffness >= 1. || length == 0.;
+        let stiffness = if is_rigid {
+            stiffness * time_scale
+        } else {
+            stiffness * time_scale * time_scale
+        };
+        let damping = self.get_damping() * time_scale;
+        let force = delta * ((current_length - length) / current_length * stiffness);
+
+        let inverse_mass = |body: &Option<Body>| body.as_ref().map_or(0., Body::get_inverse_mass);
+        let inverse_inertia =
+            |body: &Option<Body>| body.as_ref().map_or(0., Body::get_inverse_inertia);
+        let mass_total = inverse_mass(&body_a) + inverse_mass(&body_b);
+        let resistance_total = mass_total + inverse_inertia(&body_a) + inverse_inertia(&body_b);
+        let torque_share = TORQUE_DAMPEN * (1. - self.get_angular_stiffness()) / resistance_total;
+
+        let normal = delta / current_length;
+        let normal_velocity = if damping > 0. {
+            normal.dot(&(Constraint::velocity(&body_b) - Constraint::velocity(&body_a)))
+        } else {
+            0.
+        };
+
+        if let Some(mut body) = body_a.filter(|body| !body.get_is_static()) {
+            let share = body.get_inverse_mass() / mass_total;
+            let torque =
+                Vector::cross(&point_a, &force) * torque_share * body.get_inverse_inertia();
+            Constraint::apply(
+                &mut body,
+                &(force * -share),
+                -torque,
+                &(normal * (-damping * normal_velocity * share)),
+            );
+        }
+
+        if let Some(mut body) = body_b.filter(|body| !body.get_is_static()) {
+            let share = body.get_inverse_mass() / mass_total;
+            let torque =
+                Vector::cross(&point_b, &force) * torque_share * body.get_inverse_inertia();
+            Constraint::apply(
+                &mut body,
+                &(force * share),
+                torque,
+                &(normal * (damping * normal_velocity * share)),
+            );
+        }
+    }
+
+    // Wakes the constrained bodies and keeps part of their impulse for the next step.
+    pub fn post_solve_all(bodies: &mut [Body]) {
+        for body in bodies.iter_mut() {
+            let mut impulse = body.get_constraint_impulse();
+            if body.get_is_static() || Constraint::is_zero(&impulse) {
+                continue;
+            }
+
+            body.set_is_sleeping(false);
+            impulse.mult(WARMING);
+            impulse.set_angle(impulse.get_angle() * WARMING);
+            body.set_constraint_impulse(&impulse);
+        }
+    }
+
+    fn is_fixed(&self) -> bool {
+        let is_free = |body: Option<Body>| body.is_some_and(|body| !body.get_is_static());
+        !is_free(self.get_body_a()) || !is_free(self.get_body_b())
+    }
+
+    fn is_zero(impulse: &ConstraintImpulse) -> bool {
+        impulse.get_x() == 0. && impulse.get_y() == 0. && impulse.get_angle() == 0.
+    }
+
+    fn world_point(body: &Option<Body>, point: &Vector) -> Vector {
+        match body {
+            Some(body) => Vector::new_from(&body.get_position()) + *point,
+            None => *point,
+        }
+    }
+
+    fn velocity(body: &Option<Body>) -> Vector {
+        match body {
+            Some(body) => {
+                let position = body.get_position();
+                let position_prev = body.get_position_prev().unwrap_or(position);
+                Vector::new_from(&position) - Vector::new_from(&position_prev)
+            }
+            None => Vector::create(0., 0.),
+        }
+    }
+
+    // Moves the body and records the move in its constraint impulse. The damping only changes
+    // the previous position, so it acts on the velocity alone.
+    fn apply(body: &mut Body, offset: &Vector, angle: Real, damping: &Vector) {
+        let mut impulse = body.get_constraint_impulse();
+        impulse.add_xy(offset);
+        impulse.set_angle(impulse.get_angle() + angle);
+        body.set_constraint_impulse(&impulse);
+
+        Constraint::shift(body, offset, angle);
+        if damping.get_x() != 0. || damping.get_y() != 0. {
+            let mut position_prev = body.get_position_prev().unwrap_or(body.get_position());
+            position_prev.add_xy(damping);
+            body.set_position_prev(&position_prev);
+        }
+    }
+
+    // Changes the pose but not the previous pose, so the move shows up in the velocity.
+    fn shift(body: &mut Body, offset: &Vector, angle: Real) {
+        let position_prev = body.get_position_prev().unwrap_or(body.get_position());
+        let angle_prev = body.get_angle_prev();
+
+        if offset.get_x() != 0. || offset.get_y() != 0. {
+            body.translate(offset, None);
+        }
+        if angle != 0. {
+            body.rotate(angle, None::<&Vector>, None);
+        }
+
+        body.set_position_prev(&position_prev);
+        body.set_angle_prev(angle_prev);
+    }
+    // endregion: Actions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{body_test_utils::test_box, common_test_utils::assert_float};
+
+    use super::*;
+
+    #[test]
+    fn create_should_default_the_length_and_stiffness_from_the_initial_points() {
+        // Arrange
+        let body = test_box(0., 100., 10., 10.);
+
+        // Act
+        let result = Constraint::create(&[
+            ConstraintOption::BodyB(body.clone()),
+            ConstraintOption::PointA(Vector::create(30., 60.)),
+        ]);
+        let result_zero = Constraint::create(&[
+            ConstraintOption::BodyB(body.clone()),
+            ConstraintOption::PointA(Vector::create(30., 60.)),
+            ConstraintOption::Length(0.),
+        ]);
+
+        // Assert
+        assert_float(result.get_length(), 50.);
+        assert_float(result.get_stiffness(), 1.);
+        assert_float(result_zero.get_length(), 0.);
+        assert_float(result_zero.get_stiffness(), 0.7);
+    }
+
+    #[test]
+    fn solve_all_should_pull_a_body_back_to_the_length_of_a_rigid_constraint() {
+        // Arrange
+        let mut body = test_box(0., 150., 10., 10.);
+        let mut constraints = vec![Constraint::create(&[
+            ConstraintOption::PointA(Vector::create(0., 0.)),
+            ConstraintOption::BodyB(body.clone()),
+            ConstraintOption::Length(100.),
+        ])];
+
+        // Act
+        Constraint::solve_all(&mut constraints, common::BASE_DELTA);
+        let result_impulse = body.get_constraint_impulse();
+        Constraint::post_solve_all(std::slice::from_mut(&mut body));
+
+        // Assert
+        assert_float(body.get_position().get_x(), 0.);
+        assert_float(body.get_position().get_y(), 100.);
+        assert_float(body.get_position_prev().unwrap().get_y(), 150.);
+        assert_float(result_impulse.get_y(), -50.);
+        assert_float(body.get_constraint_impulse().get_y(), -20.);
+    }
+
+    #[test]
+    fn solve_all_should_share_the_correction_by_inverse_mass() {
+        // Arrange
+        let body_a = test_box(0., 0., 10., 10.);
+        let mut body_b = test_box(30., 0., 10., 10.);
+        body_b.set_static(true);
+        let body_c = test_box(60., 0., 10., 10.);
+        let mut constraints = vec![
+            Constraint::create(&[
+                ConstraintOption::BodyA(body_a.clone()),
+                ConstraintOption::BodyB(body_c.clone()),
+                ConstraintOption::Length(40.),
+            ]),
+            Constraint::create(&[
+                ConstraintOption::BodyA(body_b.clone()),
+                ConstraintOption::BodyB(body_c.clone()),
+                ConstraintOption::Length(20.),
+            ]),
+        ];
+
+        // Act
+        Constraint::solve_all(&mut constraints, common::BASE_DELTA);
+
+        // Assert
+        assert_float(body_b.get_position().get_x(), 30.);
+        assert_float(body_c.get_position().get_x(), 45.);
+        assert_float(body_a.get_position().get_x(), 5.);
+        assert_eq!(body_a.get_position().get_y(), 0.);
+    }
+
+    #[test]
+    fn pre_solve_all_should_move_bodies_by_their_kept_impulse() {
+        // Arrange
+        let mut body = test_box(0., 0., 10., 10.);
+        body.set_constraint_impulse(&ConstraintImpulse::new(2., -1., 0.));
+        let position_prev = body.get_position_prev().unwrap();
+
+        // Act
+        Constraint::pre_solve_all(std::slice::from_mut(&mut body));
+
+        // Assert
+        assert_float(body.get_position().get_x(), 2.);
+        assert_float(body.get_position().get_y(), -1.);
+        assert_float(
+            body.get_position_prev().unwrap().get_x(),
+            position_prev.get_x(),
+        );
+        assert_float(
+            body.get_position_prev().unwrap().get_y(),
+            position_prev.get_y(),
+        );
+    }
+}
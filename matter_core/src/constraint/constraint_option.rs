@@ -0,0 +1,15 @@
+use crate::{body::body::Body, core::real::Real, geometry::vector::Vector};
+
+#[derive(Clone)]
+pub enum ConstraintOption {
+    AngleA(Real),
+    AngleB(Real),
+    AngularStiffness(Real),
+    BodyA(Body),
+    BodyB(Body),
+    Damping(Real),
+    Length(Real),
+    PointA(Vector),
+    PointB(Vector),
+    Stiffness(Real),
+}
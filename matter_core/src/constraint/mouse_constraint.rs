@@ -0,0 +1,268 @@
+use crate::body::body::Body;
+use crate::collision::query;
+use crate::core::collision_filter::CollisionFilter;
+use crate::core::pointer::{Pointer, PointerButton};
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::vector::Vector;
+
+use super::constraint::Constraint;
+use super::constraint_option::ConstraintOption;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DragEventKind {
+    StartDrag,
+    EndDrag,
+}
+
+#[derive(Clone)]
+pub struct DragEvent {
+    pub kind: DragEventKind,
+    pub body: Body,
+}
+
+// Drags bodies with a pointer. While the primary button is down the body under the pointer is
+// held by a spring from the pointer to the point it was picked at. The constraint has to be
+// solved with the others, e.g. by adding it to the world.
+pub struct MouseConstraint {
+    pointer: Pointer,
+    constraint: Constraint,
+    collision_filter: CollisionFilter,
+}
+
+impl MouseConstraint {
+    // The options are applied over a short and stiff spring.
+    pub fn new(pointer: Pointer, options: &[ConstraintOption]) -> Self {
+        let mut constraint_options = vec![
+            ConstraintOption::PointA(Vector::new_from(&pointer.get_position())),
+            ConstraintOption::PointB(Vector::create(0., 0.)),
+            ConstraintOption::Length(0.01),
+            ConstraintOption::Stiffness(0.1),
+            ConstraintOption::AngularStiffness(1.),
+        ];
+        constraint_options.extend_from_slice(options);
+
+        MouseConstraint {
+            pointer: pointer,
+            constraint: Constraint::create(&constraint_options),
+            collision_filter: CollisionFilter::new(1, u32::MAX, 0),
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_pointer(&self) -> &Pointer {
+        &self.pointer
+    }
+
+    pub fn get_pointer_mut(&mut self) -> &mut Pointer {
+        &mut self.pointer
+    }
+
+    pub fn get_constraint(&self) -> Constraint {
+        self.constraint.clone()
+    }
+
+    // The body being dragged.
+    pub fn get_body(&self) -> Option<Body> {
+        self.constraint.get_body_b()
+    }
+
+    pub fn get_collision_filter(&self) -> CollisionFilter {
+        self.collision_filter
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+
+    // Only bodies whose filter can collide with this one are picked.
+    pub fn set_collision_filter(&mut self, value: &CollisionFilter) {
+        self.collision_filter = *value;
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+
+    // Picks, drags or drops a body following the pointer, to be called once before each update
+    // of the engine. The events of the pointer are cleared afterwards.
+    pub fn update(&mut self, bodies: &[Body]) -> Option<DragEvent> {
+        let event = self.drag(bodies);
+        self.pointer.clear_events();
+        event
+    }
+
+    fn drag(&mut self, bodies: &[Body]) -> Option<DragEvent> {
+        let position = self.pointer.get_position();
+
+        if self.pointer.get_button() == Some(PointerButton::Primary) {
+            match self.constraint.get_body_b() {
+                Some(mut body) => {
+                    body.set_is_sleeping(false);
+                    self.constraint.set_point_a(&position);
+                }
+                None => {
+                    let candidates: Vec<Body> = bodies
+                        .iter()
+                        .filter(|body| {
+                            CollisionFilter::can_collide(
+                                &body.get_collision_filter(),
+                                &self.collision_filter,
+                            )
+                        })
+                        .cloned()
+                        .collect();
+                    let mut body = query::point(&candidates, &position).into_iter().next()?;
+
+                    let body_position = body.get_position();
+                    self.constraint.set_point_a(&position);
+                    self.constraint.set_body_b(Some(&body));
+                    self.constraint.set_point_b(&Vector::create(
+                        position.get_x() - body_position.get_x(),
+                        position.get_y() - body_position.get_y(),
+                    ));
+                    self.constraint.set_angle_b(body.get_angle());
+                    body.set_is_sleeping(false);
+
+                    return Some(DragEvent {
+                        kind: DragEventKind::StartDrag,
+                        body: body,
+                    });
+                }
+            }
+        } else if let Some(body) = self.constraint.get_body_b() {
+            self.constraint.set_body_b(None);
+            self.constraint.set_point_b(&Vector::create(0., 0.));
+
+            return Some(DragEvent {
+                kind: DragEventKind::EndDrag,
+                body: body,
+            });
+        }
+        None
+    }
+    // endregion: Actions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::common;
+    use crate::core::pointer::PointerEvent;
+    use crate::core::position::Position;
+    use crate::core::real::Real;
+    use crate::test_utils::{
+        body_test_utils::test_box, common_test_utils::assert_float, geometry_test_utils::assert_xy,
+    };
+
+    use super::*;
+
+    fn press(mouse_constraint: &mut MouseConstraint, x: Real, y: Real) {
+        mouse_constraint
+            .get_pointer_mut()
+            .handle(&PointerEvent::Down(
+                Position::new(x, y),
+                PointerButton::Primary,
+            ));
+    }
+
+    #[test]
+    fn update_should_pick_the_body_under_the_pointer_and_wake_it() {
+        // Arrange
+        let mut body = test_box(0., 0., 20., 20.);
+        body.set_is_sleeping(true);
+        let bodies = vec![test_box(100., 0., 20., 20.), body.clone()];
+        let mut mouse_constraint = MouseConstraint::new(Pointer::new(), &[]);
+        press(&mut mouse_constraint, 5., -5.);
+
+        // Act
+        let result = mouse_constraint.update(&bodies).unwrap();
+        let result_held = mouse_constraint.update(&bodies);
+
+        // Assert
+        assert_eq!(result.kind, DragEventKind::StartDrag);
+        assert_eq!(result.body.get_id(), body.get_id());
+        assert!(result_held.is_none());
+        assert_eq!(mouse_constraint.get_body().unwrap().get_id(), body.get_id());
+        assert_eq!(body.get_is_sleeping(), false);
+        assert_xy(&mouse_constraint.get_constraint().get_point_b(), 5., -5.);
+    }
+
+    #[test]
+    fn update_should_move_the_anchor_with_the_pointer_and_drag_the_body() {
+        // Arrange
+        let body = test_box(0., 0., 20., 20.);
+        let bodies = vec![body.clone()];
+        let mut mouse_constraint = MouseConstraint::new(Pointer::new(), &[]);
+        press(&mut mouse_constraint, 0., 0.);
+        mouse_constraint.update(&bodies);
+        mouse_constraint
+            .get_pointer_mut()
+            .handle(&PointerEvent::Move(Position::new(50., 0.)));
+
+        // Act
+        mouse_constraint.update(&bodies);
+        let mut constraints = vec![mouse_constraint.get_constraint()];
+        Constraint::solve_all(&mut constraints, common::BASE_DELTA);
+
+        // Assert
+        assert_xy(&mouse_constraint.get_constraint().get_point_a(), 50., 0.);
+        assert!(body.get_position().get_x() > 0.);
+    }
+
+    #[test]
+    fn update_should_drop_the_body_when_the_button_is_released() {
+        // Arrange
+        let body = test_box(0., 0., 20., 20.);
+        let bodies = vec![body.clone()];
+        let mut mouse_constraint = MouseConstraint::new(Pointer::new(), &[]);
+        press(&mut mouse_constraint, 0., 0.);
+        mouse_constraint.update(&bodies);
+        mouse_constraint.get_pointer_mut().handle(&PointerEvent::Up(
+            Position::new(0., 0.),
+            PointerButton::Primary,
+        ));
+
+        // Act
+        let result = mouse_constraint.update(&bodies).unwrap();
+
+        // Assert
+        assert_eq!(result.kind, DragEventKind::EndDrag);
+        assert_eq!(result.body.get_id(), body.get_id());
+        assert!(mouse_constraint.get_body().is_none());
+    }
+
+    #[test]
+    fn update_should_only_pick_bodies_allowed_by_the_collision_filter() {
+        // Arrange
+        let mut body = test_box(0., 0., 20., 20.);
+        let mut collision_filter = body.get_collision_filter();
+        collision_filter.set_category(2);
+        body.set_collision_filter(&collision_filter);
+        let bodies = vec![body];
+        let mut mouse_constraint = MouseConstraint::new(Pointer::new(), &[]);
+        mouse_constraint.set_collision_filter(&CollisionFilter::new(1, !2, 0));
+        press(&mut mouse_constraint, 0., 0.);
+
+        // Act
+        let result = mouse_constraint.update(&bodies);
+
+        // Assert
+        assert!(result.is_none());
+        assert!(mouse_constraint.get_body().is_none());
+    }
+
+    #[test]
+    fn update_should_clear_the_wheel_delta() {
+        // Arrange
+        let mut mouse_constraint = MouseConstraint::new(Pointer::new(), &[]);
+        mouse_constraint
+            .get_pointer_mut()
+            .handle(&PointerEvent::Wheel(-3.));
+
+        // Act
+        mouse_constraint.update(&[]);
+
+        // Assert
+        assert_float(mouse_constraint.get_pointer_mut().get_wheel_delta(), 0.);
+    }
+}
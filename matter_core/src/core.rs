@@ -1,9 +1,16 @@
 pub mod collision_filter;
+pub mod collision_layers;
+pub mod color;
 pub mod common;
 pub mod constraint_impulse;
+pub mod fixed;
 pub mod force;
+pub mod math;
+pub mod pointer;
 pub mod position;
+pub mod real;
 pub mod render;
+pub mod runner;
 pub mod sprite;
 pub mod velocity;
 pub mod xy;
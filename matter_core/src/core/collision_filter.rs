@@ -1,12 +1,28 @@
-#[derive(Clone, Copy, Default)]
+use std::sync::atomic::{AtomicI32, AtomicU32, Ordering};
+
+static NEXT_COLLIDING_GROUP_ID: AtomicI32 = AtomicI32::new(1);
+static NEXT_NON_COLLIDING_GROUP_ID: AtomicI32 = AtomicI32::new(-1);
+static NEXT_CATEGORY: AtomicU32 = AtomicU32::new(1);
+
+// Categories are single bits of a 32 bit mask. Bodies sharing a non zero group always collide
+// when the group is positive and never when it is negative, otherwise the category of each body
+// has to be in the mask of the other.
+#[derive(Clone, Copy)]
 pub struct CollisionFilter {
-    category: u16,
+    category: u32,
     mask: u32,
-    group: u16,
+    group: i32,
+}
+
+// The default category colliding with every category and no group, as the filter of a body.
+impl Default for CollisionFilter {
+    fn default() -> Self {
+        CollisionFilter::new(1, u32::MAX, 0)
+    }
 }
 
 impl CollisionFilter {
-    pub fn new(category: u16, mask: u32, group: u16) -> Self {
+    pub fn new(category: u32, mask: u32, group: i32) -> Self {
         CollisionFilter {
             category: category,
             mask: mask,
@@ -14,7 +30,7 @@ impl CollisionFilter {
         }
     }
 
-    pub fn get_category(&self) -> u16 {
+    pub fn get_category(&self) -> u32 {
         self.category
     }
 
@@ -22,7 +38,133 @@ impl CollisionFilter {
         self.mask
     }
 
-    pub fn get_group(&self) -> u16 {
+    pub fn get_group(&self) -> i32 {
         self.group
     }
+
+    pub fn set_category(&mut self, value: u32) {
+        self.category = value;
+    }
+
+    pub fn set_mask(&mut self, value: u32) {
+        self.mask = value;
+    }
+
+    pub fn set_group(&mut self, value: i32) {
+        self.group = value;
+    }
+
+    pub fn can_collide(filter_a: &CollisionFilter, filter_b: &CollisionFilter) -> bool {
+        if filter_a.group == filter_b.group && filter_a.group != 0 {
+            return filter_a.group > 0;
+        }
+
+        (filter_a.mask & filter_b.category) != 0 && (filter_b.mask & filter_a.category) != 0
+    }
+
+    // A new group id, negative for groups whose members never collide with each other.
+    pub fn next_group(non_colliding: bool) -> i32 {
+        if non_colliding {
+            NEXT_NON_COLLIDING_GROUP_ID.fetch_sub(1, Ordering::Relaxed)
+        } else {
+            NEXT_COLLIDING_GROUP_ID.fetch_add(1, Ordering::Relaxed)
+        }
+    }
+
+    // The next unused category bit, the first bit is the default category. None once all 32
+    // bits are taken.
+    pub fn next_category() -> Option<u32> {
+        NEXT_CATEGORY
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |category| {
+                category.checked_shl(1).filter(|next| *next != 0)
+            })
+            .ok()
+            .map(|category| category << 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_collide_should_use_the_shared_group_first() {
+        // Arrange
+        let colliding_a = CollisionFilter::new(1, 0, 3);
+        let colliding_b = CollisionFilter::new(2, 0, 3);
+        let non_colliding_a = CollisionFilter::new(1, u32::MAX, -3);
+        let non_colliding_b = CollisionFilter::new(1, u32::MAX, -3);
+
+        // Act
+        let result_colliding = CollisionFilter::can_collide(&colliding_a, &colliding_b);
+        let result_non_colliding = CollisionFilter::can_collide(&non_colliding_a, &non_colliding_b);
+
+        // Assert
+        assert_eq!(result_colliding, true);
+        assert_eq!(result_non_colliding, false);
+    }
+
+    #[test]
+    fn can_collide_should_require_both_masks_to_contain_the_other_category() {
+        // Arrange
+        let filter_a = CollisionFilter::new(0b01, 0b10, 0);
+        let filter_b = CollisionFilter::new(0b10, 0b01, 0);
+        let filter_c = CollisionFilter::new(0b10, 0b10, 4);
+
+        // Act
+        let result = CollisionFilter::can_collide(&filter_a, &filter_b);
+        let result_one_sided = CollisionFilter::can_collide(&filter_a, &filter_c);
+
+        // Assert
+        assert_eq!(result, true);
+        assert_eq!(result_one_sided, false);
+    }
+
+    #[test]
+    fn next_group_should_count_away_from_zero() {
+        // Arrange
+
+        // Act
+        let result_colliding = [
+            CollisionFilter::next_group(false),
+            CollisionFilter::next_group(false),
+        ];
+        let result_non_colliding = [
+            CollisionFilter::next_group(true),
+            CollisionFilter::next_group(true),
+        ];
+
+        // Assert
+        assert!(result_colliding[0] > 0 && result_colliding[1] > result_colliding[0]);
+        assert!(result_non_colliding[0] < 0 && result_non_colliding[1] < result_non_colliding[0]);
+    }
+
+    #[test]
+    fn default_should_collide_with_every_category() {
+        // Arrange
+        let filter = CollisionFilter::default();
+
+        // Act
+        let result = CollisionFilter::can_collide(&filter, &CollisionFilter::new(4, 1, 0));
+
+        // Assert
+        assert_eq!(filter.get_category(), 1);
+        assert_eq!(filter.get_mask(), u32::MAX);
+        assert_eq!(filter.get_group(), 0);
+        assert!(result);
+    }
+
+    #[test]
+    fn next_category_should_return_unused_single_bits() {
+        // Arrange
+
+        // Act
+        let result_a = CollisionFilter::next_category().unwrap();
+        let result_b = CollisionFilter::next_category().unwrap();
+
+        // Assert
+        assert_eq!(result_a.count_ones(), 1);
+        assert_eq!(result_b.count_ones(), 1);
+        assert!(result_b > result_a && result_a > 1);
+    }
 }
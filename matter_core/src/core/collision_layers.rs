@@ -0,0 +1,126 @@
+use super::collision_filter::CollisionFilter;
+
+// Gives names to category bits, a layer takes the next free category the first time it is used.
+// Each instance has its own 31 categories after the default one, so separate sets of layers
+// don't use up each other's bits.
+#[derive(Clone)]
+pub struct CollisionLayers {
+    layers: Vec<(String, u32)>,
+    last_category: u32,
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        CollisionLayers::new()
+    }
+}
+
+impl CollisionLayers {
+    pub fn new() -> Self {
+        CollisionLayers {
+            layers: Vec::new(),
+            last_category: 1,
+        }
+    }
+
+    // None when no category bits are left.
+    pub fn get_category(&mut self, name: &str) -> Option<u32> {
+        if let Some((_, category)) = self.layers.iter().find(|(layer, _)| layer == name) {
+            return Some(*category);
+        }
+
+        let category = self
+            .last_category
+            .checked_shl(1)
+            .filter(|next| *next != 0)?;
+        self.last_category = category;
+        self.layers.push((name.to_string(), category));
+        Some(category)
+    }
+
+    pub fn get_mask(&mut self, names: &[&str]) -> Option<u32> {
+        let mut mask = 0;
+        for name in names.iter() {
+            mask |= self.get_category(name)?;
+        }
+        Some(mask)
+    }
+
+    // A filter for a body on the layer that collides with the listed layers.
+    pub fn create_filter(
+        &mut self,
+        name: &str,
+        collides_with: &[&str],
+        group: Option<i32>,
+    ) -> Option<CollisionFilter> {
+        let category = self.get_category(name)?;
+        let mask = self.get_mask(collides_with)?;
+        Some(CollisionFilter::new(category, mask, group.unwrap_or(0)))
+    }
+
+    pub fn get_name(&self, category: u32) -> Option<&str> {
+        self.layers
+            .iter()
+            .find(|(_, layer_category)| *layer_category == category)
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_category_should_return_the_same_bit_for_the_same_name() {
+        // Arrange
+        let mut layers = CollisionLayers::new();
+
+        // Act
+        let result_player = layers.get_category("player").unwrap();
+        let result_enemy = layers.get_category("enemy").unwrap();
+        let result_player_again = layers.get_category("player").unwrap();
+
+        // Assert
+        assert_eq!(result_player, result_player_again);
+        assert_ne!(result_player, result_enemy);
+        assert_eq!(result_player.count_ones(), 1);
+        assert_eq!(layers.get_name(result_enemy), Some("enemy"));
+    }
+
+    #[test]
+    fn get_category_should_allocate_the_categories_per_instance() {
+        // Arrange
+        let mut layers = CollisionLayers::new();
+        let mut layers_other = CollisionLayers::new();
+
+        // Act
+        let result: Vec<Option<u32>> = (0..32)
+            .map(|index| layers.get_category(&index.to_string()))
+            .collect();
+        let result_other = layers_other.get_category("player");
+
+        // Assert
+        assert_eq!(result[0], Some(2));
+        assert_eq!(result[30], Some(1 << 31));
+        assert_eq!(result[31], None);
+        assert_eq!(result_other, Some(2));
+    }
+
+    #[test]
+    fn create_filter_should_collide_only_with_the_listed_layers() {
+        // Arrange
+        let mut layers = CollisionLayers::new();
+
+        // Act
+        let player = layers.create_filter("player", &["wall"], None).unwrap();
+        let wall = layers
+            .create_filter("wall", &["player", "bullet"], None)
+            .unwrap();
+        let bullet = layers.create_filter("bullet", &["wall"], None).unwrap();
+
+        // Assert
+        assert!(CollisionFilter::can_collide(&player, &wall));
+        assert!(CollisionFilter::can_collide(&bullet, &wall));
+        assert!(!CollisionFilter::can_collide(&player, &bullet));
+    }
+}
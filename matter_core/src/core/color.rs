@@ -0,0 +1,172 @@
+use super::real::Real;
+
+// An 8 bit RGBA color. The named colors are the CSS ones used by the matter.js renderer.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Color {
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+}
+
+const NAMED: [(&str, Color); 12] = [
+    ("black", Color::BLACK),
+    ("white", Color::WHITE),
+    ("transparent", Color::TRANSPARENT),
+    ("red", Color::RED),
+    ("green", Color::GREEN),
+    ("blue", Color::BLUE),
+    ("yellow", Color::YELLOW),
+    ("orange", Color::ORANGE),
+    ("grey", Color::GREY),
+    ("gray", Color::GREY),
+    ("indianred", Color::INDIAN_RED),
+    ("cornflowerblue", Color::CORNFLOWER_BLUE),
+];
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const TRANSPARENT: Color = Color::new(0, 0, 0, 0);
+    pub const RED: Color = Color::rgb(255, 0, 0);
+    pub const GREEN: Color = Color::rgb(0, 128, 0);
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+    pub const YELLOW: Color = Color::rgb(255, 255, 0);
+    pub const ORANGE: Color = Color::rgb(255, 165, 0);
+    pub const GREY: Color = Color::rgb(128, 128, 128);
+    pub const INDIAN_RED: Color = Color::rgb(205, 92, 92);
+    pub const CORNFLOWER_BLUE: Color = Color::rgb(100, 149, 237);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Color {
+            r: r,
+            g: g,
+            b: b,
+            a: a,
+        }
+    }
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Color::new(r, g, b, 255)
+    }
+
+    // A CSS color name, or a hex color as #rgb, #rrggbb or #rrggbbaa.
+    pub fn parse(value: &str) -> Option<Color> {
+        let value = value.trim();
+        if value.starts_with('#') {
+            Color::from_hex(value)
+        } else {
+            Color::from_name(value)
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Color> {
+        NAMED
+            .iter()
+            .find(|(named, _)| named.eq_ignore_ascii_case(name))
+            .map(|(_, color)| *color)
+    }
+
+    pub fn from_hex(hex: &str) -> Option<Color> {
+        let digits = hex.strip_prefix('#')?;
+        if !digits.is_ascii() {
+            return None;
+        }
+        let channel = |index: usize, width: usize| -> Option<u8> {
+            let value = u8::from_str_radix(&digits[index * width..(index + 1) * width], 16).ok()?;
+            Some(if width == 1 { value * 17 } else { value })
+        };
+
+        match digits.len() {
+            3 => Some(Color::rgb(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?)),
+            6 => Some(Color::rgb(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+            8 => Some(Color::new(
+                channel(0, 2)?,
+                channel(1, 2)?,
+                channel(2, 2)?,
+                channel(3, 2)?,
+            )),
+            _ => None,
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_r(&self) -> u8 {
+        self.r
+    }
+
+    pub fn get_g(&self) -> u8 {
+        self.g
+    }
+
+    pub fn get_b(&self) -> u8 {
+        self.b
+    }
+
+    pub fn get_a(&self) -> u8 {
+        self.a
+    }
+
+    pub fn get_alpha(&self) -> Real {
+        self.a as Real / 255.
+    }
+    // endregion: Getters
+
+    pub fn with_alpha(&self, alpha: Real) -> Color {
+        Color {
+            a: (alpha.clamp(0., 1.) * 255.).round() as u8,
+            ..*self
+        }
+    }
+
+    pub fn to_rgba(self) -> [u8; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    // #rrggbb for opaque colors, rgba() otherwise.
+    pub fn to_css(self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            let alpha = (self.get_alpha() * 1000.).round() / 1000.;
+            format!("rgba({},{},{},{})", self.r, self.g, self.b, alpha)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_read_hex_and_named_colors() {
+        // Arrange
+        let values = ["#f19648", "#bbb", "#ffffff80", "IndianRed", "#12", "salmon"];
+
+        // Act
+        let result: Vec<Option<Color>> = values.iter().map(|value| Color::parse(value)).collect();
+
+        // Assert
+        assert_eq!(result[0], Some(Color::rgb(241, 150, 72)));
+        assert_eq!(result[1], Some(Color::rgb(187, 187, 187)));
+        assert_eq!(result[2], Some(Color::new(255, 255, 255, 128)));
+        assert_eq!(result[3], Some(Color::INDIAN_RED));
+        assert_eq!(result[4], None);
+        assert_eq!(result[5], None);
+    }
+
+    #[test]
+    fn to_css_should_write_hex_or_rgba() {
+        // Arrange
+        let color = Color::rgb(241, 150, 72);
+
+        // Act
+        let result = color.to_css();
+        let result_alpha = color.with_alpha(0.5).to_css();
+
+        // Assert
+        assert_eq!(result, "#f19648");
+        assert_eq!(result_alpha, "rgba(241,150,72,0.502)");
+    }
+}
@@ -1,6 +1,8 @@
 use uuid::Uuid;
 
-pub const BASE_DELTA: f64 = 1000. / 60.;
+use super::real::Real;
+
+pub const BASE_DELTA: Real = 1000. / 60.;
 
 #[derive(Clone, Copy, Default)]
 pub enum ShapeType {
@@ -40,7 +42,7 @@ impl<T> OrderedHashMap<T> {
     }
 }
 
-pub fn clamp(value: f64, min: f64, max: f64) -> f64 {
+pub fn clamp(value: Real, min: Real, max: Real) -> Real {
     if value < min {
         min
     } else if value > max {
@@ -54,7 +56,7 @@ pub fn next_id() -> uuid::Uuid {
     Uuid::new_v4()
 }
 
-pub fn sign(value: f64) -> i8 {
+pub fn sign(value: Real) -> i8 {
     if value < 0. {
         -1
     } else {
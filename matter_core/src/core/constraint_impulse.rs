@@ -1,32 +1,33 @@
+use super::real::Real;
 use super::xy::XY;
 
 #[derive(Clone, Copy, Default)]
 pub struct ConstraintImpulse {
-    x: f64,
-    y: f64,
-    angle: f64,
+    x: Real,
+    y: Real,
+    angle: Real,
 }
 
 impl XY for ConstraintImpulse {
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.x
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.y
     }
 
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.x = x;
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.y = y;
     }
 }
 
 impl ConstraintImpulse {
-    pub fn new(x: f64, y: f64, angle: f64) -> Self {
+    pub fn new(x: Real, y: Real, angle: Real) -> Self {
         ConstraintImpulse {
             x: x,
             y: y,
@@ -34,11 +35,11 @@ impl ConstraintImpulse {
         }
     }
 
-    pub fn get_angle(&self) -> f64 {
+    pub fn get_angle(&self) -> Real {
         self.angle
     }
 
-    // fn set_angle(&mut self, angle: f64) {
-    //     self.angle = angle
-    // }
+    pub fn set_angle(&mut self, angle: Real) {
+        self.angle = angle
+    }
 }
@@ -0,0 +1,448 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use super::real::Real;
+
+const FRACTION_BITS: u32 = 32;
+const TWO_PI_Q64: i128 = 115904311329233965478;
+const HALF_PI_Q64: i128 = 28976077832308491370;
+
+// A signed Q32.32 fixed point number. Everything below is integer arithmetic, so the results
+// are bit identical on every platform and compiler. Results that do not fit saturate. Only the
+// transcendental functions in `math` go through it, values are converted from and back to `Real`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed {
+    raw: i64,
+}
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed { raw: 0 };
+    pub const ONE: Fixed = Fixed {
+        raw: 1 << FRACTION_BITS,
+    };
+    pub const MIN: Fixed = Fixed { raw: i64::MIN };
+    pub const MAX: Fixed = Fixed { raw: i64::MAX };
+    pub const PI: Fixed = Fixed { raw: 13493037705 };
+    pub const HALF_PI: Fixed = Fixed { raw: 6746518852 };
+    pub const TWO_PI: Fixed = Fixed { raw: 26986075409 };
+    pub const LN_2: Fixed = Fixed { raw: 2977044472 };
+
+    pub const fn from_raw(raw: i64) -> Self {
+        Fixed { raw: raw }
+    }
+
+    pub fn from_int(value: i32) -> Self {
+        Fixed {
+            raw: (value as i64) << FRACTION_BITS,
+        }
+    }
+
+    // Rounds to the nearest representable value, NaN becomes zero.
+    pub fn from_real(value: Real) -> Self {
+        let scaled = (value as f64 * (1_u64 << FRACTION_BITS) as f64).round();
+        Fixed { raw: scaled as i64 }
+    }
+
+    pub fn to_real(self) -> Real {
+        (self.raw as f64 / (1_u64 << FRACTION_BITS) as f64) as Real
+    }
+
+    pub fn get_raw(&self) -> i64 {
+        self.raw
+    }
+
+    fn saturate(raw: i128) -> Self {
+        Fixed {
+            raw: raw.clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+        }
+    }
+
+    fn div_int(&self, divisor: i64) -> Self {
+        Fixed {
+            raw: self.raw / divisor,
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        Fixed {
+            raw: self.raw.saturating_abs(),
+        }
+    }
+
+    // Negative values have no square root and return zero.
+    pub fn sqrt(&self) -> Self {
+        if self.raw <= 0 {
+            return Fixed::ZERO;
+        }
+
+        let mut remainder = (self.raw as u128) << FRACTION_BITS;
+        let mut result: u128 = 0;
+        let mut bit: u128 = 1 << 126;
+        while bit > remainder {
+            bit >>= 2;
+        }
+        while bit != 0 {
+            if remainder >= result + bit {
+                remainder -= result + bit;
+                result = (result >> 1) + bit;
+            } else {
+                result >>= 1;
+            }
+            bit >>= 2;
+        }
+        Fixed { raw: result as i64 }
+    }
+
+    pub fn sin(&self) -> Self {
+        self.sin_cos().0
+    }
+
+    pub fn cos(&self) -> Self {
+        self.sin_cos().1
+    }
+
+    pub fn sin_cos(&self) -> (Self, Self) {
+        // reduce with a 64 bit fraction so large angles keep their precision
+        let angle = ((self.raw as i128) << FRACTION_BITS).rem_euclid(TWO_PI_Q64);
+        let quadrant = (angle + HALF_PI_Q64 / 2) / HALF_PI_Q64;
+        let reduced = angle - quadrant * HALF_PI_Q64;
+        let reduced = Fixed {
+            raw: ((reduced + (1 << (FRACTION_BITS - 1))) >> FRACTION_BITS) as i64,
+        };
+
+        let sin = Fixed::sin_reduced(reduced);
+        let cos = Fixed::cos_reduced(reduced);
+        match quadrant % 4 {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+
+    // Taylor series for angles within an eighth turn of zero.
+    fn sin_reduced(angle: Fixed) -> Fixed {
+        let squared = angle * angle;
+        let mut sum = Fixed::ONE;
+        for divisor in [156, 110, 72, 42, 20, 6] {
+            sum = Fixed::ONE - (squared * sum).div_int(divisor);
+        }
+        angle * sum
+    }
+
+    fn cos_reduced(angle: Fixed) -> Fixed {
+        let squared = angle * angle;
+        let mut sum = Fixed::ONE;
+        for divisor in [182, 132, 90, 56, 30, 12, 2] {
+            sum = Fixed::ONE - (squared * sum).div_int(divisor);
+        }
+        sum
+    }
+
+    pub fn atan(&self) -> Self {
+        self.atan2(Fixed::ONE)
+    }
+
+    // The angle of the point (x, self), like f64::atan2.
+    pub fn atan2(&self, x: Fixed) -> Self {
+        if self.raw == 0 && x.raw == 0 {
+            return Fixed::ZERO;
+        }
+
+        let y_abs = self.abs();
+        let x_abs = x.abs();
+        let mut angle = if x_abs >= y_abs {
+            Fixed::atan_unit(y_abs / x_abs)
+        } else {
+            Fixed::HALF_PI - Fixed::atan_unit(x_abs / y_abs)
+        };
+        if x.raw < 0 {
+            angle = Fixed::PI - angle;
+        }
+        if self.raw < 0 {
+            -angle
+        } else {
+            angle
+        }
+    }
+
+    // Arc tangent for values between zero and one.
+    fn atan_unit(value: Fixed) -> Fixed {
+        // two argument halvings bring the value below tan(pi / 16) where the series converges fast
+        let mut value = value;
+        for _ in 0..2 {
+            value = value / (Fixed::ONE + (Fixed::ONE + value * value).sqrt());
+        }
+
+        let squared = value * value;
+        let mut power = value;
+        let mut sum = Fixed::ZERO;
+        for (index, divisor) in [1, 3, 5, 7, 9, 11, 13, 15, 17].iter().enumerate() {
+            if index % 2 == 0 {
+                sum = sum + power.div_int(*divisor);
+            } else {
+                sum = sum - power.div_int(*divisor);
+            }
+            power = power * squared;
+        }
+        Fixed { raw: sum.raw << 2 }
+    }
+
+    // Values outside -1 to 1 are clamped.
+    pub fn acos(&self) -> Self {
+        let value = (*self).clamp(-Fixed::ONE, Fixed::ONE);
+        let sin = ((Fixed::ONE - value) * (Fixed::ONE + value)).sqrt();
+        sin.atan2(value)
+    }
+
+    pub fn exp(&self) -> Self {
+        // e^x = 2^k * e^r with r = x - k * ln(2) and r within half of ln(2) of zero
+        let quotient = *self / Fixed::LN_2;
+        let k = (quotient.raw + (1 << (FRACTION_BITS - 1))) >> FRACTION_BITS;
+        if k > 31 {
+            return Fixed::MAX;
+        }
+        if k < -33 {
+            return Fixed::ZERO;
+        }
+
+        let remainder = *self - Fixed::from_int(k as i32) * Fixed::LN_2;
+        let mut sum = Fixed::ONE;
+        for divisor in (1..=12).rev() {
+            sum = Fixed::ONE + (remainder * sum).div_int(divisor);
+        }
+
+        if k >= 0 {
+            Fixed::saturate((sum.raw as i128) << k)
+        } else {
+            Fixed { raw: sum.raw >> -k }
+        }
+    }
+
+    // The natural logarithm, zero and negative values return MIN.
+    pub fn ln(&self) -> Self {
+        if self.raw <= 0 {
+            return Fixed::MIN;
+        }
+
+        // self = m * 2^k with m between one and two
+        let k = 63 - self.raw.leading_zeros() as i64 - FRACTION_BITS as i64;
+        let mantissa = Fixed {
+            raw: if k >= 0 {
+                self.raw >> k
+            } else {
+                self.raw << -k
+            },
+        };
+
+        // ln(m) = 2 * atanh(s) with s = (m - 1) / (m + 1) below one third
+        let s = (mantissa - Fixed::ONE) / (mantissa + Fixed::ONE);
+        let squared = s * s;
+        let mut power = s;
+        let mut sum = Fixed::ZERO;
+        for divisor in (1..=21).step_by(2) {
+            sum = sum + power.div_int(divisor);
+            power = power * squared;
+        }
+
+        Fixed::from_int(k as i32) * Fixed::LN_2 + Fixed { raw: sum.raw << 1 }
+    }
+
+    // Only defined for positive bases, anything else returns zero.
+    pub fn powf(&self, exponent: Fixed) -> Self {
+        if self.raw <= 0 {
+            return Fixed::ZERO;
+        }
+        (exponent * self.ln()).exp()
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+
+    fn add(self, other: Fixed) -> Fixed {
+        Fixed {
+            raw: self.raw.saturating_add(other.raw),
+        }
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+
+    fn sub(self, other: Fixed) -> Fixed {
+        Fixed {
+            raw: self.raw.saturating_sub(other.raw),
+        }
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+
+    fn mul(self, other: Fixed) -> Fixed {
+        Fixed::saturate((self.raw as i128 * other.raw as i128) >> FRACTION_BITS)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+
+    fn div(self, other: Fixed) -> Fixed {
+        if other.raw == 0 {
+            return match self.raw.signum() {
+                1 => Fixed::MAX,
+                -1 => Fixed::MIN,
+                _ => Fixed::ZERO,
+            };
+        }
+        Fixed::saturate(((self.raw as i128) << FRACTION_BITS) / other.raw as i128)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+
+    fn neg(self) -> Fixed {
+        Fixed {
+            raw: self.raw.saturating_neg(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test inputs are built from f64 so they keep their precision with the `f32` feature.
+    fn fixed(value: f64) -> Fixed {
+        Fixed::from_raw((value * (1_u64 << FRACTION_BITS) as f64).round() as i64)
+    }
+
+    fn assert_close(result: Fixed, expected: f64, tolerance: f64) {
+        let result = result.get_raw() as f64 / (1_u64 << FRACTION_BITS) as f64;
+        assert!(
+            (result - expected).abs() <= tolerance,
+            "result: {} did not match expected: {}",
+            result,
+            expected
+        );
+    }
+
+    #[test]
+    fn from_real_should_round_trip_representable_values() {
+        // Arrange
+        let value = -12.375;
+
+        // Act
+        let result = Fixed::from_real(value);
+
+        // Assert
+        assert_eq!(result.get_raw(), -12 * (1 << 32) - 3 * (1 << 29));
+        assert_eq!(result.to_real(), -12.375);
+    }
+
+    #[test]
+    fn arithmetic_should_saturate_instead_of_overflowing() {
+        // Arrange
+        let large = Fixed::from_int(i32::MAX);
+
+        // Act
+        let result_mul = large * large;
+        let result_add = Fixed::MAX + Fixed::ONE;
+        let result_div = Fixed::ONE / Fixed::ZERO;
+
+        // Assert
+        assert_eq!(result_mul, Fixed::MAX);
+        assert_eq!(result_add, Fixed::MAX);
+        assert_eq!(result_div, Fixed::MAX);
+        assert_eq!(
+            Fixed::from_int(6) / Fixed::from_int(4),
+            Fixed::from_real(1.5)
+        );
+    }
+
+    #[test]
+    fn sin_cos_should_match_the_floating_point_functions() {
+        for step in -400..400 {
+            // Arrange
+            let angle = step as f64 * 0.0625;
+
+            // Act
+            let (sin, cos) = fixed(angle).sin_cos();
+
+            // Assert
+            assert_close(sin, angle.sin(), 1e-8);
+            assert_close(cos, angle.cos(), 1e-8);
+        }
+    }
+
+    #[test]
+    fn sin_cos_should_keep_precision_for_large_angles() {
+        // Arrange
+        let angle = 3467634.1875;
+
+        // Act
+        let (sin, cos) = fixed(angle).sin_cos();
+
+        // Assert
+        assert_close(sin, f64::sin(angle), 1e-8);
+        assert_close(cos, f64::cos(angle), 1e-8);
+    }
+
+    #[test]
+    fn sqrt_should_match_the_floating_point_function() {
+        for value in [0.25, 2., 1600., 1e-6, 123456.789] {
+            // Act
+            let result = fixed(value).sqrt();
+
+            // Assert
+            assert_close(result, f64::sqrt(value), 1e-8);
+        }
+        assert_eq!(Fixed::from_int(-4).sqrt(), Fixed::ZERO);
+    }
+
+    #[test]
+    fn atan2_should_return_angles_in_every_quadrant() {
+        for (y, x) in [
+            (1., 2.),
+            (3., -1.),
+            (-0.5, -4.),
+            (-7., 0.1),
+            (0., -1.),
+            (2., 0.),
+        ] {
+            // Act
+            let result = fixed(y).atan2(fixed(x));
+
+            // Assert
+            assert_close(result, f64::atan2(y, x), 1e-8);
+        }
+        assert_eq!(Fixed::ZERO.atan2(Fixed::ZERO), Fixed::ZERO);
+    }
+
+    #[test]
+    fn acos_should_match_the_floating_point_function() {
+        for value in [-1., -0.3, 0., 0.5, 1.] {
+            // Act
+            let result = fixed(value).acos();
+
+            // Assert
+            assert_close(result, f64::acos(value), 1e-8);
+        }
+    }
+
+    #[test]
+    fn exp_ln_and_powf_should_match_the_floating_point_functions() {
+        for value in [0.01, 0.5, 1., 2., 8., 1000.] {
+            // Act
+            let result_ln = fixed(value).ln();
+            let result_powf = fixed(value).powf(fixed(0.32));
+
+            // Assert
+            assert_close(result_ln, f64::ln(value), 1e-8);
+            assert_close(result_powf, f64::powf(value, 0.32), 1e-7);
+        }
+        assert_close(Fixed::from_int(5).exp(), f64::exp(5.), 1e-6);
+        assert_close(Fixed::from_int(-3).exp(), f64::exp(-3.), 1e-8);
+    }
+}
@@ -1,25 +1,26 @@
+use super::real::Real;
 use super::xy::{XYNew, XY};
 
 #[derive(Clone, Copy, Default)]
 pub struct Force {
-    x: f64,
-    y: f64,
+    x: Real,
+    y: Real,
 }
 
 impl XY for Force {
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.x
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.y
     }
 
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.x = x;
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.y = y;
     }
 }
@@ -27,7 +28,7 @@ impl XY for Force {
 impl XYNew for Force {
     type XY = Force;
 
-    fn new(x: f64, y: f64) -> Self {
+    fn new(x: Real, y: Real) -> Self {
         Force { x: x, y: y }
     }
 }
@@ -0,0 +1,67 @@
+use super::real::Real;
+
+#[cfg(feature = "deterministic")]
+use super::fixed::Fixed;
+
+// Transcendental functions come from the platform math library and are not guaranteed to give
+// the same result on every compiler and CPU. With the `deterministic` feature sin, cos, atan2,
+// acos and powf are evaluated in fixed point instead, so lockstep simulations step to the same
+// state everywhere. This is not a fixed point backend for the whole crate: the body state and
+// the arithmetic stay in `Real`. Addition, multiplication, division and square roots are
+// correctly rounded under IEEE 754 and Rust does not fuse them, so they already give the same
+// bits on every platform.
+
+#[cfg(not(feature = "deterministic"))]
+pub fn sin_cos(angle: Real) -> (Real, Real) {
+    angle.sin_cos()
+}
+
+#[cfg(feature = "deterministic")]
+pub fn sin_cos(angle: Real) -> (Real, Real) {
+    let (sin, cos) = Fixed::from_real(angle).sin_cos();
+    (sin.to_real(), cos.to_real())
+}
+
+pub fn sin(angle: Real) -> Real {
+    sin_cos(angle).0
+}
+
+pub fn cos(angle: Real) -> Real {
+    sin_cos(angle).1
+}
+
+#[cfg(not(feature = "deterministic"))]
+pub fn atan2(y: Real, x: Real) -> Real {
+    y.atan2(x)
+}
+
+#[cfg(feature = "deterministic")]
+pub fn atan2(y: Real, x: Real) -> Real {
+    Fixed::from_real(y).atan2(Fixed::from_real(x)).to_real()
+}
+
+#[cfg(not(feature = "deterministic"))]
+pub fn acos(value: Real) -> Real {
+    value.acos()
+}
+
+#[cfg(feature = "deterministic")]
+pub fn acos(value: Real) -> Real {
+    Fixed::from_real(value).acos().to_real()
+}
+
+#[cfg(not(feature = "deterministic"))]
+pub fn powf(base: Real, exponent: Real) -> Real {
+    base.powf(exponent)
+}
+
+#[cfg(feature = "deterministic")]
+pub fn powf(base: Real, exponent: Real) -> Real {
+    Fixed::from_real(base)
+        .powf(Fixed::from_real(exponent))
+        .to_real()
+}
+
+pub fn sqrt(value: Real) -> Real {
+    value.sqrt()
+}
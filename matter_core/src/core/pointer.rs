@@ -0,0 +1,207 @@
+use super::position::Position;
+use super::real::Real;
+use super::xy::{XYNew, XY};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PointerButton {
+    Primary,
+    Middle,
+    Secondary,
+}
+
+// An input from a mouse, a touch or a test, in the coordinates of its source.
+#[derive(Clone, Copy)]
+pub enum PointerEvent {
+    Move(Position),
+    Down(Position, PointerButton),
+    Up(Position, PointerButton),
+    Wheel(Real),
+}
+
+// The state of a pointer fed by events. The absolute position is the one of the source, the
+// position is mapped into the world by the scale and then the offset, e.g. of a view.
+#[derive(Clone, Copy)]
+pub struct Pointer {
+    absolute: Position,
+    position: Position,
+    down_position: Position,
+    up_position: Position,
+    button: Option<PointerButton>,
+    wheel_delta: Real,
+    offset: Position,
+    scale: Position,
+}
+
+impl Default for Pointer {
+    fn default() -> Self {
+        Pointer::new()
+    }
+}
+
+impl Pointer {
+    pub fn new() -> Self {
+        Pointer {
+            absolute: Position::default(),
+            position: Position::default(),
+            down_position: Position::default(),
+            up_position: Position::default(),
+            button: None,
+            wheel_delta: 0.,
+            offset: Position::default(),
+            scale: Position::new(1., 1.),
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_absolute(&self) -> Position {
+        self.absolute
+    }
+
+    pub fn get_position(&self) -> Position {
+        self.position
+    }
+
+    pub fn get_down_position(&self) -> Position {
+        self.down_position
+    }
+
+    pub fn get_up_position(&self) -> Position {
+        self.up_position
+    }
+
+    // The button held down, None when the pointer is up.
+    pub fn get_button(&self) -> Option<PointerButton> {
+        self.button
+    }
+
+    pub fn get_wheel_delta(&self) -> Real {
+        self.wheel_delta
+    }
+
+    pub fn get_offset(&self) -> Position {
+        self.offset
+    }
+
+    pub fn get_scale(&self) -> Position {
+        self.scale
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+    pub fn set_offset(&mut self, offset: &impl XY) {
+        self.offset = Position::new(offset.get_x(), offset.get_y());
+        self.position = self.map_to_world(&self.absolute);
+    }
+
+    pub fn set_scale(&mut self, scale: &impl XY) {
+        self.scale = Position::new(scale.get_x(), scale.get_y());
+        self.position = self.map_to_world(&self.absolute);
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+    pub fn handle(&mut self, event: &PointerEvent) {
+        match event {
+            PointerEvent::Move(absolute) => {
+                self.move_to(absolute);
+            }
+            PointerEvent::Down(absolute, button) => {
+                self.move_to(absolute);
+                self.down_position = self.position;
+                self.button = Some(*button);
+            }
+            PointerEvent::Up(absolute, _) => {
+                self.move_to(absolute);
+                self.up_position = self.position;
+                self.button = None;
+            }
+            PointerEvent::Wheel(delta) => {
+                self.wheel_delta = *delta;
+            }
+        }
+    }
+
+    // Clears what only lasts for one update, as the wheel delta, as clearSourceEvents in
+    // matter.js.
+    pub fn clear_events(&mut self) {
+        self.wheel_delta = 0.;
+    }
+
+    fn move_to(&mut self, absolute: &Position) {
+        self.absolute = *absolute;
+        self.position = self.map_to_world(absolute);
+    }
+
+    fn map_to_world(&self, absolute: &Position) -> Position {
+        Position::new(
+            absolute.get_x() * self.scale.get_x() + self.offset.get_x(),
+            absolute.get_y() * self.scale.get_y() + self.offset.get_y(),
+        )
+    }
+    // endregion: Actions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{common_test_utils::assert_float, geometry_test_utils::assert_xy};
+
+    use super::*;
+
+    #[test]
+    fn handle_should_track_the_position_and_button() {
+        // Arrange
+        let mut pointer = Pointer::new();
+
+        // Act
+        pointer.handle(&PointerEvent::Down(
+            Position::new(10., 20.),
+            PointerButton::Primary,
+        ));
+        let result_down = pointer.get_button();
+        pointer.handle(&PointerEvent::Move(Position::new(30., 40.)));
+        pointer.handle(&PointerEvent::Up(
+            Position::new(50., 60.),
+            PointerButton::Primary,
+        ));
+        pointer.handle(&PointerEvent::Wheel(-3.));
+
+        // Assert
+        assert_eq!(result_down, Some(PointerButton::Primary));
+        assert_eq!(pointer.get_button(), None);
+        assert_xy(&pointer.get_down_position(), 10., 20.);
+        assert_xy(&pointer.get_up_position(), 50., 60.);
+        assert_xy(&pointer.get_position(), 50., 60.);
+        assert_float(pointer.get_wheel_delta(), -3.);
+    }
+
+    #[test]
+    fn clear_events_should_reset_the_wheel_delta() {
+        // Arrange
+        let mut pointer = Pointer::new();
+        pointer.handle(&PointerEvent::Wheel(-3.));
+
+        // Act
+        pointer.clear_events();
+
+        // Assert
+        assert_float(pointer.get_wheel_delta(), 0.);
+    }
+
+    #[test]
+    fn set_scale_and_offset_should_map_the_position_into_the_world() {
+        // Arrange
+        let mut pointer = Pointer::new();
+        pointer.handle(&PointerEvent::Move(Position::new(10., 20.)));
+
+        // Act
+        pointer.set_scale(&Position::new(2., 0.5));
+        pointer.set_offset(&Position::new(-5., 5.));
+
+        // Assert
+        assert_xy(&pointer.get_absolute(), 10., 20.);
+        assert_xy(&pointer.get_position(), 15., 15.);
+    }
+}
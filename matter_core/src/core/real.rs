@@ -0,0 +1,16 @@
+// The scalar type used by the geometry and the bodies. Defaults to f64, the `f32` feature
+// switches the whole simulation over to single precision.
+
+#[cfg(not(feature = "f32"))]
+mod scalar {
+    pub type Real = f64;
+    pub use std::f64::consts;
+}
+
+#[cfg(feature = "f32")]
+mod scalar {
+    pub type Real = f32;
+    pub use std::f32::consts;
+}
+
+pub use scalar::*;
@@ -1,20 +1,43 @@
+use crate::body::body::Body;
+
+use super::color::Color;
+use super::real::Real;
 use super::sprite::Sprite;
 
+// The colors matter.js picks the fill of dynamic bodies from.
+pub const FILL_PALETTE: [Color; 5] = [
+    Color::rgb(0xf1, 0x96, 0x48),
+    Color::rgb(0xf5, 0xd2, 0x59),
+    Color::rgb(0xf5, 0x5a, 0x3c),
+    Color::rgb(0x06, 0x3e, 0x7b),
+    Color::rgb(0xec, 0xec, 0xd1),
+];
+pub const FILL_STATIC: Color = Color::rgb(0x14, 0x15, 0x1f);
+pub const STROKE: Color = Color::rgb(0xcc, 0xcc, 0xcc);
+pub const STROKE_STATIC: Color = Color::rgb(0x55, 0x55, 0x55);
+
+// How a body is drawn. The styles left unset are taken from the parent body for a part, and
+// then from the matter.js defaults. Bodies are drawn from the lowest layer up.
 #[derive(Clone, Copy, Default)]
 pub struct Render {
     visible: bool,
-    opacity: f64,
-    //stroke_style: Option<?>,
-    //fill_style: Option<?>,
-    //line_width: Option<?>,
+    opacity: Real,
+    stroke_style: Option<Color>,
+    fill_style: Option<Color>,
+    line_width: Option<Real>,
+    layer: i32,
     sprite: Sprite,
 }
 
 impl Render {
-    pub fn new(visible: bool, opacity: f64, sprite: Sprite) -> Self {
+    pub fn new(visible: bool, opacity: Real, sprite: Sprite) -> Self {
         Render {
             visible: visible,
             opacity: opacity,
+            stroke_style: None,
+            fill_style: None,
+            line_width: None,
+            layer: 0,
             sprite: sprite,
         }
     }
@@ -23,11 +46,196 @@ impl Render {
         self.visible
     }
 
-    pub fn get_opacity(&self) -> f64 {
+    pub fn get_opacity(&self) -> Real {
         self.opacity
     }
 
+    pub fn get_stroke_style(&self) -> Option<Color> {
+        self.stroke_style
+    }
+
+    pub fn get_fill_style(&self) -> Option<Color> {
+        self.fill_style
+    }
+
+    pub fn get_line_width(&self) -> Option<Real> {
+        self.line_width
+    }
+
+    pub fn get_layer(&self) -> i32 {
+        self.layer
+    }
+
     pub fn get_sprite(&self) -> Sprite {
         self.sprite
     }
+
+    // The fill, stroke and line width to draw with. Without a fill style a dynamic body takes the
+    // first color of the palette, and only a static body without one gets an outline.
+    pub fn get_styles(&self, is_static: bool) -> (Color, Color, Real) {
+        if is_static {
+            (
+                self.fill_style.unwrap_or(FILL_STATIC),
+                self.stroke_style.unwrap_or(STROKE_STATIC),
+                self.line_width
+                    .unwrap_or(if self.fill_style.is_none() { 1. } else { 0. }),
+            )
+        } else {
+            (
+                self.fill_style.unwrap_or(FILL_PALETTE[0]),
+                self.stroke_style.unwrap_or(STROKE),
+                self.line_width.unwrap_or(0.),
+            )
+        }
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn set_opacity(&mut self, opacity: Real) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_stroke_style(&mut self, color: Option<Color>) {
+        self.stroke_style = color;
+    }
+
+    pub fn set_fill_style(&mut self, color: Option<Color>) {
+        self.fill_style = color;
+    }
+
+    pub fn set_line_width(&mut self, line_width: Option<Real>) {
+        self.line_width = line_width;
+    }
+
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    pub fn set_sprite(&mut self, sprite: Sprite) {
+        self.sprite = sprite;
+    }
+
+    // The render of a part, with the styles it leaves unset taken from its parent.
+    pub fn inherit(&self, parent: &Render) -> Render {
+        Render {
+            stroke_style: self.stroke_style.or(parent.stroke_style),
+            fill_style: self.fill_style.or(parent.fill_style),
+            line_width: self.line_width.or(parent.line_width),
+            ..*self
+        }
+    }
+}
+
+// Gives bodies the default styles of matter.js, with the fill of dynamic bodies chosen by the
+// seeded generator of matter.js, so the same seed gives the same colors on every run.
+pub struct Palette {
+    seed: u64,
+}
+
+impl Palette {
+    // The seed is reduced to the range of the generator, which keeps its sequence and the
+    // arithmetic from overflowing.
+    pub fn new(seed: Option<u64>) -> Self {
+        Palette {
+            seed: seed.unwrap_or(0) % 233280,
+        }
+    }
+
+    pub fn next_fill(&mut self) -> Color {
+        let index = (self.random() * FILL_PALETTE.len() as Real) as usize;
+        FILL_PALETTE[index.min(FILL_PALETTE.len() - 1)]
+    }
+
+    // Sets the styles the body leaves unset.
+    pub fn apply(&mut self, body: &mut Body) {
+        let mut render = body.get_render();
+        let is_static = body.get_is_static();
+        if render.get_fill_style().is_none() && !is_static {
+            render.set_fill_style(Some(self.next_fill()));
+        }
+
+        let (fill, stroke, line_width) = render.get_styles(is_static);
+        render.set_fill_style(Some(fill));
+        render.set_stroke_style(Some(stroke));
+        render.set_line_width(Some(line_width));
+        body.set_render(&render);
+    }
+
+    fn random(&mut self) -> Real {
+        self.seed = (self.seed * 9301 + 49297) % 233280;
+        self.seed as Real / 233280.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{body_test_utils::test_box, common_test_utils::assert_float};
+
+    use super::*;
+
+    #[test]
+    fn inherit_should_take_the_unset_styles_from_the_parent() {
+        // Arrange
+        let mut parent = Render::new(true, 1., Sprite::default());
+        parent.set_fill_style(Some(Color::RED));
+        parent.set_line_width(Some(2.));
+        let mut part = Render::new(true, 0.5, Sprite::default());
+        part.set_fill_style(Some(Color::BLUE));
+
+        // Act
+        let result = part.inherit(&parent);
+
+        // Assert
+        assert_eq!(result.get_fill_style(), Some(Color::BLUE));
+        assert_eq!(result.get_line_width(), Some(2.));
+        assert_eq!(result.get_stroke_style(), None);
+        assert_float(result.get_opacity(), 0.5);
+    }
+
+    #[test]
+    fn apply_should_pick_the_same_colors_for_the_same_seed() {
+        // Arrange
+        let mut bodies: Vec<Body> = (0..4).map(|_| test_box(0., 0., 10., 10.)).collect();
+        bodies[3].set_static(true);
+        let mut palette = Palette::new(Some(1));
+
+        // Act
+        for body in bodies.iter_mut() {
+            palette.apply(body);
+        }
+        let result: Vec<Option<Color>> = bodies
+            .iter()
+            .map(|body| body.get_render().get_fill_style())
+            .collect();
+        let mut palette_again = Palette::new(Some(1));
+        let result_again: Vec<Color> = (0..3).map(|_| palette_again.next_fill()).collect();
+
+        // Assert
+        assert_eq!(result[0], Some(FILL_PALETTE[1]));
+        assert_eq!(result[1], Some(FILL_PALETTE[2]));
+        assert_eq!(result[2], Some(FILL_PALETTE[1]));
+        assert_eq!(result[3], Some(FILL_STATIC));
+        assert_eq!(
+            result_again,
+            vec![FILL_PALETTE[1], FILL_PALETTE[2], FILL_PALETTE[1]]
+        );
+        assert_eq!(bodies[0].get_render().get_line_width(), Some(0.));
+        assert_eq!(bodies[3].get_render().get_line_width(), Some(1.));
+    }
+
+    #[test]
+    fn next_fill_should_accept_any_seed() {
+        // Arrange
+        let mut palette = Palette::new(Some(u64::MAX));
+        let mut palette_reduced = Palette::new(Some(u64::MAX % 233280));
+
+        // Act
+        let result: Vec<Color> = (0..3).map(|_| palette.next_fill()).collect();
+        let result_reduced: Vec<Color> = (0..3).map(|_| palette_reduced.next_fill()).collect();
+
+        // Assert
+        assert_eq!(result, result_reduced);
+    }
 }
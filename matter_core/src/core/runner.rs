@@ -0,0 +1,383 @@
+use std::collections::VecDeque;
+
+use crate::body::body::Body;
+
+use super::common::BASE_DELTA;
+use super::position::Position;
+use super::real::Real;
+use super::xy::{XYNew, XY};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunnerMode {
+    // Steps by the same delta, as many times as the elapsed time allows up to max_substeps.
+    Fixed,
+    // Steps once per tick by the smoothed frame time, clamped between delta_min and delta_max.
+    Variable,
+}
+
+// Turns frame times into simulation steps. The caller passes the current time in milliseconds
+// and a closure stepping the simulation by a delta, e.g. updating the engine.
+pub struct Runner {
+    mode: RunnerMode,
+    delta: Real,
+    delta_min: Real,
+    delta_max: Real,
+    delta_sample_size: usize,
+    delta_history: VecDeque<Real>,
+    max_substeps: u32,
+    accumulator: Real,
+    time_prev: Option<Real>,
+    frame_delta: Real,
+    alpha: Real,
+    is_enabled: bool,
+}
+
+impl Runner {
+    // Deltas that are not positive fall back to the default one.
+    pub fn new(mode: RunnerMode, delta: Option<Real>) -> Self {
+        let delta = delta.filter(|delta| *delta > 0.).unwrap_or(BASE_DELTA);
+        Runner {
+            mode: mode,
+            delta: delta,
+            delta_min: delta * 0.25,
+            delta_max: delta * 2.,
+            delta_sample_size: 60,
+            delta_history: VecDeque::new(),
+            max_substeps: 4,
+            accumulator: 0.,
+            time_prev: None,
+            frame_delta: delta,
+            alpha: 1.,
+            is_enabled: true,
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_mode(&self) -> RunnerMode {
+        self.mode
+    }
+
+    pub fn get_delta(&self) -> Real {
+        self.delta
+    }
+
+    pub fn get_delta_min(&self) -> Real {
+        self.delta_min
+    }
+
+    pub fn get_delta_max(&self) -> Real {
+        self.delta_max
+    }
+
+    pub fn get_delta_sample_size(&self) -> usize {
+        self.delta_sample_size
+    }
+
+    pub fn get_max_substeps(&self) -> u32 {
+        self.max_substeps
+    }
+
+    // The time between the last two ticks, smoothed and clamped in the variable mode.
+    pub fn get_frame_delta(&self) -> Real {
+        self.frame_delta
+    }
+
+    // How far the time is between the last step and the next one, from 0 to 1. Always 1 in the
+    // variable mode, where the bodies are drawn where they are.
+    pub fn get_alpha(&self) -> Real {
+        self.alpha
+    }
+
+    pub fn get_is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    // The position to draw the body at, between the previous and the current step.
+    pub fn get_interpolated_position(&self, body: &Body) -> Position {
+        let position = body.get_position();
+        let position_prev = body.get_position_prev().unwrap_or(position);
+        Position::new(
+            position_prev.get_x() + (position.get_x() - position_prev.get_x()) * self.alpha,
+            position_prev.get_y() + (position.get_y() - position_prev.get_y()) * self.alpha,
+        )
+    }
+
+    pub fn get_interpolated_angle(&self, body: &Body) -> Real {
+        let angle_prev = body.get_angle_prev();
+        angle_prev + (body.get_angle() - angle_prev) * self.alpha
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+    pub fn set_mode(&mut self, mode: RunnerMode) {
+        self.mode = mode;
+        self.reset();
+    }
+
+    // Deltas that are not positive are ignored. The bounds of the variable mode are scaled along,
+    // so they stay the same fractions of the delta.
+    pub fn set_delta(&mut self, value: Real) {
+        if value <= 0. || value.is_nan() {
+            return;
+        }
+        let scale = value / self.delta;
+        self.delta_min *= scale;
+        self.delta_max *= scale;
+        self.delta = value;
+    }
+
+    // Bounds that are not positive or would cross the other bound are ignored.
+    pub fn set_delta_min(&mut self, value: Real) {
+        if value <= 0. || value.is_nan() || value > self.delta_max {
+            return;
+        }
+        self.delta_min = value;
+    }
+
+    pub fn set_delta_max(&mut self, value: Real) {
+        if value <= 0. || value.is_nan() || value < self.delta_min {
+            return;
+        }
+        self.delta_max = value;
+    }
+
+    pub fn set_delta_sample_size(&mut self, value: usize) {
+        self.delta_sample_size = value.max(1);
+    }
+
+    pub fn set_max_substeps(&mut self, value: u32) {
+        self.max_substeps = value.max(1);
+    }
+
+    // A disabled runner ignores the ticks, the time it was disabled is not caught up on.
+    pub fn set_enabled(&mut self, is_enabled: bool) {
+        self.is_enabled = is_enabled;
+        self.time_prev = None;
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+    pub fn reset(&mut self) {
+        self.delta_history.clear();
+        self.accumulator = 0.;
+        self.time_prev = None;
+        self.frame_delta = self.delta;
+        self.alpha = 1.;
+    }
+
+    // Runs the steps due at the given time and returns how many ran. The first tick after
+    // creating, resetting or enabling the runner takes one step.
+    pub fn tick(&mut self, time: Real, mut step: impl FnMut(Real)) -> u32 {
+        if !self.is_enabled {
+            return 0;
+        }
+
+        // the fixed mode takes the elapsed time as is, max_substeps keeps it from spiralling
+        self.frame_delta = match (self.time_prev, self.mode) {
+            (Some(time_prev), RunnerMode::Fixed) => (time - time_prev).max(0.),
+            (Some(time_prev), RunnerMode::Variable) => self.smooth(time - time_prev),
+            (None, _) => self.delta,
+        };
+        self.time_prev = Some(time);
+
+        match self.mode {
+            RunnerMode::Fixed => {
+                self.accumulator += self.frame_delta;
+
+                let mut substeps = 0;
+                while self.accumulator >= self.delta && substeps < self.max_substeps {
+                    step(self.delta);
+                    self.accumulator -= self.delta;
+                    substeps += 1;
+                }
+
+                // drop the time that could not be caught up on instead of spiralling
+                if self.accumulator >= self.delta {
+                    self.accumulator %= self.delta;
+                }
+
+                self.alpha = self.accumulator / self.delta;
+                substeps
+            }
+            RunnerMode::Variable => {
+                step(self.frame_delta);
+                self.alpha = 1.;
+                1
+            }
+        }
+    }
+
+    // The mean of the last frame deltas, each clamped to the allowed range.
+    fn smooth(&mut self, frame_delta: Real) -> Real {
+        self.delta_history
+            .push_back(frame_delta.clamp(self.delta_min, self.delta_max));
+        while self.delta_history.len() > self.delta_sample_size {
+            self.delta_history.pop_front();
+        }
+
+        self.delta_history.iter().sum::<Real>() / self.delta_history.len() as Real
+    }
+    // endregion: Actions
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{body_test_utils::test_box, common_test_utils::assert_float};
+
+    use super::*;
+
+    fn run(runner: &mut Runner, times: &[Real]) -> Vec<Vec<Real>> {
+        times
+            .iter()
+            .map(|time| {
+                let mut deltas: Vec<Real> = Vec::new();
+                runner.tick(*time, |delta| deltas.push(delta));
+                deltas
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tick_should_take_one_fixed_step_per_frame_at_the_step_rate() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Fixed, Some(10.));
+
+        // Act
+        let result = run(&mut runner, &[0., 10., 20.]);
+
+        // Assert
+        assert_eq!(result, vec![vec![10.]; 3]);
+        assert_float(runner.get_alpha(), 0.);
+    }
+
+    #[test]
+    fn tick_should_accumulate_frames_shorter_than_the_step() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Fixed, Some(10.));
+
+        // Act
+        let result = run(&mut runner, &[0., 5., 10., 15.]);
+
+        // Assert
+        assert_eq!(result, vec![vec![10.], vec![], vec![10.], vec![]]);
+        assert_float(runner.get_alpha(), 0.5);
+    }
+
+    #[test]
+    fn tick_should_limit_the_substeps_and_drop_the_time_left() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Fixed, Some(10.));
+
+        // Act
+        let result = run(&mut runner, &[0., 1005.]);
+
+        // Assert
+        assert_eq!(result[1].len(), 4);
+        assert_float(runner.get_alpha(), 0.5);
+    }
+
+    #[test]
+    fn tick_should_keep_up_with_frames_longer_than_delta_max() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Fixed, Some(10.));
+
+        // Act
+        let result = run(&mut runner, &[0., 35., 70.]);
+
+        // Assert
+        assert_eq!(result[1].len(), 3);
+        assert_eq!(result[2].len(), 4);
+        assert_float(runner.get_alpha(), 0.);
+    }
+
+    #[test]
+    fn set_delta_should_ignore_deltas_that_are_not_positive_and_scale_the_bounds() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Fixed, Some(10.));
+
+        // Act
+        runner.set_delta(0.);
+        runner.set_delta(-5.);
+        let result = runner.get_delta();
+        runner.set_delta(20.);
+
+        // Assert
+        assert_float(result, 10.);
+        assert_float(runner.get_delta(), 20.);
+        assert_float(runner.get_delta_min(), 5.);
+        assert_float(runner.get_delta_max(), 40.);
+    }
+
+    #[test]
+    fn set_delta_min_and_max_should_ignore_invalid_or_crossing_bounds() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Variable, Some(10.));
+
+        // Act
+        runner.set_delta_min(100.);
+        runner.set_delta_min(Real::NAN);
+        runner.set_delta_max(1.);
+        runner.set_delta_max(-5.);
+        let result = run(&mut runner, &[0., 100.]);
+        runner.set_delta_min(4.);
+        runner.set_delta_max(30.);
+
+        // Assert
+        assert_eq!(result[1], vec![20.]);
+        assert_float(runner.get_delta_min(), 4.);
+        assert_float(runner.get_delta_max(), 30.);
+    }
+
+    #[test]
+    fn tick_should_take_one_clamped_and_smoothed_variable_step_per_frame() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Variable, Some(10.));
+        runner.set_delta_sample_size(2);
+
+        // Act
+        let result = run(&mut runner, &[0., 8., 20., 120.]);
+
+        // Assert
+        assert_eq!(result[0], vec![10.]);
+        assert_eq!(result[1], vec![8.]);
+        assert_eq!(result[2], vec![10.]);
+        assert_eq!(result[3], vec![16.]);
+        assert_float(runner.get_alpha(), 1.);
+    }
+
+    #[test]
+    fn tick_should_not_step_a_disabled_runner() {
+        // Arrange
+        let mut runner = Runner::new(RunnerMode::Fixed, None);
+        runner.set_enabled(false);
+
+        // Act
+        let result = run(&mut runner, &[0., 100.]);
+
+        // Assert
+        assert_eq!(result, vec![Vec::<Real>::new(); 2]);
+    }
+
+    #[test]
+    fn get_interpolated_position_should_blend_the_previous_and_current_pose() {
+        // Arrange
+        let mut body = test_box(0., 0., 10., 10.);
+        body.set_position(Position::new(10., -20.), Some(true));
+        body.set_angle(1., Some(true));
+        let mut runner = Runner::new(RunnerMode::Fixed, Some(10.));
+        run(&mut runner, &[0., 2.5]);
+
+        // Act
+        let result = runner.get_interpolated_position(&body);
+        let result_angle = runner.get_interpolated_angle(&body);
+
+        // Assert
+        assert_float(runner.get_alpha(), 0.25);
+        assert_float(result.get_x(), 2.5);
+        assert_float(result.get_y(), -5.);
+        assert_float(result_angle, 0.25);
+    }
+}
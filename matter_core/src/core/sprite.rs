@@ -1,13 +1,15 @@
+use super::real::Real;
+
 #[derive(Clone, Copy, Default)]
 pub struct Sprite {
-    x_scale: f64,
-    y_scale: f64,
-    x_offset: f64,
-    y_offset: f64,
+    x_scale: Real,
+    y_scale: Real,
+    x_offset: Real,
+    y_offset: Real,
 }
 
 impl Sprite {
-    pub fn new(x_scale: f64, y_scale: f64, x_offset: f64, y_offset: f64) -> Self {
+    pub fn new(x_scale: Real, y_scale: Real, x_offset: Real, y_offset: Real) -> Self {
         Sprite {
             x_scale: x_scale,
             y_scale: y_scale,
@@ -16,19 +18,19 @@ impl Sprite {
         }
     }
 
-    pub fn get_x_scale(&self) -> f64 {
+    pub fn get_x_scale(&self) -> Real {
         self.x_scale
     }
 
-    pub fn get_y_scale(&self) -> f64 {
+    pub fn get_y_scale(&self) -> Real {
         self.y_scale
     }
 
-    pub fn get_x_offset(&self) -> f64 {
+    pub fn get_x_offset(&self) -> Real {
         self.x_offset
     }
 
-    pub fn get_y_offset(&self) -> f64 {
+    pub fn get_y_offset(&self) -> Real {
         self.y_offset
     }
 }
@@ -1,32 +1,33 @@
+use super::real::Real;
 use super::xy::{XYNew, XY};
 
 #[derive(Clone, Copy, Default)]
 pub struct Velocity {
-    x: f64,
-    y: f64,
+    x: Real,
+    y: Real,
 }
 
 impl XY for Velocity {
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.x
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.y
     }
 
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.x = x;
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.y = y;
     }
 }
 
 impl XYNew for Velocity {
     type XY = Velocity;
-    fn new(x: f64, y: f64) -> Self {
+    fn new(x: Real, y: Real) -> Self {
         Velocity { x: x, y: y }
     }
 }
@@ -1,13 +1,19 @@
+use crate::core::math;
+use crate::core::real::Real;
+use crate::geometry::predicates;
+use crate::geometry::transform::Transform;
+use crate::geometry::vector::Vector;
+
 pub trait XY {
-    fn get_x(&self) -> f64;
-    fn get_y(&self) -> f64;
+    fn get_x(&self) -> Real;
+    fn get_y(&self) -> Real;
 
-    fn set_x(&mut self, x: f64);
-    fn set_y(&mut self, y: f64);
+    fn set_x(&mut self, x: Real);
+    fn set_y(&mut self, y: Real);
     fn set_xy(&mut self, xy: &impl XY) {
         self.set_x_y(xy.get_x(), xy.get_y())
     }
-    fn set_x_y(&mut self, x: f64, y: f64) {
+    fn set_x_y(&mut self, x: Real, y: Real) {
         self.set_x(x);
         self.set_y(y);
     }
@@ -16,15 +22,15 @@ pub trait XY {
         self.add_x(xy.get_x());
         self.add_y(xy.get_y());
     }
-    fn add_x_y(&mut self, x: f64, y: f64) {
+    fn add_x_y(&mut self, x: Real, y: Real) {
         self.add_x(x);
         self.add_y(y);
     }
-    fn add_x(&mut self, x: f64) {
+    fn add_x(&mut self, x: Real) {
         self.set_x(self.get_x() + x);
     }
 
-    fn add_y(&mut self, y: f64) {
+    fn add_y(&mut self, y: Real) {
         self.set_y(self.get_y() + y);
     }
 
@@ -33,12 +39,12 @@ pub trait XY {
         self.set_y(self.get_y() - value.get_y());
     }
 
-    fn mult(&mut self, scalar: f64) {
+    fn mult(&mut self, scalar: Real) {
         self.set_x(self.get_x() * scalar);
         self.set_y(self.get_y() * scalar);
     }
 
-    fn div(&mut self, scalar: f64) {
+    fn div(&mut self, scalar: Real) {
         self.set_x(self.get_x() / scalar);
         self.set_y(self.get_y() / scalar);
     }
@@ -47,34 +53,29 @@ pub trait XY {
         self.mult(-1.);
     }
 
-    fn dot(&self, multiplier: &impl XY) -> f64 {
+    fn dot(&self, multiplier: &impl XY) -> Real {
         self.get_x() * multiplier.get_x() + self.get_y() * multiplier.get_y()
     }
 
-    fn magnitude_squared(&self) -> f64 {
+    fn magnitude_squared(&self) -> Real {
         self.get_x().powi(2) + self.get_y().powi(2)
     }
 
-    fn magnitude(&self) -> f64 {
-        self.magnitude_squared().sqrt()
+    fn magnitude(&self) -> Real {
+        math::sqrt(self.magnitude_squared())
     }
 
-    fn rotate_about(&mut self, angle: f64, point: &impl XY) {
-        let cos = angle.cos();
-        let sin = angle.sin();
-        let x = point.get_x()
-            + ((self.get_x() - point.get_x()) * cos - (self.get_y() - point.get_y()) * sin);
-        let y = point.get_y()
-            + ((self.get_x() - point.get_x()) * sin + (self.get_y() - point.get_y()) * cos);
-        self.set_x_y(x, y);
+    fn rotate_about(&mut self, angle: Real, point: &impl XY) {
+        let rotated = Transform::from_rotation(angle)
+            .rotate_about(&Vector::create(self.get_x(), self.get_y()), point);
+        self.set_x_y(rotated.get_x(), rotated.get_y());
     }
 
-    fn cross3(vector_a: &impl XY, vector_b: &impl XY, vector_c: &impl XY) -> f64 {
-        (vector_b.get_x() - vector_a.get_x()) * (vector_c.get_y() - vector_a.get_y())
-            - (vector_b.get_y() - vector_a.get_y()) * (vector_c.get_x() - vector_a.get_x())
+    fn cross3(vector_a: &impl XY, vector_b: &impl XY, vector_c: &impl XY) -> Real {
+        predicates::orient2d(vector_a, vector_b, vector_c)
     }
 
-    fn cross(vector_a: &impl XY, vector_b: &impl XY) -> f64 {
+    fn cross(vector_a: &impl XY, vector_b: &impl XY) -> Real {
         (vector_a.get_x() * vector_b.get_y()) - (vector_a.get_y() * vector_b.get_x())
     }
 
@@ -86,8 +87,8 @@ pub trait XY {
         self.set_x_y(x, y);
     }
 
-    fn angle(vector_a: &impl XY, vector_b: &impl XY) -> f64 {
-        f64::atan2(
+    fn angle(vector_a: &impl XY, vector_b: &impl XY) -> Real {
+        math::atan2(
             vector_b.get_y() - vector_a.get_y(),
             vector_b.get_x() - vector_a.get_x(),
         )
@@ -107,7 +108,7 @@ pub trait XY {
 pub trait XYNew {
     type XY;
 
-    fn new(x: f64, y: f64) -> Self::XY;
+    fn new(x: Real, y: Real) -> Self::XY;
     fn new_from(xy: &impl XY) -> Self::XY {
         <Self as XYNew>::new(xy.get_x(), xy.get_y())
     }
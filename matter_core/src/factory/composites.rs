@@ -0,0 +1,539 @@
+use crate::body::body::Body;
+use crate::composite::composite::Composite;
+use crate::constraint::constraint::Constraint;
+use crate::constraint::constraint_option::ConstraintOption;
+use crate::core::collision_filter::CollisionFilter;
+use crate::core::real::Real;
+use crate::core::xy::XY;
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+// Factories for common composites. The bodies come from a callback creating a body centred on
+// the given position, the constraint options are applied after the defaults of each factory.
+
+fn get_bounds(body: &Body) -> Bounds {
+    Bounds::from_points(&body.get_vertices().get_value())
+}
+
+fn create_constraint(defaults: Vec<ConstraintOption>, options: &[ConstraintOption]) -> Constraint {
+    let mut constraint_options = defaults;
+    constraint_options.extend_from_slice(options);
+    Constraint::create(&constraint_options)
+}
+
+// Places the bodies in rows and columns with their top left corner starting at x and y. The
+// callback gets the position, column, row, last body and index and can skip a cell with None.
+pub fn stack(
+    x: Real,
+    y: Real,
+    columns: usize,
+    rows: usize,
+    column_gap: Real,
+    row_gap: Real,
+    mut callback: impl FnMut(Real, Real, usize, usize, Option<&Body>, usize) -> Option<Body>,
+) -> Composite {
+    let mut stack = Composite::new();
+    let mut current_y = y;
+    let mut last_body: Option<Body> = None;
+    let mut index = 0;
+
+    for row in 0..rows {
+        let mut current_x = x;
+        let mut max_height: Real = 0.;
+
+        for column in 0..columns {
+            match callback(current_x, current_y, column, row, last_body.as_ref(), index) {
+                Some(mut body) => {
+                    let bounds = get_bounds(&body);
+                    max_height = max_height.max(bounds.get_height());
+                    body.translate(
+                        &Vector::create(bounds.get_width() * 0.5, bounds.get_height() * 0.5),
+                        None,
+                    );
+
+                    current_x = get_bounds(&body).max.x + column_gap;
+                    stack.add_body(&body);
+                    last_body = Some(body);
+                    index += 1;
+                }
+                None => current_x += column_gap,
+            }
+        }
+
+        current_y += max_height + row_gap;
+    }
+    stack
+}
+
+// A stack with the columns narrowing towards the top, the row passed to the callback counts from
+// the bottom.
+pub fn pyramid(
+    x: Real,
+    y: Real,
+    columns: usize,
+    rows: usize,
+    column_gap: Real,
+    row_gap: Real,
+    mut callback: impl FnMut(Real, Real, usize, usize, Option<&Body>, usize) -> Option<Body>,
+) -> Composite {
+    let actual_rows = rows.min(columns.div_ceil(2));
+
+    stack(
+        x,
+        y,
+        columns,
+        rows,
+        column_gap,
+        row_gap,
+        |_, stack_y, column, row, last_body, index| {
+            if row > actual_rows {
+                return None;
+            }
+
+            let row = actual_rows - row;
+            if column < row || column + row > columns - 1 {
+                return None;
+            }
+
+            let last_body_width = last_body.map_or(0., |body| get_bounds(body).get_width());
+
+            // the first body is placed before its width is known
+            if index == 1 {
+                if let Some(mut body) = last_body.cloned() {
+                    let shift = if columns % 2 == 1 { 1. } else { -1. };
+                    body.translate(
+                        &Vector::create((column as Real + shift) * last_body_width, 0.),
+                        None,
+                    );
+                }
+            }
+
+            let x_offset = column as Real * last_body_width;
+            callback(
+                x + x_offset + column as Real * column_gap,
+                stack_y,
+                column,
+                row,
+                last_body,
+                index,
+            )
+        },
+    )
+}
+
+// Links each body of the composite to the next. The offsets are fractions of the body sizes
+// from their positions.
+pub fn chain(
+    composite: &mut Composite,
+    x_offset_a: Real,
+    y_offset_a: Real,
+    x_offset_b: Real,
+    y_offset_b: Real,
+    options: &[ConstraintOption],
+) {
+    let bodies = composite.get_bodies().clone();
+
+    for pair in bodies.windows(2) {
+        let bounds_a = get_bounds(&pair[0]);
+        let bounds_b = get_bounds(&pair[1]);
+        let constraint = create_constraint(
+            vec![
+                ConstraintOption::BodyA(pair[0].clone()),
+                ConstraintOption::PointA(Vector::create(
+                    bounds_a.get_width() * x_offset_a,
+                    bounds_a.get_height() * y_offset_a,
+                )),
+                ConstraintOption::BodyB(pair[1].clone()),
+                ConstraintOption::PointB(Vector::create(
+                    bounds_b.get_width() * x_offset_b,
+                    bounds_b.get_height() * y_offset_b,
+                )),
+            ],
+            options,
+        );
+        composite.add_constraint(&constraint);
+    }
+}
+
+// Links the bodies of a stack to their neighbours in the grid, and with cross_brace also to the
+// diagonal neighbours in the row above. A stack with fewer bodies than cells, e.g. one that
+// skipped cells, can't be mapped onto the grid and is left unlinked.
+pub fn mesh(
+    composite: &mut Composite,
+    columns: usize,
+    rows: usize,
+    cross_brace: bool,
+    options: &[ConstraintOption],
+) {
+    let bodies = composite.get_bodies().clone();
+    if bodies.len() < columns * rows {
+        return;
+    }
+    let body = |column: usize, row: usize| bodies[column + row * columns].clone();
+    let mut link = |composite: &mut Composite, body_a: Body, body_b: Body| {
+        let constraint = create_constraint(
+            vec![
+                ConstraintOption::BodyA(body_a),
+                ConstraintOption::BodyB(body_b),
+            ],
+            options,
+        );
+        composite.add_constraint(&constraint);
+    };
+
+    for row in 0..rows {
+        for column in 1..columns {
+            link(composite, body(column - 1, row), body(column, row));
+        }
+
+        if row > 0 {
+            for column in 0..columns {
+                link(composite, body(column, row - 1), body(column, row));
+
+                if cross_brace && column > 0 {
+                    link(composite, body(column - 1, row - 1), body(column, row));
+                }
+
+                if cross_brace && column < columns - 1 {
+                    link(composite, body(column + 1, row - 1), body(column, row));
+                }
+            }
+        }
+    }
+}
+
+// A grid of particles held together by soft constraints. The callback gets the position and the
+// radius of each particle, the particles do not rotate.
+#[allow(clippy::too_many_arguments)]
+pub fn soft_body(
+    x: Real,
+    y: Real,
+    columns: usize,
+    rows: usize,
+    column_gap: Real,
+    row_gap: Real,
+    cross_brace: bool,
+    particle_radius: Real,
+    mut callback: impl FnMut(Real, Real, Real) -> Body,
+    options: &[ConstraintOption],
+) -> Composite {
+    let mut soft_body = stack(
+        x,
+        y,
+        columns,
+        rows,
+        column_gap,
+        row_gap,
+        |x, y, _, _, _, _| {
+            let mut particle = callback(x, y, particle_radius);
+            particle.set_inertia(Real::INFINITY);
+            Some(particle)
+        },
+    );
+
+    let mut constraint_options = vec![ConstraintOption::Stiffness(0.2)];
+    constraint_options.extend_from_slice(options);
+    mesh(
+        &mut soft_body,
+        columns,
+        rows,
+        cross_brace,
+        &constraint_options,
+    );
+    soft_body
+}
+
+// A row of balls hanging from fixed points. The callback gets the position and the size of each
+// ball, which are made elastic and frictionless.
+pub fn newtons_cradle(
+    x: Real,
+    y: Real,
+    number: usize,
+    size: Real,
+    length: Real,
+    mut callback: impl FnMut(Real, Real, Real) -> Body,
+    options: &[ConstraintOption],
+) -> Composite {
+    let mut cradle = Composite::new();
+    let separation = 1.9;
+
+    for index in 0..number {
+        let ball_x = x + index as Real * size * separation;
+        let mut ball = callback(ball_x, y + length, size);
+        ball.set_inertia(Real::INFINITY);
+        ball.set_resitution(1.);
+        ball.set_friction(0.);
+        ball.set_friction_air(0.0001);
+        ball.set_slop(1.);
+
+        let constraint = create_constraint(
+            vec![
+                ConstraintOption::PointA(Vector::create(ball_x, y)),
+                ConstraintOption::BodyB(ball.clone()),
+            ],
+            options,
+        );
+
+        cradle.add_body(&ball);
+        cradle.add_constraint(&constraint);
+    }
+    cradle
+}
+
+// A body on two wheels fixed by axles, in a group that does not collide with itself. The
+// callbacks create the body from its position and size and the wheels from their position and
+// radius.
+#[allow(clippy::too_many_arguments)]
+pub fn car(
+    x: Real,
+    y: Real,
+    width: Real,
+    height: Real,
+    wheel_size: Real,
+    mut body_callback: impl FnMut(Real, Real, Real, Real) -> Body,
+    mut wheel_callback: impl FnMut(Real, Real, Real) -> Body,
+    options: &[ConstraintOption],
+) -> Composite {
+    let group = CollisionFilter::next_group(true);
+    let wheel_base = 20.;
+    let wheel_offsets = [-width * 0.5 + wheel_base, width * 0.5 - wheel_base];
+    let wheel_y_offset = 0.;
+
+    let mut car = Composite::new();
+    let mut body = body_callback(x, y, width, height);
+    let mut filter = body.get_collision_filter();
+    filter.set_group(group);
+    body.set_collision_filter(&filter);
+    body.set_density(0.0002);
+    car.add_body(&body);
+
+    for wheel_offset in wheel_offsets {
+        let mut wheel = wheel_callback(x + wheel_offset, y + wheel_y_offset, wheel_size);
+        let mut filter = wheel.get_collision_filter();
+        filter.set_group(group);
+        wheel.set_collision_filter(&filter);
+        wheel.set_friction(0.8);
+
+        let axle = create_constraint(
+            vec![
+                ConstraintOption::BodyB(body.clone()),
+                ConstraintOption::PointB(Vector::create(wheel_offset, wheel_y_offset)),
+                ConstraintOption::BodyA(wheel.clone()),
+                ConstraintOption::Stiffness(1.),
+                ConstraintOption::Length(0.),
+            ],
+            options,
+        );
+
+        car.add_body(&wheel);
+        car.add_constraint(&axle);
+    }
+    car
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::{body_test_utils::test_box, common_test_utils::assert_float};
+
+    use super::*;
+
+    fn test_stack(columns: usize, rows: usize) -> Composite {
+        stack(0., 0., columns, rows, 5., 5., |x, y, _, _, _, _| {
+            Some(test_box(x, y, 10., 10.))
+        })
+    }
+
+    fn get_xs(bodies: &[Body]) -> Vec<Real> {
+        bodies
+            .iter()
+            .map(|body| body.get_position().get_x())
+            .collect()
+    }
+
+    #[test]
+    fn stack_should_place_bodies_in_rows_and_columns_from_the_top_left() {
+        // Arrange
+        let columns = 3;
+        let rows = 2;
+
+        // Act
+        let result = test_stack(columns, rows);
+
+        // Assert
+        let bodies = result.get_bodies();
+        assert_eq!(bodies.len(), 6);
+        assert_float(bodies[0].get_position().get_x(), 5.);
+        assert_float(bodies[0].get_position().get_y(), 5.);
+        assert_float(bodies[2].get_position().get_x(), 35.);
+        assert_float(bodies[3].get_position().get_x(), 5.);
+        assert_float(bodies[3].get_position().get_y(), 20.);
+    }
+
+    #[test]
+    fn stack_should_skip_the_cells_without_a_body() {
+        // Arrange
+        let callback = |x, y, column, _, _: Option<&Body>, _| {
+            if column == 1 {
+                None
+            } else {
+                Some(test_box(x, y, 10., 10.))
+            }
+        };
+
+        // Act
+        let result = stack(0., 0., 3, 1, 5., 5., callback);
+
+        // Assert
+        assert_eq!(get_xs(result.get_bodies()), vec![5., 25.]);
+    }
+
+    #[test]
+    fn pyramid_should_narrow_the_rows_towards_the_top() {
+        // Arrange
+        let callback = |x, y, _, _, _: Option<&Body>, _| Some(test_box(x, y, 10., 10.));
+
+        // Act
+        let result = pyramid(0., 0., 5, 10, 0., 0., callback);
+
+        // Assert
+        let bodies = result.get_bodies();
+        assert_eq!(bodies.len(), 9);
+        let xs = get_xs(bodies);
+        assert_float(xs[0], 25.);
+        assert_eq!(xs[1..4], [15., 25., 35.]);
+        assert_eq!(xs[4..9], [5., 15., 25., 35., 45.]);
+        assert_float(bodies[0].get_position().get_y(), 5.);
+        assert_float(bodies[8].get_position().get_y(), 25.);
+    }
+
+    #[test]
+    fn chain_should_link_consecutive_bodies_at_the_offsets() {
+        // Arrange
+        let mut composite = test_stack(3, 1);
+
+        // Act
+        chain(
+            &mut composite,
+            0.5,
+            0.,
+            -0.5,
+            0.,
+            &[ConstraintOption::Stiffness(0.8)],
+        );
+
+        // Assert
+        let constraints = composite.get_constraints();
+        let bodies = composite.get_bodies();
+        assert_eq!(constraints.len(), 2);
+        assert_eq!(
+            constraints[1].get_body_a().unwrap().get_id(),
+            bodies[1].get_id()
+        );
+        assert_eq!(
+            constraints[1].get_body_b().unwrap().get_id(),
+            bodies[2].get_id()
+        );
+        assert_float(constraints[0].get_point_a().get_x(), 5.);
+        assert_float(constraints[0].get_point_b().get_x(), -5.);
+        assert_float(constraints[0].get_length(), 5.);
+        assert_float(constraints[0].get_stiffness(), 0.8);
+    }
+
+    #[test]
+    fn mesh_should_link_the_grid_and_the_cross_braces() {
+        // Arrange
+        let mut composite = test_stack(3, 2);
+        let mut composite_braced = test_stack(3, 2);
+
+        // Act
+        mesh(&mut composite, 3, 2, false, &[]);
+        mesh(&mut composite_braced, 3, 2, true, &[]);
+
+        // Assert
+        assert_eq!(composite.get_constraints().len(), 7);
+        assert_eq!(composite_braced.get_constraints().len(), 11);
+    }
+
+    #[test]
+    fn mesh_should_leave_a_stack_with_missing_cells_unlinked() {
+        // Arrange
+        let mut composite = test_stack(3, 2);
+        let body = composite.get_bodies()[5].clone();
+        composite.remove_body(&body, None);
+
+        // Act
+        mesh(&mut composite, 3, 2, true, &[]);
+
+        // Assert
+        assert!(composite.get_constraints().is_empty());
+    }
+
+    #[test]
+    fn soft_body_should_create_soft_constraints_between_particles_that_do_not_rotate() {
+        // Arrange
+        let callback = |x, y, radius: Real| test_box(x, y, radius * 2., radius * 2.);
+
+        // Act
+        let result = soft_body(0., 0., 3, 3, 0., 0., false, 5., callback, &[]);
+
+        // Assert
+        assert_eq!(result.get_bodies().len(), 9);
+        assert_eq!(result.get_constraints().len(), 12);
+        assert_float(result.get_constraints()[0].get_stiffness(), 0.2);
+        assert_eq!(result.get_bodies()[0].get_inverse_inertia(), 0.);
+    }
+
+    #[test]
+    fn newtons_cradle_should_hang_elastic_balls_from_fixed_points() {
+        // Arrange
+        let callback = |x, y, size: Real| test_box(x, y, size * 2., size * 2.);
+
+        // Act
+        let result = newtons_cradle(100., 50., 3, 10., 200., callback, &[]);
+
+        // Assert
+        let bodies = result.get_bodies();
+        let constraints = result.get_constraints();
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(constraints.len(), 3);
+        assert_float(bodies[2].get_position().get_x(), 138.);
+        assert_float(bodies[2].get_position().get_y(), 250.);
+        assert_float(bodies[2].get_resitution(), 1.);
+        assert_float(constraints[2].get_world_point_a().get_x(), 138.);
+        assert_float(constraints[2].get_length(), 200.);
+        assert!(constraints[2].get_body_a().is_none());
+    }
+
+    #[test]
+    fn car_should_fix_the_wheels_to_the_body_in_a_non_colliding_group() {
+        // Arrange
+        let body_callback = |x, y, width, height| test_box(x, y, width, height);
+        let wheel_callback = |x, y, radius: Real| test_box(x, y, radius * 2., radius * 2.);
+
+        // Act
+        let result = car(
+            150.,
+            100.,
+            150.,
+            30.,
+            25.,
+            body_callback,
+            wheel_callback,
+            &[],
+        );
+
+        // Assert
+        let bodies = result.get_bodies();
+        let constraints = result.get_constraints();
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(constraints.len(), 2);
+        let group = bodies[0].get_collision_filter().get_group();
+        assert!(group < 0);
+        assert!(bodies
+            .iter()
+            .all(|body| body.get_collision_filter().get_group() == group));
+        assert_float(bodies[1].get_position().get_x(), 95.);
+        assert_float(constraints[0].get_world_point_b().get_x(), 95.);
+        assert_float(constraints[0].get_length(), 0.);
+    }
+}
@@ -1,5 +1,9 @@
 pub mod axes;
+pub mod bitmap;
 pub mod bounds;
+pub mod contour;
+pub mod predicates;
+pub mod transform;
 pub mod vector;
 pub mod vertex;
 pub mod vertices;
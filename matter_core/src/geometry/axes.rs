@@ -1,10 +1,12 @@
 use crate::core::common::OrderedHashMap;
+use crate::core::real::Real;
 use crate::core::xy::XY;
 use std::ops::Index;
 use std::ops::IndexMut;
 use std::slice::Iter;
 use std::slice::IterMut;
 
+use super::transform::Transform;
 use super::vector::Vector;
 use super::vertices::Vertices;
 use super::{vector, vertex::Vertex};
@@ -70,7 +72,7 @@ impl Axes {
             );
 
             let gradient = if normal.get_y() == 0.0 {
-                f64::INFINITY
+                Real::INFINITY
             } else {
                 let result = normal.get_x() / normal.get_y();
                 if result == -0.0 {
@@ -88,20 +90,21 @@ impl Axes {
         Axes { value: axes.values }
     }
 
-    pub fn rotate(&mut self, angle: f64) {
-        let cos = angle.cos();
-        let sin = angle.sin();
+    pub fn rotate(&mut self, angle: Real) {
+        self.transform(&Transform::from_rotation(angle));
+    }
 
+    // Axes are directions, so only the rotation of the transform applies.
+    pub fn transform(&mut self, transform: &Transform) {
         for axis in self.value.iter_mut() {
-            let xx = axis.get_x() * cos - axis.get_y() * sin;
-            axis.set_y(axis.get_x() * sin + axis.get_y() * cos);
-            axis.set_x(xx);
+            axis.set_xy(&transform.apply_to_vector(axis));
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::core::real::consts;
 
     use uuid::Uuid;
 
@@ -116,7 +119,7 @@ mod tests {
         let points = test_square();
         let vertices = Vertices::create(points, None);
         let mut axes = Axes::from_vertices(&vertices);
-        let angle = 90.0_f64;
+        let angle = 90.0;
 
         // Act
         axes.rotate(angle);
@@ -139,4 +142,20 @@ mod tests {
         assert_xy(&result[0], 0.0, 1.0);
         assert_xy(&result[1], -1.0, 0.0);
     }
+
+    #[test]
+    fn transform_should_only_rotate_the_axes() {
+        //Arrange
+        let points = test_square();
+        let vertices = Vertices::create(points, None);
+        let mut axes = Axes::from_vertices(&vertices);
+        let transform = Transform::new(consts::PI, &Vector::create(10., 20.));
+
+        // Act
+        axes.transform(&transform);
+
+        // Assert
+        assert_xy(&axes[0], -0.00000000000000012246467991473532, -1.0);
+        assert_xy(&axes[1], 1.0, -0.00000000000000012246467991473532);
+    }
 }
@@ -0,0 +1,386 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BitmapError {
+    InvalidHeader(String),
+    InvalidData(String),
+}
+
+// A single channel bitmap where higher values mean more solid (coverage or alpha).
+#[derive(Clone)]
+pub struct Bitmap {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Bitmap {
+    pub fn new(width: usize, height: usize, pixels: Vec<u8>) -> Result<Bitmap, BitmapError> {
+        if pixels.len() != width * height {
+            let error = format!(
+                "Expected {} pixels for a {}x{} bitmap but got {}",
+                width * height,
+                width,
+                height,
+                pixels.len()
+            );
+            return Err(BitmapError::InvalidData(error));
+        }
+        Ok(Bitmap {
+            width: width,
+            height: height,
+            pixels: pixels,
+        })
+    }
+
+    pub fn from_raw(width: usize, height: usize, bytes: &[u8]) -> Result<Bitmap, BitmapError> {
+        Bitmap::new(width, height, bytes.to_vec())
+    }
+
+    pub fn from_rgba_alpha(
+        width: usize,
+        height: usize,
+        bytes: &[u8],
+    ) -> Result<Bitmap, BitmapError> {
+        if bytes.len() != width * height * 4 {
+            let error = format!(
+                "Expected {} bytes for a {}x{} RGBA bitmap but got {}",
+                width * height * 4,
+                width,
+                height,
+                bytes.len()
+            );
+            return Err(BitmapError::InvalidData(error));
+        }
+        let alpha = bytes.chunks_exact(4).map(|pixel| pixel[3]).collect();
+        Bitmap::new(width, height, alpha)
+    }
+
+    // Reads PBM (P1, P4) and PGM (P2, P5) images. Black PBM pixels are treated as solid,
+    // PGM values are rescaled to the 0 to 255 range.
+    pub fn from_netpbm(bytes: &[u8]) -> Result<Bitmap, BitmapError> {
+        let mut reader = NetpbmReader {
+            bytes: bytes,
+            position: 0,
+        };
+
+        let magic = reader.token()?;
+        let width = reader.number()?;
+        let height = reader.number()?;
+        let area = NetpbmReader::size(width, height)?;
+        // the header can't be trusted, so no more is reserved than the file could hold
+        let capacity = area.min(bytes.len());
+
+        match magic.as_str() {
+            "P1" => {
+                let mut pixels = Vec::with_capacity(capacity);
+                while pixels.len() < area {
+                    match reader.bit()? {
+                        1 => pixels.push(u8::MAX),
+                        _ => pixels.push(0),
+                    }
+                }
+                Bitmap::new(width, height, pixels)
+            }
+            "P2" => {
+                let max_value = reader.max_value()?;
+                let mut pixels = Vec::with_capacity(capacity);
+                while pixels.len() < area {
+                    let value = reader.number()?;
+                    pixels.push(Bitmap::rescale(value, max_value)?);
+                }
+                Bitmap::new(width, height, pixels)
+            }
+            "P4" => {
+                reader.single_whitespace()?;
+                let row_bytes = width.div_ceil(8);
+                let data = reader.remaining(NetpbmReader::size(row_bytes, height)?)?;
+                let mut pixels = Vec::with_capacity(area);
+                for row in data.chunks_exact(row_bytes) {
+                    for column in 0..width {
+                        let bit = (row[column / 8] >> (7 - column % 8)) & 1;
+                        pixels.push(if bit == 1 { u8::MAX } else { 0 });
+                    }
+                }
+                Bitmap::new(width, height, pixels)
+            }
+            "P5" => {
+                let max_value = reader.max_value()?;
+                reader.single_whitespace()?;
+                let sample_bytes = if max_value < 256 { 1 } else { 2 };
+                let data = reader.remaining(NetpbmReader::size(area, sample_bytes)?)?;
+                let pixels: Result<Vec<u8>, BitmapError> = data
+                    .chunks_exact(sample_bytes)
+                    .map(|sample| {
+                        let value = sample
+                            .iter()
+                            .fold(0_usize, |value, byte| (value << 8) | *byte as usize);
+                        Bitmap::rescale(value, max_value)
+                    })
+                    .collect();
+                Bitmap::new(width, height, pixels?)
+            }
+            _ => {
+                let error = format!("Unsupported netpbm format {}", magic);
+                Err(BitmapError::InvalidHeader(error))
+            }
+        }
+    }
+
+    fn rescale(value: usize, max_value: usize) -> Result<u8, BitmapError> {
+        if value > max_value {
+            let error = format!("Sample {} exceeds the maximum value {}", value, max_value);
+            return Err(BitmapError::InvalidData(error));
+        }
+        Ok(((value * u8::MAX as usize + max_value / 2) / max_value) as u8)
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_pixels(&self) -> &Vec<u8> {
+        &self.pixels
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> u8 {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            0
+        } else {
+            self.pixels[y as usize * self.width + x as usize]
+        }
+    }
+}
+
+struct NetpbmReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> NetpbmReader<'a> {
+    fn skip_whitespace_and_comments(&mut self) {
+        while self.position < self.bytes.len() {
+            let byte = self.bytes[self.position];
+            if byte == b'#' {
+                while self.position < self.bytes.len() && self.bytes[self.position] != b'\n' {
+                    self.position += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.position += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    // The product of the sizes from the header, which may be too large for any image.
+    fn size(a: usize, b: usize) -> Result<usize, BitmapError> {
+        a.checked_mul(b)
+            .ok_or_else(|| BitmapError::InvalidHeader(format!("The size {}x{} is too large", a, b)))
+    }
+
+    fn token(&mut self) -> Result<String, BitmapError> {
+        self.skip_whitespace_and_comments();
+        let start = self.position;
+        while self.position < self.bytes.len() && !self.bytes[self.position].is_ascii_whitespace() {
+            self.position += 1;
+        }
+        if start == self.position {
+            return Err(BitmapError::InvalidHeader(
+                "Unexpected end of netpbm data".to_string(),
+            ));
+        }
+        Ok(String::from_utf8_lossy(&self.bytes[start..self.position]).to_string())
+    }
+
+    fn number(&mut self) -> Result<usize, BitmapError> {
+        let token = self.token()?;
+        token.parse::<usize>().map_err(|error| {
+            let error = format!("Could not parse {} as a number.\nError:{}", token, error);
+            BitmapError::InvalidData(error)
+        })
+    }
+
+    fn max_value(&mut self) -> Result<usize, BitmapError> {
+        let max_value = self.number()?;
+        if max_value == 0 || max_value > u16::MAX as usize {
+            let error = format!("Invalid maximum value {}", max_value);
+            return Err(BitmapError::InvalidHeader(error));
+        }
+        Ok(max_value)
+    }
+
+    fn bit(&mut self) -> Result<u8, BitmapError> {
+        self.skip_whitespace_and_comments();
+        match self.bytes.get(self.position) {
+            Some(b'0') => {
+                self.position += 1;
+                Ok(0)
+            }
+            Some(b'1') => {
+                self.position += 1;
+                Ok(1)
+            }
+            _ => Err(BitmapError::InvalidData(
+                "Expected a 0 or 1 in PBM data".to_string(),
+            )),
+        }
+    }
+
+    fn single_whitespace(&mut self) -> Result<(), BitmapError> {
+        match self.bytes.get(self.position) {
+            Some(byte) if byte.is_ascii_whitespace() => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(BitmapError::InvalidHeader(
+                "Expected whitespace after the netpbm header".to_string(),
+            )),
+        }
+    }
+
+    fn remaining(&mut self, length: usize) -> Result<&'a [u8], BitmapError> {
+        if self.bytes.len() < self.position + length {
+            let error = format!(
+                "Expected {} bytes of pixel data but got {}",
+                length,
+                self.bytes.len() - self.position
+            );
+            return Err(BitmapError::InvalidData(error));
+        }
+        let data = &self.bytes[self.position..self.position + length];
+        self.position += length;
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_netpbm_should_read_an_ascii_pbm_with_comments() {
+        // Arrange
+        let data = b"P1\n# a comment\n3 2\n1 0 1\n0 1 0\n";
+
+        // Act
+        let result = Bitmap::from_netpbm(data).unwrap();
+
+        // Assert
+        assert_eq!(result.get_width(), 3);
+        assert_eq!(result.get_height(), 2);
+        assert_eq!(result.get_pixels(), &vec![255, 0, 255, 0, 255, 0]);
+    }
+
+    #[test]
+    fn from_netpbm_should_read_a_binary_pbm() {
+        // Arrange
+        let data = [
+            b"P4\n10 2\n".to_vec(),
+            vec![0b1000_0001, 0b0100_0000, 0xff, 0xc0],
+        ]
+        .concat();
+
+        // Act
+        let result = Bitmap::from_netpbm(&data).unwrap();
+
+        // Assert
+        assert_eq!(result.get(0, 0), 255);
+        assert_eq!(result.get(1, 0), 0);
+        assert_eq!(result.get(7, 0), 255);
+        assert_eq!(result.get(9, 0), 255);
+        assert_eq!(result.get(8, 0), 0);
+        assert!((0..10).all(|x| result.get(x, 1) == 255));
+    }
+
+    #[test]
+    fn from_netpbm_should_read_and_rescale_an_ascii_pgm() {
+        // Arrange
+        let data = b"P2 2 2 15 0 15 7 8";
+
+        // Act
+        let result = Bitmap::from_netpbm(data).unwrap();
+
+        // Assert
+        assert_eq!(result.get_pixels(), &vec![0, 255, 119, 136]);
+    }
+
+    #[test]
+    fn from_netpbm_should_read_a_binary_pgm_with_two_byte_samples() {
+        // Arrange
+        let data = [b"P5 2 1 65535\n".to_vec(), vec![0xff, 0xff, 0x00, 0x00]].concat();
+
+        // Act
+        let result = Bitmap::from_netpbm(&data).unwrap();
+
+        // Assert
+        assert_eq!(result.get_pixels(), &vec![255, 0]);
+    }
+
+    #[test]
+    fn from_netpbm_should_return_an_error_for_an_unknown_format() {
+        // Arrange
+        let data = b"P6 1 1 255\n\x00\x00\x00";
+
+        // Act
+        let result = Bitmap::from_netpbm(data);
+
+        // Assert
+        assert!(matches!(result, Err(BitmapError::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn from_netpbm_should_return_an_error_for_an_oversized_header() {
+        // Arrange
+        let data = format!("P5 {} {} 255\n\x00", usize::MAX, 2);
+        let data_large = b"P2 100000 100000 255\n0 0";
+
+        // Act
+        let result = Bitmap::from_netpbm(data.as_bytes());
+        let result_large = Bitmap::from_netpbm(data_large);
+
+        // Assert
+        assert!(matches!(result, Err(BitmapError::InvalidHeader(_))));
+        assert!(result_large.is_err());
+    }
+
+    #[test]
+    fn from_netpbm_should_return_an_error_for_truncated_data() {
+        // Arrange
+        let data = b"P5 2 2 255\n\x00\x00";
+
+        // Act
+        let result = Bitmap::from_netpbm(data);
+
+        // Assert
+        assert!(matches!(result, Err(BitmapError::InvalidData(_))));
+    }
+
+    #[test]
+    fn from_rgba_alpha_should_use_the_alpha_channel() {
+        // Arrange
+        let data = [10, 20, 30, 40, 50, 60, 70, 80];
+
+        // Act
+        let result = Bitmap::from_rgba_alpha(2, 1, &data).unwrap();
+
+        // Assert
+        assert_eq!(result.get_pixels(), &vec![40, 80]);
+    }
+
+    #[test]
+    fn get_should_return_zero_outside_the_bitmap() {
+        // Arrange
+        let bitmap = Bitmap::from_raw(1, 1, &[200]).unwrap();
+
+        // Act
+        let inside = bitmap.get(0, 0);
+        let outside = bitmap.get(-1, 0);
+
+        // Assert
+        assert_eq!(inside, 200);
+        assert_eq!(outside, 0);
+    }
+}
@@ -1,37 +1,40 @@
 use crate::core::{
+    real::Real,
     velocity::{self, Velocity},
     xy::{XYNew, XY},
 };
 
+use super::transform::Transform;
+use super::vector::Vector;
 use super::vertices::Vertices;
 
 #[derive(Clone, Copy)]
 pub struct BoundsPart {
-    pub x: f64,
-    pub y: f64,
+    pub x: Real,
+    pub y: Real,
 }
 
 impl XY for BoundsPart {
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.x
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.y
     }
 
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.x = x;
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.y = y;
     }
 }
 
 impl XYNew for BoundsPart {
     type XY = BoundsPart;
-    fn new(x: f64, y: f64) -> Self {
+    fn new(x: Real, y: Real) -> Self {
         BoundsPart { x: x, y: y }
     }
 }
@@ -56,10 +59,10 @@ impl Bounds {
     }
 
     pub fn update(&mut self, vertices: &Vertices, velocity: Option<&Velocity>) {
-        self.min.set_x(f64::INFINITY);
-        self.max.set_x(-f64::INFINITY);
-        self.min.set_y(f64::INFINITY);
-        self.max.set_y(-f64::INFINITY);
+        self.min.set_x(Real::INFINITY);
+        self.max.set_x(-Real::INFINITY);
+        self.min.set_y(Real::INFINITY);
+        self.max.set_y(-Real::INFINITY);
 
         for vertex in vertices.iter() {
             if vertex.get_x() > self.max.get_x() {
@@ -92,10 +95,7 @@ impl Bounds {
     }
 
     pub fn create(vertices: Option<Vertices>) -> Bounds {
-        let mut bounds = Bounds {
-            min: BoundsPart::new(0., 0.),
-            max: BoundsPart::new(0., 0.),
-        };
+        let mut bounds = Bounds::empty();
 
         if let Some(vertices) = vertices {
             bounds.update(&vertices, None);
@@ -103,6 +103,173 @@ impl Bounds {
         bounds
     }
 
+    // The inverted infinite box, it contains and overlaps nothing and is the identity for union.
+    pub fn empty() -> Bounds {
+        Bounds {
+            min: BoundsPart::new(Real::INFINITY, Real::INFINITY),
+            max: BoundsPart::new(-Real::INFINITY, -Real::INFINITY),
+        }
+    }
+
+    pub fn from_points<T: XY>(points: &[T]) -> Bounds {
+        let mut bounds = Bounds::empty();
+        for point in points.iter() {
+            bounds.min.set_x(bounds.min.get_x().min(point.get_x()));
+            bounds.min.set_y(bounds.min.get_y().min(point.get_y()));
+            bounds.max.set_x(bounds.max.get_x().max(point.get_x()));
+            bounds.max.set_y(bounds.max.get_y().max(point.get_y()));
+        }
+        bounds
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.get_x() > self.max.get_x() || self.min.get_y() > self.max.get_y()
+    }
+
+    pub fn get_width(&self) -> Real {
+        if self.is_empty() {
+            0.
+        } else {
+            self.max.get_x() - self.min.get_x()
+        }
+    }
+
+    pub fn get_height(&self) -> Real {
+        if self.is_empty() {
+            0.
+        } else {
+            self.max.get_y() - self.min.get_y()
+        }
+    }
+
+    pub fn area(&self) -> Real {
+        self.get_width() * self.get_height()
+    }
+
+    pub fn perimeter(&self) -> Real {
+        2. * (self.get_width() + self.get_height())
+    }
+
+    // Not a number for empty bounds.
+    pub fn centre(&self) -> Vector {
+        Vector::create(
+            (self.min.get_x() + self.max.get_x()) * 0.5,
+            (self.min.get_y() + self.max.get_y()) * 0.5,
+        )
+    }
+
+    pub fn union(bounds_a: &Bounds, bounds_b: &Bounds) -> Bounds {
+        Bounds::new(
+            BoundsPart::new(
+                bounds_a.min.get_x().min(bounds_b.min.get_x()),
+                bounds_a.min.get_y().min(bounds_b.min.get_y()),
+            ),
+            BoundsPart::new(
+                bounds_a.max.get_x().max(bounds_b.max.get_x()),
+                bounds_a.max.get_y().max(bounds_b.max.get_y()),
+            ),
+        )
+    }
+
+    pub fn intersection(bounds_a: &Bounds, bounds_b: &Bounds) -> Bounds {
+        let bounds = Bounds::new(
+            BoundsPart::new(
+                bounds_a.min.get_x().max(bounds_b.min.get_x()),
+                bounds_a.min.get_y().max(bounds_b.min.get_y()),
+            ),
+            BoundsPart::new(
+                bounds_a.max.get_x().min(bounds_b.max.get_x()),
+                bounds_a.max.get_y().min(bounds_b.max.get_y()),
+            ),
+        );
+
+        if bounds.is_empty() {
+            Bounds::empty()
+        } else {
+            bounds
+        }
+    }
+
+    // Grows the bounds by the margin on every side, empty bounds stay empty.
+    pub fn expand(&mut self, margin: Real) {
+        if self.is_empty() {
+            return;
+        }
+
+        self.min.add_x_y(-margin, -margin);
+        self.max.add_x_y(margin, margin);
+        if self.is_empty() {
+            *self = Bounds::empty();
+        }
+    }
+
+    pub fn contains_bounds(&self, other: &Bounds) -> bool {
+        other.is_empty()
+            || (self.min.get_x() <= other.min.get_x()
+                && self.min.get_y() <= other.min.get_y()
+                && self.max.get_x() >= other.max.get_x()
+                && self.max.get_y() >= other.max.get_y())
+    }
+
+    // The fraction of the direction at which the ray enters the bounds, zero when the origin
+    // is inside.
+    pub fn ray_intersection(&self, origin: &impl XY, direction: &impl XY) -> Option<Real> {
+        self.slab_intersection(origin, direction, Real::INFINITY)
+    }
+
+    // The fraction along the segment from start to end at which it enters the bounds.
+    pub fn segment_intersection(&self, start: &impl XY, end: &impl XY) -> Option<Real> {
+        let direction = Vector::create(end.get_x() - start.get_x(), end.get_y() - start.get_y());
+        self.slab_intersection(start, &direction, 1.)
+    }
+
+    fn slab_intersection(
+        &self,
+        origin: &impl XY,
+        direction: &impl XY,
+        max_fraction: Real,
+    ) -> Option<Real> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut fraction_enter: Real = 0.;
+        let mut fraction_exit = max_fraction;
+        let slabs = [
+            (
+                origin.get_x(),
+                direction.get_x(),
+                self.min.get_x(),
+                self.max.get_x(),
+            ),
+            (
+                origin.get_y(),
+                direction.get_y(),
+                self.min.get_y(),
+                self.max.get_y(),
+            ),
+        ];
+
+        for (origin, direction, min, max) in slabs {
+            if direction == 0. {
+                if origin < min || origin > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let fraction_min = (min - origin) / direction;
+            let fraction_max = (max - origin) / direction;
+            fraction_enter = fraction_enter.max(fraction_min.min(fraction_max));
+            fraction_exit = fraction_exit.min(fraction_min.max(fraction_max));
+            if fraction_enter > fraction_exit {
+                return None;
+            }
+        }
+
+        Some(fraction_enter)
+    }
+
     pub fn contains(&self, point: &impl XY) -> bool {
         point.get_x() >= self.min.get_x()
             && point.get_x() <= self.max.get_x()
@@ -124,6 +291,26 @@ impl Bounds {
         self.max.add_y(vector.get_y());
     }
 
+    // Replaces the bounds with the bounds of its transformed corners.
+    pub fn transform(&mut self, transform: &Transform) {
+        let corners = [
+            BoundsPart::new(self.min.get_x(), self.min.get_y()),
+            BoundsPart::new(self.max.get_x(), self.min.get_y()),
+            BoundsPart::new(self.max.get_x(), self.max.get_y()),
+            BoundsPart::new(self.min.get_x(), self.max.get_y()),
+        ];
+
+        self.min.set_x_y(Real::INFINITY, Real::INFINITY);
+        self.max.set_x_y(-Real::INFINITY, -Real::INFINITY);
+        for corner in corners.iter() {
+            let corner = transform.apply_to_point(corner);
+            self.min.set_x(self.min.get_x().min(corner.get_x()));
+            self.min.set_y(self.min.get_y().min(corner.get_y()));
+            self.max.set_x(self.max.get_x().max(corner.get_x()));
+            self.max.set_y(self.max.get_y().max(corner.get_y()));
+        }
+    }
+
     pub fn shift(&mut self, position: &impl XY) {
         let delta_x = self.max.get_x() - self.min.get_x();
         let delta_y = self.max.get_y() - self.min.get_y();
@@ -137,13 +324,15 @@ impl Bounds {
 
 #[cfg(test)]
 mod tests {
+    use crate::core::real::consts;
 
     use uuid::Uuid;
 
     use crate::geometry::vector::Vector;
     use crate::geometry::{vector, vertices};
+    use crate::test_utils::common_test_utils::assert_float;
     use crate::test_utils::geometry_test_utils::{
-        assert_bounds, test_bounds, test_square_with_decimals,
+        assert_bounds, assert_xy, test_bounds, test_square_with_decimals,
     };
 
     use super::*;
@@ -161,6 +350,19 @@ mod tests {
         assert_bounds(&bounds, 4.0, 1.0, 104.0, 101.0);
     }
 
+    #[test]
+    fn transform_should_mutate_bounds_to_contain_the_transformed_corners() {
+        // Arrange
+        let mut bounds = test_bounds();
+        let transform = Transform::new(consts::PI / 2., &Vector::create(4.0, 1.0));
+
+        // Act
+        bounds.transform(&transform);
+
+        // Assert
+        assert_bounds(&bounds, -246.0, 101.0, -146.0, 201.0);
+    }
+
     #[test]
     fn translate_should_mutate_bounds_by_vector() {
         // Arrange
@@ -233,14 +435,145 @@ mod tests {
     }
 
     #[test]
-    fn create_should_create_a_valid_bounds_without_vertices() {
+    fn create_should_create_empty_bounds_without_vertices() {
         // Arrange
 
         // Act
         let result = Bounds::create(None);
 
         // Assert
-        assert_bounds(&result, 0.0, 0.0, 0.0, 0.0);
+        assert_eq!(result.is_empty(), true);
+        assert_eq!(result.contains(&Vector::create(0.0, 0.0)), false);
+        assert_float(result.area(), 0.0);
+    }
+
+    #[test]
+    fn union_should_ignore_empty_bounds() {
+        // Arrange
+        let bounds = test_bounds();
+
+        // Act
+        let result = Bounds::union(&Bounds::empty(), &bounds);
+        let result_empty = Bounds::union(&Bounds::empty(), &Bounds::empty());
+
+        // Assert
+        assert_bounds(&result, 100.0, 150.0, 200.0, 250.0);
+        assert_eq!(result_empty.is_empty(), true);
+    }
+
+    #[test]
+    fn union_should_contain_both_bounds() {
+        // Arrange
+        let bounds_a = test_bounds();
+        let bounds_b =
+            Bounds::from_points(&[Vector::create(-5.0, 300.0), Vector::create(0.0, 10.0)]);
+
+        // Act
+        let result = Bounds::union(&bounds_a, &bounds_b);
+
+        // Assert
+        assert_bounds(&result, -5.0, 10.0, 200.0, 300.0);
+        assert_eq!(result.contains_bounds(&bounds_a), true);
+        assert_eq!(result.contains_bounds(&bounds_b), true);
+        assert_eq!(bounds_a.contains_bounds(&result), false);
+    }
+
+    #[test]
+    fn intersection_should_return_the_overlapping_area() {
+        // Arrange
+        let bounds_a = test_bounds();
+        let mut bounds_b = test_bounds();
+        bounds_b.translate(&Vector::create(50.0, -25.0));
+
+        // Act
+        let result = Bounds::intersection(&bounds_a, &bounds_b);
+
+        // Assert
+        assert_bounds(&result, 150.0, 150.0, 200.0, 225.0);
+    }
+
+    #[test]
+    fn intersection_should_return_empty_bounds_for_disjoint_bounds() {
+        // Arrange
+        let bounds_a = test_bounds();
+        let bounds_b = Bounds::from_points(&[Vector::create(0.0, 0.0), Vector::create(1.0, 1.0)]);
+
+        // Act
+        let result = Bounds::intersection(&bounds_a, &bounds_b);
+
+        // Assert
+        assert_eq!(result.is_empty(), true);
+    }
+
+    #[test]
+    fn expand_should_grow_bounds_on_every_side() {
+        // Arrange
+        let mut bounds = test_bounds();
+        let mut empty = Bounds::empty();
+
+        // Act
+        bounds.expand(5.0);
+        empty.expand(5.0);
+
+        // Assert
+        assert_bounds(&bounds, 95.0, 145.0, 205.0, 255.0);
+        assert_eq!(empty.is_empty(), true);
+    }
+
+    #[test]
+    fn area_perimeter_and_centre_should_return_valid_results() {
+        // Arrange
+        let bounds = Bounds::from_points(&[Vector::create(1.0, 2.0), Vector::create(5.0, 4.0)]);
+
+        // Act
+        let result_area = bounds.area();
+        let result_perimeter = bounds.perimeter();
+        let result_centre = bounds.centre();
+
+        // Assert
+        assert_float(result_area, 8.0);
+        assert_float(result_perimeter, 12.0);
+        assert_xy(&result_centre, 3.0, 3.0);
+    }
+
+    #[test]
+    fn segment_intersection_should_return_the_entry_fraction() {
+        // Arrange
+        let bounds = test_bounds();
+
+        // Act
+        let result =
+            bounds.segment_intersection(&Vector::create(0.0, 200.0), &Vector::create(200.0, 200.0));
+        let result_inside =
+            bounds.segment_intersection(&Vector::create(150.0, 200.0), &Vector::create(0.0, 0.0));
+        let result_short =
+            bounds.segment_intersection(&Vector::create(0.0, 200.0), &Vector::create(50.0, 200.0));
+        let result_miss =
+            bounds.segment_intersection(&Vector::create(0.0, 0.0), &Vector::create(300.0, 0.0));
+
+        // Assert
+        assert_float(result.unwrap(), 0.5);
+        assert_float(result_inside.unwrap(), 0.0);
+        assert_eq!(result_short, None);
+        assert_eq!(result_miss, None);
+    }
+
+    #[test]
+    fn ray_intersection_should_return_the_entry_fraction() {
+        // Arrange
+        let bounds = test_bounds();
+
+        // Act
+        let result = bounds.ray_intersection(&Vector::create(0.0, 0.0), &Vector::create(1.0, 1.0));
+        let result_behind =
+            bounds.ray_intersection(&Vector::create(0.0, 0.0), &Vector::create(-1.0, -1.0));
+        let result_empty =
+            Bounds::empty().ray_intersection(&Vector::create(0.0, 0.0), &Vector::create(1.0, 1.0));
+
+        // Assert
+        assert_float(result.unwrap(), 150.0);
+        assert_eq!(result_behind, None);
+        assert_eq!(result_empty, None);
     }
 
     #[test]
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+
+use super::bitmap::Bitmap;
+use super::vector::Vector;
+use super::vertices::{Vertices, WindingRule};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum EdgeKey {
+    Horizontal(i64, i64),
+    Vertical(i64, i64),
+}
+
+#[derive(Clone)]
+pub struct TracedShape {
+    outer: Vertices,
+    holes: Vec<Vertices>,
+}
+
+impl TracedShape {
+    pub fn new(outer: Vertices, holes: Vec<Vertices>) -> Self {
+        TracedShape {
+            outer: outer,
+            holes: holes,
+        }
+    }
+
+    pub fn get_outer(&self) -> &Vertices {
+        &self.outer
+    }
+
+    pub fn get_holes(&self) -> &Vec<Vertices> {
+        &self.holes
+    }
+
+    // Traces every solid region of the bitmap (pixels >= threshold) with marching squares and
+    // simplifies the contours. Coordinates are in pixels, outer rings have a positive signed
+    // area and hole rings a negative one.
+    pub fn trace(bitmap: &Bitmap, threshold: u8, tolerance: Option<Real>) -> Vec<TracedShape> {
+        let threshold = threshold.max(1);
+        let tolerance = tolerance.unwrap_or(0.25);
+
+        let segments = TracedShape::march(bitmap, threshold);
+        let rings: Vec<Vec<Vector>> = TracedShape::link(&segments)
+            .iter()
+            .map(|keys| {
+                let points = keys
+                    .iter()
+                    .map(|key| TracedShape::edge_point(bitmap, threshold, key))
+                    .collect();
+                TracedShape::simplify(&points, tolerance)
+            })
+            .filter(|points| points.len() >= 3)
+            .collect();
+
+        TracedShape::nest(rings)
+    }
+
+    fn is_inside(bitmap: &Bitmap, threshold: u8, x: i64, y: i64) -> bool {
+        bitmap.get(x, y) >= threshold
+    }
+
+    fn march(bitmap: &Bitmap, threshold: u8) -> Vec<(EdgeKey, EdgeKey)> {
+        let mut segments = Vec::new();
+        let width = bitmap.get_width() as i64;
+        let height = bitmap.get_height() as i64;
+
+        for y in -1..height {
+            for x in -1..width {
+                let corners = [
+                    TracedShape::is_inside(bitmap, threshold, x, y),
+                    TracedShape::is_inside(bitmap, threshold, x + 1, y),
+                    TracedShape::is_inside(bitmap, threshold, x + 1, y + 1),
+                    TracedShape::is_inside(bitmap, threshold, x, y + 1),
+                ];
+                let edges = [
+                    EdgeKey::Horizontal(x, y),
+                    EdgeKey::Vertical(x + 1, y),
+                    EdgeKey::Horizontal(x, y + 1),
+                    EdgeKey::Vertical(x, y),
+                ];
+
+                let crossed: Vec<EdgeKey> = (0..4)
+                    .filter(|index| corners[*index] != corners[(index + 1) % 4])
+                    .map(|index| edges[index])
+                    .collect();
+
+                match crossed.len() {
+                    2 => segments.push((crossed[0], crossed[1])),
+                    4 => {
+                        let sum: u32 = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)]
+                            .iter()
+                            .map(|(x, y)| bitmap.get(*x, *y) as u32)
+                            .sum();
+                        let centre_inside = sum >= 4 * threshold as u32;
+
+                        if centre_inside == corners[0] {
+                            segments.push((edges[0], edges[1]));
+                            segments.push((edges[2], edges[3]));
+                        } else {
+                            segments.push((edges[3], edges[0]));
+                            segments.push((edges[1], edges[2]));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        segments
+    }
+
+    fn link(segments: &Vec<(EdgeKey, EdgeKey)>) -> Vec<Vec<EdgeKey>> {
+        let mut adjacency: HashMap<EdgeKey, Vec<usize>> = HashMap::new();
+        for (index, (start, end)) in segments.iter().enumerate() {
+            adjacency.entry(*start).or_default().push(index);
+            adjacency.entry(*end).or_default().push(index);
+        }
+
+        let mut used = vec![false; segments.len()];
+        let mut rings = Vec::new();
+
+        for index in 0..segments.len() {
+            if used[index] {
+                continue;
+            }
+            used[index] = true;
+
+            let (first, mut current) = segments[index];
+            let mut ring = vec![first];
+
+            while current != first {
+                ring.push(current);
+                let next = adjacency[&current]
+                    .iter()
+                    .find(|segment| !used[**segment])
+                    .copied();
+
+                match next {
+                    Some(segment) => {
+                        used[segment] = true;
+                        let (start, end) = segments[segment];
+                        current = if start == current { end } else { start };
+                    }
+                    None => break,
+                }
+            }
+            rings.push(ring);
+        }
+        rings
+    }
+
+    fn edge_point(bitmap: &Bitmap, threshold: u8, key: &EdgeKey) -> Vector {
+        let (x, y, next_x, next_y) = match key {
+            EdgeKey::Horizontal(x, y) => (*x, *y, x + 1, *y),
+            EdgeKey::Vertical(x, y) => (*x, *y, *x, y + 1),
+        };
+        let value = bitmap.get(x, y) as Real;
+        let next_value = bitmap.get(next_x, next_y) as Real;
+        let fraction = (threshold as Real - value) / (next_value - value);
+
+        Vector::create(
+            x as Real + 0.5 + fraction * (next_x - x) as Real,
+            y as Real + 0.5 + fraction * (next_y - y) as Real,
+        )
+    }
+
+    fn simplify(points: &Vec<Vector>, tolerance: Real) -> Vec<Vector> {
+        if points.len() < 4 {
+            return points.clone();
+        }
+
+        let first = &points[0];
+        let mut far_index = 0;
+        let mut far_distance = -1.;
+        for (index, point) in points.iter().enumerate() {
+            let mut delta = Vector::new_from(point);
+            delta.sub(first);
+            if delta.magnitude_squared() > far_distance {
+                far_distance = delta.magnitude_squared();
+                far_index = index;
+            }
+        }
+
+        let mut closed = points.clone();
+        closed.push(points[0]);
+
+        let mut result = TracedShape::simplify_chain(&closed[..=far_index], tolerance);
+        result.pop();
+        let mut second = TracedShape::simplify_chain(&closed[far_index..], tolerance);
+        second.pop();
+        result.append(&mut second);
+        result
+    }
+
+    fn simplify_chain(points: &[Vector], tolerance: Real) -> Vec<Vector> {
+        let last = points.len() - 1;
+        if last < 2 {
+            return points.to_vec();
+        }
+
+        let start = &points[0];
+        let end = &points[last];
+        let mut direction = Vector::new_from(end);
+        direction.sub(start);
+        let length = direction.magnitude();
+
+        let mut max_index = 0;
+        let mut max_distance = 0.;
+        for (index, point) in points.iter().enumerate().take(last).skip(1) {
+            let mut delta = Vector::new_from(point);
+            delta.sub(start);
+            let distance = if length == 0. {
+                delta.magnitude()
+            } else {
+                Real::abs(Vector::cross(&direction, &delta)) / length
+            };
+            if distance > max_distance {
+                max_distance = distance;
+                max_index = index;
+            }
+        }
+
+        if max_distance > tolerance {
+            let mut result = TracedShape::simplify_chain(&points[..=max_index], tolerance);
+            result.pop();
+            result.append(&mut TracedShape::simplify_chain(
+                &points[max_index..],
+                tolerance,
+            ));
+            result
+        } else {
+            vec![*start, *end]
+        }
+    }
+
+    fn nest(rings: Vec<Vec<Vector>>) -> Vec<TracedShape> {
+        let vertices: Vec<Vertices> = rings
+            .into_iter()
+            .map(|points| Vertices::create(points, None))
+            .collect();
+
+        let containers: Vec<Vec<usize>> = (0..vertices.len())
+            .map(|index| {
+                (0..vertices.len())
+                    .filter(|other| {
+                        *other != index
+                            && vertices[*other]
+                                .contains_with(&vertices[index][0], WindingRule::EvenOdd)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut shapes: Vec<TracedShape> = Vec::new();
+        let mut shape_of_ring: HashMap<usize, usize> = HashMap::new();
+
+        for (index, ring) in vertices.iter().enumerate() {
+            if containers[index].len().is_multiple_of(2) {
+                shape_of_ring.insert(index, shapes.len());
+                shapes.push(TracedShape::new(
+                    TracedShape::oriented(ring, true),
+                    Vec::new(),
+                ));
+            }
+        }
+
+        for (index, ring) in vertices.iter().enumerate() {
+            let depth = containers[index].len();
+            if depth.is_multiple_of(2) {
+                continue;
+            }
+            let parent = containers[index]
+                .iter()
+                .find(|container| containers[**container].len() == depth - 1);
+
+            if let Some(shape) = parent.and_then(|parent| shape_of_ring.get(parent)) {
+                shapes[*shape]
+                    .holes
+                    .push(TracedShape::oriented(ring, false));
+            }
+        }
+        shapes
+    }
+
+    fn oriented(ring: &Vertices, positive: bool) -> Vertices {
+        if (ring.area(Some(true)) > 0.) == positive {
+            return ring.clone();
+        }
+        let mut points: Vec<Vector> = ring.iter().map(Vector::new_from).collect();
+        points.reverse();
+        Vertices::create(points, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        body::body::Body,
+        geometry::bounds::Bounds,
+        test_utils::{common_test_utils::assert_float, geometry_test_utils::assert_bounds},
+    };
+
+    use super::*;
+
+    fn bitmap_from_rows(rows: &[&str]) -> Bitmap {
+        let width = rows[0].len();
+        let pixels: Vec<u8> = rows
+            .iter()
+            .flat_map(|row| row.chars().map(|c| if c == '#' { 255 } else { 0 }))
+            .collect();
+        Bitmap::from_raw(width, rows.len(), &pixels).unwrap()
+    }
+
+    #[test]
+    fn trace_should_return_a_simplified_outline_for_a_filled_square() {
+        // Arrange
+        let bitmap = bitmap_from_rows(&[
+            "........", "........", "..####..", "..####..", "..####..", "..####..", "........",
+            "........",
+        ]);
+
+        // Act
+        let result = TracedShape::trace(&bitmap, 128, None);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        let outer = result[0].get_outer();
+        assert!(outer.len() <= 8);
+        assert!(outer.area(Some(true)) > 15.);
+        assert!(outer.area(Some(true)) < 16.);
+        assert_eq!(outer.is_convex(), Some(true));
+        assert_eq!(result[0].get_holes().len(), 0);
+        let bounds = Bounds::create(Some(outer.clone()));
+        assert_bounds(
+            &bounds,
+            2.0019607843137255,
+            2.0019607843137255,
+            5.998039215686275,
+            5.998039215686275,
+        );
+    }
+
+    #[test]
+    fn trace_should_return_holes_with_an_opposite_orientation() {
+        // Arrange
+        let bitmap = bitmap_from_rows(&[
+            "........", ".######.", ".######.", ".##..##.", ".##..##.", ".######.", ".######.",
+            "........",
+        ]);
+
+        // Act
+        let result = TracedShape::trace(&bitmap, 128, None);
+
+        // Assert
+        assert_eq!(result.len(), 1);
+        assert!(result[0].get_outer().area(Some(true)) > 35.);
+        assert_eq!(result[0].get_holes().len(), 1);
+        let hole = &result[0].get_holes()[0];
+        assert!(hole.area(Some(true)) < -3.);
+        assert!(hole.contains_with(&Vector::create(4., 4.), WindingRule::NonZero));
+    }
+
+    #[test]
+    fn trace_should_return_separate_shapes_for_islands_and_islands_inside_holes() {
+        // Arrange
+        let bitmap = bitmap_from_rows(&[
+            "...........",
+            ".#######.#.",
+            ".#.....#...",
+            ".#.###.#...",
+            ".#.###.#...",
+            ".#.....#...",
+            ".#######...",
+            "...........",
+        ]);
+
+        // Act
+        let result = TracedShape::trace(&bitmap, 128, Some(0.1));
+
+        // Assert
+        assert_eq!(result.len(), 3);
+        let with_holes: Vec<&TracedShape> = result
+            .iter()
+            .filter(|shape| shape.get_holes().len() == 1)
+            .collect();
+        assert_eq!(with_holes.len(), 1);
+        assert!(with_holes[0]
+            .get_outer()
+            .contains_with(&Vector::create(4.5, 4.), WindingRule::NonZero));
+        assert!(result
+            .iter()
+            .all(|shape| shape.get_outer().area(Some(true)) > 0.));
+    }
+
+    #[test]
+    fn trace_should_ignore_pixels_below_the_threshold() {
+        // Arrange
+        let bitmap = Bitmap::from_raw(3, 3, &[0, 0, 0, 0, 100, 0, 0, 0, 0]).unwrap();
+
+        // Act
+        let result_above = TracedShape::trace(&bitmap, 128, None);
+        let result_below = TracedShape::trace(&bitmap, 50, Some(0.));
+
+        // Assert
+        assert_eq!(result_above.len(), 0);
+        assert_eq!(result_below.len(), 1);
+    }
+
+    #[test]
+    fn trace_should_feed_into_body_creation() {
+        // Arrange
+        let bitmap = Bitmap::from_netpbm(
+            b"P1\n6 6\n0 0 0 0 0 0\n0 1 1 1 1 0\n0 1 0 0 1 0\n0 1 0 0 1 0\n0 1 1 1 1 0\n0 0 0 0 0 0\n",
+        )
+        .unwrap();
+        let shapes = TracedShape::trace(&bitmap, 128, None);
+        let mut body = Body::default_body();
+
+        // Act
+        body.set_vertices_with_holes(shapes[0].get_outer(), shapes[0].get_holes());
+
+        // Assert
+        let outer_area = shapes[0].get_outer().area(None);
+        let hole_area = shapes[0].get_holes()[0].area(None);
+        assert_float(body.get_area(), outer_area - hole_area);
+        assert!(body.get_area() < outer_area);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::core::real::Real;
+use crate::core::xy::XY;
+
+// Adaptive precision predicates after Shewchuk: a fast floating point evaluation guarded by an
+// error bound, falling back to exact expansion arithmetic when the sign can not be trusted.
+
+const EPSILON: Real = Real::EPSILON * 0.5;
+const CCW_ERROR_BOUND_A: Real = (3.0 + 16.0 * EPSILON) * EPSILON;
+const ICC_ERROR_BOUND_A: Real = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+// Positive when a, b and c are in counter clockwise order (y axis up), negative when clockwise
+// and zero when collinear. The sign is always exact, the magnitude approximates twice the area.
+pub fn orient2d(a: &impl XY, b: &impl XY, c: &impl XY) -> Real {
+    let det_left = (a.get_x() - c.get_x()) * (b.get_y() - c.get_y());
+    let det_right = (a.get_y() - c.get_y()) * (b.get_x() - c.get_x());
+    let det = det_left - det_right;
+
+    let det_sum = if det_left > 0.0 {
+        if det_right <= 0.0 {
+            return det;
+        }
+        det_left + det_right
+    } else if det_left < 0.0 {
+        if det_right >= 0.0 {
+            return det;
+        }
+        -det_left - det_right
+    } else {
+        return det;
+    };
+
+    let error_bound = CCW_ERROR_BOUND_A * det_sum;
+    if det >= error_bound || -det >= error_bound {
+        return det;
+    }
+
+    orient2d_exact(a, b, c)
+}
+
+// Positive when d lies inside the circle through a, b and c (given in counter clockwise order),
+// negative when outside and zero when the four points are cocircular.
+pub fn incircle(a: &impl XY, b: &impl XY, c: &impl XY, d: &impl XY) -> Real {
+    let adx = a.get_x() - d.get_x();
+    let bdx = b.get_x() - d.get_x();
+    let cdx = c.get_x() - d.get_x();
+    let ady = a.get_y() - d.get_y();
+    let bdy = b.get_y() - d.get_y();
+    let cdy = c.get_y() - d.get_y();
+
+    let bdxcdy = bdx * cdy;
+    let cdxbdy = cdx * bdy;
+    let alift = adx * adx + ady * ady;
+
+    let cdxady = cdx * ady;
+    let adxcdy = adx * cdy;
+    let blift = bdx * bdx + bdy * bdy;
+
+    let adxbdy = adx * bdy;
+    let bdxady = bdx * ady;
+    let clift = cdx * cdx + cdy * cdy;
+
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    let error_bound = ICC_ERROR_BOUND_A * permanent;
+    if det > error_bound || -det > error_bound {
+        return det;
+    }
+
+    incircle_exact(a, b, c, d)
+}
+
+fn orient2d_exact(a: &impl XY, b: &impl XY, c: &impl XY) -> Real {
+    let acx = two_diff(a.get_x(), c.get_x());
+    let bcy = two_diff(b.get_y(), c.get_y());
+    let acy = two_diff(a.get_y(), c.get_y());
+    let bcx = two_diff(b.get_x(), c.get_x());
+
+    let left = expansion_product(&acx, &bcy);
+    let right = expansion_product(&acy, &bcx);
+    estimate(&expansion_diff(&left, &right))
+}
+
+fn incircle_exact(a: &impl XY, b: &impl XY, c: &impl XY, d: &impl XY) -> Real {
+    let adx = two_diff(a.get_x(), d.get_x());
+    let bdx = two_diff(b.get_x(), d.get_x());
+    let cdx = two_diff(c.get_x(), d.get_x());
+    let ady = two_diff(a.get_y(), d.get_y());
+    let bdy = two_diff(b.get_y(), d.get_y());
+    let cdy = two_diff(c.get_y(), d.get_y());
+
+    let lift = |dx: &Vec<Real>, dy: &Vec<Real>| {
+        expansion_sum(&expansion_product(dx, dx), &expansion_product(dy, dy))
+    };
+    let alift = lift(&adx, &ady);
+    let blift = lift(&bdx, &bdy);
+    let clift = lift(&cdx, &cdy);
+
+    let bc = expansion_diff(
+        &expansion_product(&bdx, &cdy),
+        &expansion_product(&cdx, &bdy),
+    );
+    let ca = expansion_diff(
+        &expansion_product(&cdx, &ady),
+        &expansion_product(&adx, &cdy),
+    );
+    let ab = expansion_diff(
+        &expansion_product(&adx, &bdy),
+        &expansion_product(&bdx, &ady),
+    );
+
+    let det = expansion_sum(
+        &expansion_sum(
+            &expansion_product(&alift, &bc),
+            &expansion_product(&blift, &ca),
+        ),
+        &expansion_product(&clift, &ab),
+    );
+    estimate(&det)
+}
+
+// MARK: Expansion arithmetic
+// Expansions are stored as non-overlapping components ordered by increasing magnitude, so the
+// sign of an expansion is the sign of its last component.
+
+fn fast_two_sum(a: Real, b: Real) -> (Real, Real) {
+    let x = a + b;
+    let b_virtual = x - a;
+    (x, b - b_virtual)
+}
+
+fn two_sum(a: Real, b: Real) -> (Real, Real) {
+    let x = a + b;
+    let b_virtual = x - a;
+    let a_virtual = x - b_virtual;
+    let b_roundoff = b - b_virtual;
+    let a_roundoff = a - a_virtual;
+    (x, a_roundoff + b_roundoff)
+}
+
+fn two_diff(a: Real, b: Real) -> Vec<Real> {
+    let x = a - b;
+    let b_virtual = a - x;
+    let a_virtual = x + b_virtual;
+    let b_roundoff = b_virtual - b;
+    let a_roundoff = a - a_virtual;
+    zero_eliminated(vec![a_roundoff + b_roundoff, x])
+}
+
+fn two_product(a: Real, b: Real) -> (Real, Real) {
+    let x = a * b;
+    (x, a.mul_add(b, -x))
+}
+
+fn zero_eliminated(expansion: Vec<Real>) -> Vec<Real> {
+    expansion
+        .into_iter()
+        .filter(|value| *value != 0.0)
+        .collect()
+}
+
+fn grow_expansion(expansion: &[Real], value: Real) -> Vec<Real> {
+    let mut result = Vec::with_capacity(expansion.len() + 1);
+    let mut q = value;
+    for component in expansion.iter() {
+        let (sum, error) = two_sum(q, *component);
+        if error != 0.0 {
+            result.push(error);
+        }
+        q = sum;
+    }
+    if q != 0.0 || result.is_empty() {
+        result.push(q);
+    }
+    zero_eliminated(result)
+}
+
+fn expansion_sum(e: &[Real], f: &[Real]) -> Vec<Real> {
+    let mut result = e.to_vec();
+    for component in f.iter() {
+        result = grow_expansion(&result, *component);
+    }
+    result
+}
+
+fn expansion_diff(e: &[Real], f: &[Real]) -> Vec<Real> {
+    let negated: Vec<Real> = f.iter().map(|component| -component).collect();
+    expansion_sum(e, &negated)
+}
+
+fn scale_expansion(e: &[Real], b: Real) -> Vec<Real> {
+    let mut result = Vec::with_capacity(e.len() * 2);
+    let mut iter = e.iter();
+    let Some(first) = iter.next() else {
+        return result;
+    };
+
+    let (mut q, error) = two_product(*first, b);
+    if error != 0.0 {
+        result.push(error);
+    }
+    for component in iter {
+        let (product_high, product_low) = two_product(*component, b);
+        let (sum, error) = two_sum(q, product_low);
+        if error != 0.0 {
+            result.push(error);
+        }
+        let (new_q, error) = fast_two_sum(product_high, sum);
+        if error != 0.0 {
+            result.push(error);
+        }
+        q = new_q;
+    }
+    if q != 0.0 {
+        result.push(q);
+    }
+    result
+}
+
+fn expansion_product(e: &[Real], f: &[Real]) -> Vec<Real> {
+    let mut result: Vec<Real> = Vec::new();
+    for component in f.iter() {
+        result = expansion_sum(&result, &scale_expansion(e, *component));
+    }
+    result
+}
+
+fn estimate(expansion: &[Real]) -> Real {
+    expansion.iter().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::vector::Vector;
+
+    use super::*;
+
+    fn exact_orientation(a: (i128, i128), b: (i128, i128), c: (i128, i128)) -> i128 {
+        (a.0 - c.0) * (b.1 - c.1) - (a.1 - c.1) * (b.0 - c.0)
+    }
+
+    #[test]
+    fn orient2d_should_return_the_exact_sign_for_nearly_collinear_points() {
+        // Arrange
+        let unit = Real::EPSILON;
+        let scale = (1_i128) << (Real::MANTISSA_DIGITS - 1);
+        let a = Vector::create(12.0, 12.0);
+        let b = Vector::create(24.0, 24.0);
+        let a_exact = (12 * scale, 12 * scale);
+        let b_exact = (24 * scale, 24 * scale);
+
+        for i in 0..32 {
+            for j in 0..32 {
+                let c = Vector::create(0.5 + i as Real * unit, 0.5 + j as Real * unit);
+                let c_exact = (scale / 2 + i as i128, scale / 2 + j as i128);
+
+                // Act
+                let result = orient2d(&a, &b, &c);
+
+                // Assert
+                let expected = exact_orientation(a_exact, b_exact, c_exact);
+                assert_eq!(
+                    result.partial_cmp(&0.0),
+                    expected.partial_cmp(&0),
+                    "wrong orientation for offset ({}, {})",
+                    i,
+                    j
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn orient2d_should_return_a_positive_value_for_counter_clockwise_points() {
+        // Arrange
+        let a = Vector::create(0.0, 0.0);
+        let b = Vector::create(1.0, 0.0);
+        let c = Vector::create(0.0, 1.0);
+
+        // Act
+        let result = orient2d(&a, &b, &c);
+
+        // Assert
+        assert_eq!(result, 1.0);
+        assert_eq!(orient2d(&a, &c, &b), -1.0);
+    }
+
+    #[test]
+    fn incircle_should_classify_points_inside_outside_and_on_the_circle() {
+        // Arrange
+        let a = Vector::create(1.0, 0.0);
+        let b = Vector::create(0.0, 1.0);
+        let c = Vector::create(-1.0, 0.0);
+        let inside = Vector::create(0.0, 0.5);
+        let outside = Vector::create(0.0, 1.5);
+        let on_circle = Vector::create(0.0, -1.0);
+
+        // Act
+        let result_inside = incircle(&a, &b, &c, &inside);
+        let result_outside = incircle(&a, &b, &c, &outside);
+        let result_on_circle = incircle(&a, &b, &c, &on_circle);
+
+        // Assert
+        assert!(result_inside > 0.0);
+        assert!(result_outside < 0.0);
+        assert_eq!(result_on_circle, 0.0);
+    }
+
+    #[test]
+    fn incircle_should_return_the_exact_sign_for_nearly_cocircular_points() {
+        // Arrange
+        let a = Vector::create(0.1, 0.0);
+        let b = Vector::create(0.0, 0.1);
+        let c = Vector::create(-0.1, 0.0);
+        let d = Vector::create(0.0, -0.1);
+
+        // Act
+        let result = incircle(&a, &b, &c, &d);
+        let result_nudged_in =
+            incircle(&a, &b, &c, &Vector::create(0.0, -0.1 + 0.1 * Real::EPSILON));
+        let result_nudged_out =
+            incircle(&a, &b, &c, &Vector::create(0.0, -0.1 - 0.1 * Real::EPSILON));
+
+        // Assert
+        assert_eq!(result, 0.0);
+        assert!(result_nudged_in > 0.0);
+        assert!(result_nudged_out < 0.0);
+    }
+}
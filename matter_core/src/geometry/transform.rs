@@ -0,0 +1,258 @@
+use crate::core::math;
+use crate::core::real::Real;
+use crate::core::xy::{XYNew, XY};
+
+use super::vector::Vector;
+
+// A rotation followed by a translation, mapping local space points to world space.
+#[derive(Clone, Copy)]
+pub struct Transform {
+    cos: Real,
+    sin: Real,
+    translation: Vector,
+}
+
+impl Transform {
+    pub fn new(angle: Real, translation: &impl XY) -> Self {
+        let (sin, cos) = math::sin_cos(angle);
+        Transform {
+            cos: cos,
+            sin: sin,
+            translation: Vector::new_from(translation),
+        }
+    }
+
+    pub fn identity() -> Self {
+        Transform {
+            cos: 1.,
+            sin: 0.,
+            translation: Vector::create(0., 0.),
+        }
+    }
+
+    pub fn from_rotation(angle: Real) -> Self {
+        Transform::new(angle, &Vector::create(0., 0.))
+    }
+
+    pub fn from_translation(translation: &impl XY) -> Self {
+        Transform {
+            cos: 1.,
+            sin: 0.,
+            translation: Vector::new_from(translation),
+        }
+    }
+
+    pub fn from_rotation_about(angle: Real, point: &impl XY) -> Self {
+        let rotation = Transform::from_rotation(angle);
+        let mut translation = Vector::new_from(point);
+        translation.sub(&rotation.apply_to_vector(point));
+        Transform {
+            translation: translation,
+            ..rotation
+        }
+    }
+
+    pub fn get_cos(&self) -> Real {
+        self.cos
+    }
+
+    pub fn get_sin(&self) -> Real {
+        self.sin
+    }
+
+    pub fn get_angle(&self) -> Real {
+        math::atan2(self.sin, self.cos)
+    }
+
+    pub fn get_translation(&self) -> Vector {
+        self.translation
+    }
+
+    // The transform that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Transform) -> Transform {
+        let mut translation = self.apply_to_vector(&other.translation);
+        translation.add_xy(&self.translation);
+        Transform {
+            cos: self.cos * other.cos - self.sin * other.sin,
+            sin: self.sin * other.cos + self.cos * other.sin,
+            translation: translation,
+        }
+    }
+
+    pub fn inverse(&self) -> Transform {
+        let inverse_rotation = Transform {
+            cos: self.cos,
+            sin: -self.sin,
+            translation: Vector::create(0., 0.),
+        };
+        let mut translation = inverse_rotation.apply_to_vector(&self.translation);
+        translation.neg();
+        Transform {
+            translation: translation,
+            ..inverse_rotation
+        }
+    }
+
+    pub fn apply_to_vector(&self, vector: &impl XY) -> Vector {
+        Vector::create(
+            vector.get_x() * self.cos - vector.get_y() * self.sin,
+            vector.get_x() * self.sin + vector.get_y() * self.cos,
+        )
+    }
+
+    pub fn apply_to_point(&self, point: &impl XY) -> Vector {
+        let mut result = self.apply_to_vector(point);
+        result.add_xy(&self.translation);
+        result
+    }
+
+    pub fn apply_inverse_to_point(&self, point: &impl XY) -> Vector {
+        self.inverse().apply_to_point(point)
+    }
+
+    // Rotates a point about a pivot with this transform's rotation, ignoring the translation.
+    // Working relative to the pivot keeps the result exact for points close to it.
+    pub fn rotate_about(&self, point: &impl XY, pivot: &impl XY) -> Vector {
+        let delta = Vector::create(point.get_x() - pivot.get_x(), point.get_y() - pivot.get_y());
+        let rotated = self.apply_to_vector(&delta);
+        Vector::create(
+            pivot.get_x() + rotated.get_x(),
+            pivot.get_y() + rotated.get_y(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::real::consts::PI;
+
+    use crate::test_utils::{
+        common_test_utils::{assert_float, TOLERANCE},
+        geometry_test_utils::assert_xy,
+    };
+
+    use super::*;
+
+    fn assert_near(result: &impl XY, expected_x: Real, expected_y: Real) {
+        assert!(
+            (result.get_x() - expected_x).abs() < TOLERANCE
+                && (result.get_y() - expected_y).abs() < TOLERANCE,
+            "result: ({}, {}) did not match expected: ({}, {})",
+            result.get_x(),
+            result.get_y(),
+            expected_x,
+            expected_y
+        );
+    }
+
+    #[test]
+    fn apply_to_point_should_rotate_and_then_translate() {
+        // Arrange
+        let transform = Transform::new(PI / 2., &Vector::create(10., 20.));
+        let point = Vector::create(1., 0.);
+
+        // Act
+        let result = transform.apply_to_point(&point);
+
+        // Assert
+        assert_near(&result, 10., 21.);
+    }
+
+    #[test]
+    fn apply_to_vector_should_ignore_the_translation() {
+        // Arrange
+        let transform = Transform::new(PI, &Vector::create(10., 20.));
+        let vector = Vector::create(1., 2.);
+
+        // Act
+        let result = transform.apply_to_vector(&vector);
+
+        // Assert
+        assert_near(&result, -1., -2.);
+    }
+
+    #[test]
+    fn compose_should_apply_the_other_transform_first() {
+        // Arrange
+        let first = Transform::new(PI / 2., &Vector::create(1., 0.));
+        let second = Transform::new(PI / 2., &Vector::create(0., 5.));
+        let point = Vector::create(2., 3.);
+
+        // Act
+        let result = second.compose(&first);
+
+        // Assert
+        let expected = second.apply_to_point(&first.apply_to_point(&point));
+        assert_near(
+            &result.apply_to_point(&point),
+            expected.get_x(),
+            expected.get_y(),
+        );
+        assert_float(result.get_cos(), -1.);
+    }
+
+    #[test]
+    fn inverse_should_undo_the_transform() {
+        // Arrange
+        let transform = Transform::new(0.7, &Vector::create(-3., 8.));
+        let point = Vector::create(4., -2.);
+
+        // Act
+        let result = transform
+            .inverse()
+            .apply_to_point(&transform.apply_to_point(&point));
+
+        // Assert
+        assert_near(&result, 4., -2.);
+        assert_near(
+            &transform.apply_inverse_to_point(&transform.apply_to_point(&point)),
+            4.,
+            -2.,
+        );
+    }
+
+    #[test]
+    fn from_rotation_about_should_keep_the_pivot_in_place() {
+        // Arrange
+        let pivot = Vector::create(5., 5.);
+        let transform = Transform::from_rotation_about(PI / 2., &pivot);
+
+        // Act
+        let result_pivot = transform.apply_to_point(&pivot);
+        let result_point = transform.apply_to_point(&Vector::create(6., 5.));
+
+        // Assert
+        assert_near(&result_pivot, 5., 5.);
+        assert_near(&result_point, 5., 6.);
+    }
+
+    #[test]
+    fn rotate_about_should_rotate_around_the_pivot() {
+        // Arrange
+        let transform = Transform::from_rotation(-2.);
+        let point = Vector::create(10., 2.);
+        let pivot = Vector::create(2., 2.);
+
+        // Act
+        let result = transform.rotate_about(&point, &pivot);
+
+        // Assert
+        assert_xy(&result, -1.3291746923771393, -5.274379414605454);
+    }
+
+    #[test]
+    fn identity_should_not_change_points() {
+        // Arrange
+        let transform = Transform::identity();
+        let translation = Transform::from_translation(&Vector::create(1., 2.));
+
+        // Act
+        let result = transform.apply_to_point(&Vector::create(3., 4.));
+        let result_translated = translation.apply_to_point(&Vector::create(3., 4.));
+
+        // Assert
+        assert_xy(&result, 3., 4.);
+        assert_xy(&result_translated, 4., 6.);
+        assert_float(transform.get_angle(), 0.);
+    }
+}
@@ -1,25 +1,28 @@
+use std::ops;
+
+use crate::core::real::Real;
 use crate::core::xy::{XYNew, XY};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Vector {
-    x: f64,
-    y: f64,
+    x: Real,
+    y: Real,
 }
 
 impl XY for Vector {
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.x
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.y
     }
 
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.x = x;
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.y = y;
     }
 }
@@ -27,26 +30,171 @@ impl XY for Vector {
 impl XYNew for Vector {
     type XY = Vector;
 
-    fn new(x: f64, y: f64) -> Self {
+    fn new(x: Real, y: Real) -> Self {
         Vector { x: x, y: y }
     }
 }
 
 impl Vector {
-    pub fn create(x: f64, y: f64) -> Vector {
+    pub fn create(x: Real, y: Real) -> Vector {
         Vector::new(x, y)
     }
 
-    pub fn rotate(&mut self, angle: f64) {
+    pub fn rotate(&mut self, angle: Real) {
         let point = Vector::create(0.0, 0.0);
         self.rotate_about(angle, &point);
     }
+
+    // Value returning counterparts of the in place XY operations.
+    pub fn rotated(&self, angle: Real) -> Vector {
+        let mut result = *self;
+        result.rotate(angle);
+        result
+    }
+
+    pub fn normalised(&self) -> Vector {
+        let mut result = *self;
+        result.normalise();
+        result
+    }
+
+    // XY::perp already names the in place version
+    pub fn perpendicular(&self, negate: bool) -> Vector {
+        let mut result = *self;
+        result.perp(negate);
+        result
+    }
+}
+
+impl ops::Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::create(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl ops::Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::create(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl ops::Mul<Real> for Vector {
+    type Output = Vector;
+
+    fn mul(self, scalar: Real) -> Vector {
+        Vector::create(self.x * scalar, self.y * scalar)
+    }
+}
+
+impl ops::Mul<Vector> for Real {
+    type Output = Vector;
+
+    fn mul(self, vector: Vector) -> Vector {
+        vector * self
+    }
+}
+
+impl ops::Div<Real> for Vector {
+    type Output = Vector;
+
+    fn div(self, scalar: Real) -> Vector {
+        Vector::create(self.x / scalar, self.y / scalar)
+    }
+}
+
+impl ops::Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::create(-self.x, -self.y)
+    }
+}
+
+impl ops::AddAssign for Vector {
+    fn add_assign(&mut self, other: Vector) {
+        *self = *self + other;
+    }
+}
+
+impl ops::SubAssign for Vector {
+    fn sub_assign(&mut self, other: Vector) {
+        *self = *self - other;
+    }
+}
+
+impl ops::MulAssign<Real> for Vector {
+    fn mul_assign(&mut self, scalar: Real) {
+        *self = *self * scalar;
+    }
+}
+
+impl ops::DivAssign<Real> for Vector {
+    fn div_assign(&mut self, scalar: Real) {
+        *self = *self / scalar;
+    }
+}
+
+impl From<[Real; 2]> for Vector {
+    fn from(value: [Real; 2]) -> Self {
+        Vector::create(value[0], value[1])
+    }
+}
+
+impl From<Vector> for [Real; 2] {
+    fn from(value: Vector) -> Self {
+        [value.x, value.y]
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<mint::Vector2<Real>> for Vector {
+    fn from(value: mint::Vector2<Real>) -> Self {
+        Vector::create(value.x, value.y)
+    }
+}
+
+#[cfg(feature = "mint")]
+impl From<Vector> for mint::Vector2<Real> {
+    fn from(value: Vector) -> Self {
+        mint::Vector2 {
+            x: value.x,
+            y: value.y,
+        }
+    }
+}
+
+// glam has no generic vector, pick the one matching the scalar type
+#[cfg(all(feature = "glam", not(feature = "f32")))]
+type GlamVector = glam::DVec2;
+
+#[cfg(all(feature = "glam", feature = "f32"))]
+type GlamVector = glam::Vec2;
+
+#[cfg(feature = "glam")]
+impl From<GlamVector> for Vector {
+    fn from(value: GlamVector) -> Self {
+        Vector::create(value.x, value.y)
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<Vector> for GlamVector {
+    fn from(value: Vector) -> Self {
+        GlamVector::new(value.x, value.y)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        core::xy::{XYNew, XY},
+        core::{
+            real::Real,
+            xy::{XYNew, XY},
+        },
         geometry::vector::{self, Vector},
         test_utils::{common_test_utils::assert_float, geometry_test_utils::assert_xy},
     };
@@ -61,7 +209,7 @@ mod tests {
         let result = Vector::angle(&vector_a, &vector_b);
 
         // Assert
-        assert_float(result, 0.5880026035475675_f64);
+        assert_float(result, 0.5880026035475675);
     }
 
     #[test]
@@ -73,7 +221,7 @@ mod tests {
         vector.neg();
 
         // Assert
-        assert_xy(&vector, -2_f64, -4_f64);
+        assert_xy(&vector, -2., -4.);
     }
 
     #[test]
@@ -86,7 +234,7 @@ mod tests {
         vector.perp(negate);
 
         // Assert
-        assert_xy(&vector, -4_f64, 2_f64);
+        assert_xy(&vector, -4., 2.);
     }
 
     #[test]
@@ -99,33 +247,33 @@ mod tests {
         vector.perp(negate);
 
         // Assert
-        assert_xy(&vector, 4_f64, -2_f64);
+        assert_xy(&vector, 4., -2.);
     }
 
     #[test]
     fn div_should_calculate_a_valid_result() {
         // Arrange
         let mut vector: Vector = Vector::create(2.0, 4.0);
-        let scalar = 2_f64;
+        let scalar = 2.;
 
         // Act
         vector.div(scalar);
 
         // Assert
-        assert_xy(&vector, 1_f64, 2_f64);
+        assert_xy(&vector, 1., 2.);
     }
 
     #[test]
     fn mult_should_calculate_a_valid_result() {
         // Arrange
         let mut vector: Vector = Vector::create(2.0, 3.0);
-        let scalar = 2_f64;
+        let scalar = 2.;
 
         // Act
         vector.mult(scalar);
 
         // Assert
-        assert_xy(&vector, 4_f64, 6_f64);
+        assert_xy(&vector, 4., 6.);
     }
 
     #[test]
@@ -138,7 +286,7 @@ mod tests {
         vector_a.sub(&vector_b);
 
         // Assert
-        assert_xy(&vector_a, 2_f64, 2_f64);
+        assert_xy(&vector_a, 2., 2.);
     }
 
     #[test]
@@ -151,7 +299,7 @@ mod tests {
         let result = Vector::add(&vector_a, &vector_b);
 
         // Assert
-        assert_xy(&result, 6_f64, 8_f64);
+        assert_xy(&result, 6., 8.);
     }
 
     #[test]
@@ -203,7 +351,7 @@ mod tests {
         vector.normalise();
 
         // Assert
-        assert_xy(&vector, 0.9805806756909202_f64, 0.19611613513818404_f64);
+        assert_xy(&vector, 0.9805806756909202, 0.19611613513818404);
     }
 
     #[test]
@@ -211,26 +359,26 @@ mod tests {
         // Arrange
         let mut vector: Vector = Vector::create(10.0, 2.0);
         let point = Vector::create(2.0, 2.0);
-        let angle = -2_f64;
+        let angle = -2.;
 
         // Act
         vector.rotate_about(angle, &point);
 
         // Assert
-        assert_xy(&vector, -1.3291746923771393_f64, -5.274379414605454_f64);
+        assert_xy(&vector, -1.3291746923771393, -5.274379414605454);
     }
 
     #[test]
     fn rotate_should_mutate_to_valid_result() {
         // Arrange
         let mut vector: Vector = Vector::create(10.0, 2.0);
-        let angle = -2_f64;
+        let angle = -2.;
 
         // Act
         vector.rotate(angle);
 
         // Assert
-        assert_xy(&vector, -2.3428735118200605_f64, -9.925267941351102_f64);
+        assert_xy(&vector, -2.3428735118200605, -9.925267941351102);
     }
 
     #[test]
@@ -242,7 +390,7 @@ mod tests {
         let result = vector.magnitude_squared();
 
         // Assert
-        assert_float(result, 104.0_f64);
+        assert_float(result, 104.0);
     }
 
     #[test]
@@ -254,7 +402,7 @@ mod tests {
         let result = vector.magnitude();
 
         // Assert
-        assert_float(result, 0.0_f64);
+        assert_float(result, 0.0);
     }
 
     #[test]
@@ -268,4 +416,92 @@ mod tests {
         // Assert
         assert_float(result, 5.830951894845301);
     }
+
+    #[test]
+    fn operators_should_calculate_valid_results() {
+        // Arrange
+        let vector_a = Vector::create(2.0, 3.0);
+        let vector_b = Vector::create(4.0, 5.0);
+
+        // Act
+        let result = (vector_a + vector_b) * 2. - vector_b / 2. + -vector_a;
+
+        // Assert
+        assert_xy(&result, 8., 10.5);
+        assert_xy(&(0.5 * vector_b), 2., 2.5);
+    }
+
+    #[test]
+    fn assign_operators_should_mutate_to_valid_result() {
+        // Arrange
+        let mut vector = Vector::create(2.0, 3.0);
+
+        // Act
+        vector += Vector::create(1.0, 1.0);
+        vector *= 4.;
+        vector -= Vector::create(2.0, 6.0);
+        vector /= 2.;
+
+        // Assert
+        assert_xy(&vector, 5., 5.);
+    }
+
+    #[test]
+    fn rotated_normalised_and_perpendicular_should_not_mutate_the_vector() {
+        // Arrange
+        let vector = Vector::create(10.0, 2.0);
+
+        // Act
+        let result_rotated = vector.rotated(-2.);
+        let result_normalised = vector.normalised();
+        let result_perpendicular = vector.perpendicular(true);
+
+        // Assert
+        assert_xy(&vector, 10., 2.);
+        assert_xy(&result_rotated, -2.3428735118200605, -9.925267941351102);
+        assert_xy(&result_normalised, 0.9805806756909202, 0.19611613513818404);
+        assert_xy(&result_perpendicular, 2., -10.);
+    }
+
+    #[test]
+    fn from_should_convert_arrays_both_ways() {
+        // Arrange
+        let vector = Vector::create(1.5, -2.0);
+
+        // Act
+        let result: [Real; 2] = vector.into();
+        let result_vector = Vector::from(result);
+
+        // Assert
+        assert_eq!(result, [1.5, -2.0]);
+        assert_eq!(result_vector, vector);
+    }
+
+    #[test]
+    #[cfg(feature = "mint")]
+    fn from_should_convert_mint_vectors_both_ways() {
+        // Arrange
+        let vector = Vector::create(1.5, -2.0);
+
+        // Act
+        let result: mint::Vector2<Real> = vector.into();
+
+        // Assert
+        assert_eq!(result, mint::Vector2 { x: 1.5, y: -2.0 });
+        assert_eq!(Vector::from(result), vector);
+    }
+
+    #[test]
+    #[cfg(feature = "glam")]
+    fn from_should_convert_glam_vectors_both_ways() {
+        // Arrange
+        let vector = Vector::create(1.5, -2.0);
+
+        // Act
+        let result: super::GlamVector = vector.into();
+
+        // Assert
+        assert_eq!(result, super::GlamVector::new(1.5, -2.0));
+        assert_eq!(Vector::from(result), vector);
+    }
 }
@@ -1,6 +1,9 @@
 use crate::{
     body::body::Body,
-    core::xy::{XYNew, XY},
+    core::{
+        real::Real,
+        xy::{XYNew, XY},
+    },
 };
 
 use super::vector::Vector;
@@ -8,26 +11,26 @@ use super::vector::Vector;
 #[derive(Clone)]
 pub struct Vertex {
     body: Option<Body>,
-    x: f64,
-    y: f64,
+    x: Real,
+    y: Real,
     index: usize,
     is_internal: bool,
 }
 
 impl XY for Vertex {
-    fn get_x(&self) -> f64 {
+    fn get_x(&self) -> Real {
         self.x
     }
 
-    fn get_y(&self) -> f64 {
+    fn get_y(&self) -> Real {
         self.y
     }
 
-    fn set_x(&mut self, x: f64) {
+    fn set_x(&mut self, x: Real) {
         self.x = x;
     }
 
-    fn set_y(&mut self, y: f64) {
+    fn set_y(&mut self, y: Real) {
         self.y = y;
     }
 }
@@ -43,7 +46,7 @@ impl Vertex {
         Vertex::new(body, xy.get_x(), xy.get_y(), index, is_internal)
     }
 
-    pub fn new(body: Option<Body>, x: f64, y: f64, index: usize, is_internal: bool) -> Self {
+    pub fn new(body: Option<Body>, x: Real, y: Real, index: usize, is_internal: bool) -> Self {
         Vertex {
             body: body,
             x: x,
@@ -1,4 +1,3 @@
-use float_cmp::ApproxEq;
 use std::cmp::Ordering;
 use std::collections::btree_map::Values;
 use std::rc::Weak;
@@ -8,9 +7,14 @@ use std::vec;
 use uuid::Uuid;
 
 use crate::body::body::Body;
+use crate::core::math;
+use crate::core::real::Real;
 use crate::core::xy::{XYNew, XY};
 
 use super::super::core::common;
+use super::bounds::Bounds;
+use super::predicates;
+use super::transform::Transform;
 use super::vector::{self, Vector};
 use super::vertex::Vertex;
 use regex::Regex;
@@ -22,6 +26,13 @@ pub enum FromPathError {
     ParseFloatError(String),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindingRule {
+    EvenOdd,
+    #[default]
+    NonZero,
+}
+
 #[derive(Clone)]
 pub struct Vertices {
     value: Vec<Vertex>,
@@ -100,14 +111,14 @@ impl Vertices {
             .map(|caps| {
                 let (_, [first_number, second_number]) = caps.extract();
                 let parse_error = format!("Float parsing error while parsing path {}", path);
-                let x = match first_number.parse::<f64>() {
+                let x = match first_number.parse::<Real>() {
                     Ok(n) => n,
                     Err(e) => {
                         let parse_error = format!("{}\nError:{}", parse_error, e);
                         return Err(FromPathError::ParseFloatError(parse_error));
                     }
                 };
-                let y = match second_number.parse::<f64>() {
+                let y = match second_number.parse::<Real>() {
                     Ok(n) => n,
                     Err(e) => {
                         let parse_error = format!("{}\nError:{}", parse_error, e);
@@ -125,10 +136,10 @@ impl Vertices {
         }
     }
 
-    pub fn area(&self, signed: Option<bool>) -> f64 {
+    pub fn area(&self, signed: Option<bool>) -> Real {
         let signed = signed.unwrap_or(false);
         let mut index2 = self.value.len() - 1;
-        let mut area = 0.0_f64;
+        let mut area = 0.0;
 
         for (index, vertex) in self.value.iter().enumerate() {
             area += (self.value[index2].get_x() - vertex.get_x())
@@ -165,19 +176,19 @@ impl Vertices {
             cur.set_y(cur.get_y() + acc.get_y());
             cur
         });
-        let scalar = self.value.len() as f64;
+        let scalar = self.value.len() as Real;
         average.div(scalar);
         average
     }
 
-    pub fn innertia(&self, mass: f64) -> f64 {
-        let mut numerator: f64 = 0.;
-        let mut denominator: f64 = 0.;
+    pub fn innertia(&self, mass: Real) -> Real {
+        let mut numerator: Real = 0.;
+        let mut denominator: Real = 0.;
 
         for (index, vertex) in self.value.iter().enumerate() {
             let index2 = (index + 1) % self.value.len();
             let vertex2 = self.value[index2].clone();
-            let cross = f64::abs(Vector::cross(&vertex2, vertex));
+            let cross = Real::abs(Vector::cross(&vertex2, vertex));
 
             numerator += cross * (vertex2.dot(&vertex2) + vertex2.dot(vertex) + vertex.dot(vertex));
             denominator += cross;
@@ -185,7 +196,24 @@ impl Vertices {
         (mass / 6.0) * (numerator / denominator)
     }
 
-    pub fn translate(&mut self, vector: &impl XY, scalar: Option<f64>) {
+    pub fn second_moment(&self) -> Real {
+        let mut moment: Real = 0.;
+
+        for (index, vertex) in self.value.iter().enumerate() {
+            let index2 = (index + 1) % self.value.len();
+            let vertex2 = &self.value[index2];
+            let cross = Vector::cross(vertex, vertex2);
+
+            moment += cross * (vertex.dot(vertex) + vertex.dot(vertex2) + vertex2.dot(vertex2));
+        }
+
+        if self.area(Some(true)) < 0. {
+            moment = -moment;
+        }
+        moment / 12.
+    }
+
+    pub fn translate(&mut self, vector: &impl XY, scalar: Option<Real>) {
         let scalar = scalar.unwrap_or(1.);
 
         let mut translate = Vector::new_from(vector);
@@ -201,12 +229,7 @@ impl Vertices {
         let mut previous_vector = &self.value[self.value.len() - 1];
 
         for vertex in self.value.iter() {
-            if (point.get_x() - previous_vector.get_x())
-                * (vertex.get_y() - previous_vector.get_y())
-                + (point.get_y() - previous_vector.get_y())
-                    * (previous_vector.get_x() - vertex.get_x())
-                > 0.0
-            {
+            if predicates::orient2d(previous_vector, point, vertex) > 0.0 {
                 return false;
             }
             previous_vector = vertex
@@ -214,7 +237,51 @@ impl Vertices {
         true
     }
 
-    pub fn scale(&mut self, scale_x: f64, scale_y: f64, point: Option<&impl XY>) {
+    pub fn contains_with(&self, point: &impl XY, rule: WindingRule) -> bool {
+        if self.value.is_empty() {
+            return false;
+        }
+
+        let mut bounds = Bounds::create(None);
+        bounds.update(self, None);
+        if !bounds.contains(point) {
+            return false;
+        }
+
+        let mut winding_number = 0_i32;
+        let mut previous_vertex = &self.value[self.value.len() - 1];
+
+        for vertex in self.value.iter() {
+            let orientation = predicates::orient2d(previous_vertex, vertex, point);
+
+            if orientation == 0.0 && Vertices::is_within_segment(previous_vertex, vertex, point) {
+                return true;
+            }
+
+            if previous_vertex.get_y() <= point.get_y() {
+                if vertex.get_y() > point.get_y() && orientation > 0.0 {
+                    winding_number += 1;
+                }
+            } else if vertex.get_y() <= point.get_y() && orientation < 0.0 {
+                winding_number -= 1;
+            }
+            previous_vertex = vertex;
+        }
+
+        match rule {
+            WindingRule::EvenOdd => winding_number % 2 != 0,
+            WindingRule::NonZero => winding_number != 0,
+        }
+    }
+
+    fn is_within_segment(start: &impl XY, end: &impl XY, point: &impl XY) -> bool {
+        point.get_x() >= start.get_x().min(end.get_x())
+            && point.get_x() <= start.get_x().max(end.get_x())
+            && point.get_y() >= start.get_y().min(end.get_y())
+            && point.get_y() <= start.get_y().max(end.get_y())
+    }
+
+    pub fn scale(&mut self, scale_x: Real, scale_y: Real, point: Option<&impl XY>) {
         let point = if let Some(point) = point {
             Vector::new_from(point)
         } else {
@@ -234,16 +301,16 @@ impl Vertices {
 
     pub fn chamfer(
         &mut self,
-        radius: Option<Vec<f64>>,
-        quality: Option<f64>,
-        quality_min: Option<f64>,
-        quality_max: Option<f64>,
+        radius: Option<Vec<Real>>,
+        quality: Option<Real>,
+        quality_min: Option<Real>,
+        quality_max: Option<Real>,
     ) {
         let default_quality = -1.0;
-        let radius = radius.unwrap_or(vec![8.0_f64]);
+        let radius = radius.unwrap_or(vec![8.0]);
         let quality = quality.unwrap_or(default_quality);
-        let quality_min = quality_min.unwrap_or(2.0_f64);
-        let quality_max = quality_max.unwrap_or(104.0_f64);
+        let quality_min = quality_min.unwrap_or(2.0);
+        let quality_max = quality_max.unwrap_or(104.0);
 
         let mut new_vertices: Vec<Vertex> = Vec::new();
         let value = self.value.clone();
@@ -265,34 +332,26 @@ impl Vertices {
                 continue;
             }
 
-            let mut prev_normal = Vector::new(
+            let prev_normal = Vector::new(
                 vertex.get_y() - prev_vertex.get_y(),
                 prev_vertex.get_x() - vertex.get_x(),
-            );
-            prev_normal.normalise();
+            )
+            .normalised();
 
-            let mut next_normal = Vector::new(
+            let next_normal = Vector::new(
                 next_vertex.get_y() - vertex.get_y(),
                 vertex.get_x() - next_vertex.get_x(),
-            );
-            next_normal.normalise();
-
-            let diagonal_radius = f64::sqrt(2.0 * f64::powf(current_radius, 2.0));
-            let mut radius_vector = prev_normal.clone();
-            radius_vector.mult(current_radius);
+            )
+            .normalised();
 
-            let mut mid_normal = Vector::add(&prev_normal, &next_normal);
-            mid_normal.mult(0.5);
-            mid_normal.normalise();
-
-            let mut scaled_vertex = vertex.clone();
-            let mut mult = mid_normal.clone();
-            mult.mult(diagonal_radius);
-            scaled_vertex.sub(&mult);
+            let diagonal_radius = math::sqrt(2.0 * math::powf(current_radius, 2.0));
+            let radius_vector = prev_normal * current_radius;
+            let mid_normal = ((prev_normal + next_normal) * 0.5).normalised();
+            let scaled_vertex = Vector::new_from(vertex) - mid_normal * diagonal_radius;
 
             let mut precision = quality;
             if quality == default_quality {
-                precision = f64::powf(current_radius, 0.32) * 1.75;
+                precision = math::powf(current_radius, 0.32) * 1.75;
             }
 
             precision = common::clamp(precision, quality_min, quality_max);
@@ -300,15 +359,12 @@ impl Vertices {
                 precision += 1.0;
             }
 
-            let alpha = prev_normal.clone();
-            let alpha = f64::acos(alpha.dot(&next_normal));
+            let alpha = math::acos(prev_normal.dot(&next_normal));
             let theta = alpha / precision;
 
             let mut index = 0_usize;
-            while (index as f64) < precision {
-                let mut rotated = radius_vector.clone();
-                rotated.rotate(theta * index as f64);
-                rotated.add_xy(&scaled_vertex);
+            while (index as Real) < precision {
+                let rotated = radius_vector.rotated(theta * index as Real) + scaled_vertex;
                 new_vertices.push(Vertex::from_xy(
                     vertex.get_body().clone(),
                     &rotated,
@@ -327,32 +383,24 @@ impl Vertices {
         self.value.sort_by(|vector_a: &Vertex, vector_b: &Vertex| {
             let angle_a = Vector::angle(&centre, vector_a);
             let angle_b = Vector::angle(&centre, vector_b);
-
-            if angle_a.approx_eq(angle_b, (0.0, 2)) {
-                Ordering::Equal
-            } else if angle_a - angle_b < 0.0 {
-                Ordering::Less
-            } else {
-                Ordering::Greater
-            }
+            angle_a.partial_cmp(&angle_b).unwrap_or(Ordering::Equal)
         });
     }
 
     pub fn hull(&mut self) {
         self.value.sort_by(|vector_a, vector_b| {
-            let delta_x = vector_a.get_x() - vector_b.get_x();
-            let compare_value = if delta_x.approx_eq(0.0, (0.0, 2)) {
-                vector_a.get_y() - vector_b.get_y()
-            } else {
-                delta_x
-            };
-
-            if compare_value < 0.0 {
-                Ordering::Less
-            } else {
-                Ordering::Greater
-            }
+            let by_x = vector_a.get_x().partial_cmp(&vector_b.get_x());
+            let by_y = vector_a.get_y().partial_cmp(&vector_b.get_y());
+            by_x.unwrap_or(Ordering::Equal)
+                .then(by_y.unwrap_or(Ordering::Equal))
         });
+        self.value
+            .dedup_by(|a, b| a.get_x() == b.get_x() && a.get_y() == b.get_y());
+
+        if self.value.len() < 3 {
+            self.value.reverse();
+            return;
+        }
 
         let mut lower: Vec<Vertex> = Vec::new();
 
@@ -403,10 +451,7 @@ impl Vertices {
         while index < vertices_len {
             let j = (index + 1) % vertices_len;
             let k = (index + 2) % vertices_len;
-            let mut z = (self.value[j].get_x() - self.value[index].get_x())
-                * (self.value[k].get_y() - self.value[j].get_y());
-            z -= (self.value[j].get_y() - self.value[index].get_y())
-                * (self.value[k].get_x() - self.value[j].get_x());
+            let z = predicates::orient2d(&self.value[index], &self.value[j], &self.value[k]);
             index += 1;
 
             if z < 0.0 {
@@ -427,25 +472,27 @@ impl Vertices {
         }
     }
 
-    pub fn rotate(&mut self, angle: f64, point: &impl XY) {
+    pub fn rotate(&mut self, angle: Real, point: &impl XY) {
         if angle == 0. {
             return;
         }
 
-        let cos = f64::cos(angle);
-        let sin = f64::sin(angle);
+        let rotation = Transform::from_rotation(angle);
+        for vertex in self.value.iter_mut() {
+            vertex.set_xy(&rotation.rotate_about(vertex, point));
+        }
+    }
 
+    pub fn transform(&mut self, transform: &Transform) {
         for vertex in self.value.iter_mut() {
-            let dx = vertex.get_x() - point.get_x();
-            let dy = vertex.get_y() - point.get_y();
-            vertex.set_x(point.get_x() + (dx * cos - dy * sin));
-            vertex.set_y(point.get_y() + (dx * sin + dy * cos));
+            vertex.set_xy(&transform.apply_to_point(vertex));
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::core::real::consts;
     use crate::{
         geometry::vector,
         test_utils::{
@@ -479,6 +526,24 @@ mod tests {
         assert_xy(&vertices[3], -14.479963330682054, 38.533915441768585);
     }
 
+    #[test]
+    fn transform_should_rotate_and_translate_the_vertices() {
+        // Arrange
+        let mut vertices = Vertices {
+            value: vec_vector_to_vec_vertex(test_square()),
+        };
+        let transform = Transform::new(consts::PI, &Vector::create(10., 20.));
+
+        // Act
+        vertices.transform(&transform);
+
+        // Assert
+        assert_xy(&vertices[0], 9., 19.);
+        assert_xy(&vertices[1], 7., 19.);
+        assert_xy(&vertices[2], 7., 17.);
+        assert_xy(&vertices[3], 9., 17.);
+    }
+
     #[test]
     fn is_convex_should_return_none_for_vertices_with_z_0() {
         //Arrange
@@ -552,6 +617,56 @@ mod tests {
         assert_xy(&vertices[3], 40.1, 0.0);
     }
 
+    #[test]
+    fn hull_should_not_drop_or_duplicate_points_for_nearly_collinear_vertices() {
+        //Arrange
+        let unit = Real::EPSILON;
+        let mut points = vec![
+            Vector::create(0.5, 0.5),
+            Vector::create(12.0, 12.0),
+            Vector::create(24.0, 24.0),
+            Vector::create(24.0, 24.0),
+            Vector::create(0.0, 30.0),
+        ];
+        for index in 0..16 {
+            let offset = index as Real * unit;
+            points.push(Vector::create(0.5 + offset, 0.5 + 2.0 * offset));
+            points.push(Vector::create(0.5 + 2.0 * offset, 0.5 + offset));
+        }
+        let mut vertices = Vertices::create(points.clone(), None);
+
+        // Act
+        vertices.hull();
+
+        // Assert
+        assert_ne!(vertices.is_convex(), Some(false));
+        for (index, vertex) in vertices.iter().enumerate() {
+            for other in vertices.iter().skip(index + 1) {
+                assert!(vertex.get_x() != other.get_x() || vertex.get_y() != other.get_y());
+            }
+            let next = &vertices[(index + 1) % vertices.len()];
+            let after_next = &vertices[(index + 2) % vertices.len()];
+            assert!(Vector::cross3(vertex, next, after_next) > 0.0);
+        }
+        for point in points.iter() {
+            assert!(vertices.contains(point));
+        }
+    }
+
+    #[test]
+    fn hull_should_keep_a_single_vertex_when_all_vertices_are_equal() {
+        //Arrange
+        let points = vec![Vector::create(1.0, 2.0), Vector::create(1.0, 2.0)];
+        let mut vertices = Vertices::create(points, None);
+
+        // Act
+        vertices.hull();
+
+        // Assert
+        assert_eq!(vertices.len(), 1);
+        assert_xy(&vertices[0], 1.0, 2.0);
+    }
+
     #[test]
     fn clockwise_sort_should_mutate_the_vertices_to_a_valid_vec() {
         //Arrange
@@ -613,10 +728,10 @@ mod tests {
         //Arrange
         let points = test_square_with_decimals();
         let mut vertices = Vertices::create(points, None);
-        let radius = Some(vec![2.0_f64, 3.0_f64, 4.0_f64, 5.0_f64]);
-        let quality = Some(-3.0_f64);
-        let quality_min = Some(8.0_f64);
-        let quality_max = Some(12.0_f64);
+        let radius = Some(vec![2.0, 3.0, 4.0, 5.0]);
+        let quality = Some(-3.0);
+        let quality_min = Some(8.0);
+        let quality_max = Some(12.0);
 
         // Act
         vertices.chamfer(radius, quality, quality_min, quality_max);
@@ -661,8 +776,8 @@ mod tests {
         //Arrange
         let points = test_square_with_decimals();
         let mut vertices = Vertices::create(points, None);
-        let scale_x = 5.0_f64;
-        let scale_y = 8.0_f64;
+        let scale_x = 5.0;
+        let scale_y = 8.0;
         let point = Vector::create(0.0, 0.0);
 
         // Act
@@ -720,6 +835,134 @@ mod tests {
         assert_eq!(result, true);
     }
 
+    fn test_shape_u() -> Vec<Vector> {
+        vec![
+            Vector::create(0.0, 0.0),
+            Vector::create(30.0, 0.0),
+            Vector::create(30.0, 30.0),
+            Vector::create(20.0, 30.0),
+            Vector::create(20.0, 10.0),
+            Vector::create(10.0, 10.0),
+            Vector::create(10.0, 30.0),
+            Vector::create(0.0, 30.0),
+        ]
+    }
+
+    fn test_shape_pentagram() -> Vec<Vector> {
+        vec![
+            Vector::create(0.0, 10.0),
+            Vector::create(5.9, -8.1),
+            Vector::create(-9.5, 3.1),
+            Vector::create(9.5, 3.1),
+            Vector::create(-5.9, -8.1),
+        ]
+    }
+
+    #[test]
+    fn contains_with_should_respond_false_for_a_point_in_the_notch_of_a_concave_shape() {
+        // Arrange
+        let vertices = Vertices::create(test_shape_u(), None);
+        let vector = Vector::create(15.0, 20.0);
+
+        // Act
+        let result = vertices.contains_with(&vector, WindingRule::NonZero);
+
+        // Assert
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn contains_with_should_respond_true_for_a_point_in_the_arm_of_a_concave_shape() {
+        // Arrange
+        let vertices = Vertices::create(test_shape_u(), None);
+        let vector = Vector::create(25.0, 20.0);
+
+        // Act
+        let result_non_zero = vertices.contains_with(&vector, WindingRule::NonZero);
+        let result_even_odd = vertices.contains_with(&vector, WindingRule::EvenOdd);
+
+        // Assert
+        assert_eq!(result_non_zero, true);
+        assert_eq!(result_even_odd, true);
+    }
+
+    #[test]
+    fn contains_with_should_respond_true_when_the_vector_is_on_a_concave_edge() {
+        // Arrange
+        let vertices = Vertices::create(test_shape_u(), None);
+        let vector = Vector::create(15.0, 10.0);
+
+        // Act
+        let result = vertices.contains_with(&vector, WindingRule::EvenOdd);
+
+        // Assert
+        assert_eq!(result, true);
+    }
+
+    #[test]
+    fn contains_with_should_respond_false_when_the_vector_is_outside_the_bounds() {
+        // Arrange
+        let vertices = Vertices::create(test_shape_u(), None);
+        let vector = Vector::create(-1.0, 15.0);
+
+        // Act
+        let result = vertices.contains_with(&vector, WindingRule::NonZero);
+
+        // Assert
+        assert_eq!(result, false);
+    }
+
+    #[test]
+    fn contains_with_should_apply_the_winding_rule_to_self_intersecting_vertices() {
+        // Arrange
+        let vertices = Vertices::create(test_shape_pentagram(), None);
+        let centre = Vector::create(0.0, 0.0);
+        let tip = Vector::create(0.0, 8.0);
+
+        // Act
+        let centre_non_zero = vertices.contains_with(&centre, WindingRule::NonZero);
+        let centre_even_odd = vertices.contains_with(&centre, WindingRule::EvenOdd);
+        let tip_non_zero = vertices.contains_with(&tip, WindingRule::NonZero);
+        let tip_even_odd = vertices.contains_with(&tip, WindingRule::EvenOdd);
+
+        // Assert
+        assert_eq!(centre_non_zero, true);
+        assert_eq!(centre_even_odd, false);
+        assert_eq!(tip_non_zero, true);
+        assert_eq!(tip_even_odd, true);
+    }
+
+    #[test]
+    fn contains_with_should_respond_true_inside_both_rings_of_self_touching_vertices() {
+        // Arrange
+        let points = vec![
+            Vector::create(0.0, 0.0),
+            Vector::create(10.0, 0.0),
+            Vector::create(10.0, 10.0),
+            Vector::create(20.0, 10.0),
+            Vector::create(20.0, 20.0),
+            Vector::create(10.0, 20.0),
+            Vector::create(10.0, 10.0),
+            Vector::create(0.0, 10.0),
+        ];
+        let vertices = Vertices::create(points, None);
+
+        // Act
+        let result_first = vertices.contains_with(&Vector::create(5.0, 5.0), WindingRule::NonZero);
+        let result_second =
+            vertices.contains_with(&Vector::create(15.0, 15.0), WindingRule::EvenOdd);
+        let result_touching =
+            vertices.contains_with(&Vector::create(10.0, 10.0), WindingRule::EvenOdd);
+        let result_outside =
+            vertices.contains_with(&Vector::create(15.0, 5.0), WindingRule::NonZero);
+
+        // Assert
+        assert_eq!(result_first, true);
+        assert_eq!(result_second, true);
+        assert_eq!(result_touching, true);
+        assert_eq!(result_outside, false);
+    }
+
     #[test]
     fn translate_should_mutate_vertices_in_a_valid_way() {
         // Arrange
@@ -727,7 +970,7 @@ mod tests {
         let mut vertices = Vertices::create(points, None);
 
         let vector = Vector::create(5.0, 6.0);
-        let scalar = Some(3.0_f64);
+        let scalar = Some(3.0);
 
         // Act
         vertices.translate(&vector, scalar);
@@ -752,6 +995,23 @@ mod tests {
         assert_float(result, 10720.06666666667);
     }
 
+    #[test]
+    fn second_moment_should_calculate_a_valid_value_for_both_orientations() {
+        // Arrange
+        let points = Vertices::create(test_square_with_decimals(), None);
+        let mut reversed_points = test_square_with_decimals();
+        reversed_points.reverse();
+        let reversed = Vertices::create(reversed_points, None);
+
+        // Act
+        let result = points.second_moment();
+        let result_reversed = reversed.second_moment();
+
+        // Assert
+        assert_float(result, 1723797.4400666673);
+        assert_float(result_reversed, 1723797.4400666673);
+    }
+
     #[test]
     fn mean_should_return_a_valid_vector() {
         // Arrange
@@ -784,10 +1044,10 @@ mod tests {
         let signed = None;
 
         // Act
-        let result: f64 = points.area(signed);
+        let result: Real = points.area(signed);
 
         // Assert
-        assert_float(result, 1608.0100000000002_f64);
+        assert_float(result, 1608.0100000000002);
     }
 
     #[test]
@@ -797,10 +1057,10 @@ mod tests {
         let signed = Some(true);
 
         // Act
-        let result: f64 = points.area(signed);
+        let result: Real = points.area(signed);
 
         // Assert
-        assert_float(result, 1608.0100000000002_f64);
+        assert_float(result, 1608.0100000000002);
     }
 
     #[test]
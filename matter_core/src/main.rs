@@ -14,6 +14,16 @@ use crate::geometry::vector::{self, Vector};
 
 mod body;
 
+mod collision;
+
+mod composite;
+
+mod constraint;
+
+mod factory;
+
+mod render;
+
 // struct Vertex {
 //     body: Body,
 //     y: f64,
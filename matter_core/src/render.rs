@@ -0,0 +1,5 @@
+pub mod debug_draw;
+pub mod layers;
+#[cfg(feature = "raster")]
+pub mod raster;
+pub mod svg;
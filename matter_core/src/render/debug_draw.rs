@@ -0,0 +1,359 @@
+use crate::body::body::Body;
+use crate::collision::pairs::Pairs;
+use crate::composite::composite::Composite;
+use crate::core::color::Color;
+use crate::core::real::Real;
+use crate::core::xy::XYNew;
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+use super::layers::{
+    visible_parts, Layers, PartStyle, AXES, AXIS_LENGTH, BOUNDS, CONSTRAINT, CONTACT,
+    NORMAL_LENGTH, VELOCITY,
+};
+
+const TEXT: Color = Color::WHITE;
+
+// The primitives a backend visualises the simulation with, e.g. a game engine, an image or a
+// terminal. Everything is given in world coordinates, the backend maps them onto its view.
+pub trait DebugDraw {
+    // A closed polygon, filled and outlined as the style says.
+    fn polygon(&mut self, vertices: &[Vector], style: &PartStyle);
+
+    fn circle(&mut self, centre: &Vector, radius: Real, style: &PartStyle);
+
+    fn segment(&mut self, start: &Vector, end: &Vector, color: Color, line_width: Real);
+
+    // A small marker about 3.5 world units wide, e.g. a contact.
+    fn point(&mut self, point: &Vector, color: Color);
+
+    fn text(&mut self, position: &Vector, text: &str, color: Color);
+
+    // Called around the primitives of each layer drawn, e.g. to group them.
+    fn begin_layer(&mut self, _name: &str) {}
+
+    fn end_layer(&mut self) {}
+}
+
+// Walks the world and draws the layers enabled: the bodies by their parts, then the bounds,
+// axes, velocities, constraints, contacts and ids on top. The contacts are taken from the active
+// pairs.
+pub fn debug_draw(
+    draw: &mut impl DebugDraw,
+    world: &Composite,
+    pairs: Option<&Pairs>,
+    layers: &Layers,
+) {
+    let bodies = world.all_bodies();
+    let parts = visible_parts(&bodies);
+
+    if layers.bodies {
+        draw.begin_layer("bodies");
+        for (body, part) in parts.iter() {
+            draw_part(draw, layers, body, part);
+        }
+        draw.end_layer();
+    }
+    if layers.bounds {
+        draw.begin_layer("bounds");
+        let style = PartStyle {
+            fill: None,
+            stroke: Some(BOUNDS),
+            line_width: 1.,
+            opacity: 1.,
+        };
+        for (_, part) in parts.iter() {
+            let bounds = part
+                .get_bounds()
+                .unwrap_or_else(|| Bounds::from_points(&part.get_vertices().get_value()));
+            let corners = [
+                Vector::create(bounds.min.x, bounds.min.y),
+                Vector::create(bounds.max.x, bounds.min.y),
+                Vector::create(bounds.max.x, bounds.max.y),
+                Vector::create(bounds.min.x, bounds.max.y),
+            ];
+            draw.polygon(&corners, &style);
+        }
+        draw.end_layer();
+    }
+    if layers.axes {
+        draw.begin_layer("axes");
+        for (_, part) in parts.iter() {
+            let position = Vector::new_from(&part.get_position());
+            for axis in part.get_axes().iter().flat_map(|axes| axes.iter()) {
+                let end = position + Vector::new_from(axis) * AXIS_LENGTH;
+                draw.segment(&position, &end, AXES, 1.);
+            }
+        }
+        draw.end_layer();
+    }
+    if layers.velocity {
+        draw.begin_layer("velocity");
+        for body in bodies.iter() {
+            let position = Vector::new_from(&body.get_position());
+            let position_prev = body
+                .get_position_prev()
+                .map(|position_prev| Vector::new_from(&position_prev))
+                .unwrap_or(position);
+            draw.segment(&position, &(position * 2. - position_prev), VELOCITY, 1.);
+        }
+        draw.end_layer();
+    }
+    if layers.constraints {
+        draw.begin_layer("constraints");
+        for constraint in world.all_constraints().iter() {
+            if constraint.get_body_a().is_none() && constraint.get_body_b().is_none() {
+                continue;
+            }
+            draw.segment(
+                &Vector::new_from(&constraint.get_world_point_a()),
+                &Vector::new_from(&constraint.get_world_point_b()),
+                CONSTRAINT,
+                1.,
+            );
+        }
+        draw.end_layer();
+    }
+    if let Some(pairs) = pairs.filter(|_| layers.contacts) {
+        draw.begin_layer("contacts");
+        for pair in pairs
+            .get_list()
+            .iter()
+            .filter(|pair| pair.is_active && pair.is_enabled)
+        {
+            let mut centre = Vector::create(0., 0.);
+            for contact in pair.contacts.iter() {
+                let vertex = Vector::new_from(&contact.vertex);
+                draw.point(&vertex, CONTACT);
+                centre += vertex;
+            }
+            if !pair.contacts.is_empty() {
+                centre /= pair.contacts.len() as Real;
+                let end = centre - pair.collision.normal * NORMAL_LENGTH;
+                draw.segment(&centre, &end, CONTACT, 1.);
+            }
+        }
+        draw.end_layer();
+    }
+    if layers.ids {
+        draw.begin_layer("ids");
+        for (_, part) in parts.iter() {
+            let position = Vector::new_from(&part.get_position());
+            draw.text(&position, &part.get_id().to_string(), TEXT);
+        }
+        draw.end_layer();
+    }
+}
+
+fn draw_part(draw: &mut impl DebugDraw, layers: &Layers, body: &Body, part: &Body) {
+    let style = layers.get_style(body, part);
+    if let Some(radius) = part.get_circle_radius() {
+        draw.circle(&Vector::new_from(&part.get_position()), radius, &style);
+        return;
+    }
+
+    let vertices: Vec<Vector> = part
+        .get_vertices()
+        .get_value()
+        .iter()
+        .map(Vector::new_from)
+        .collect();
+    let internal: Vec<bool> = part
+        .get_vertices()
+        .get_value()
+        .iter()
+        .map(|vertex| vertex.get_is_internal() && !layers.internal_edges)
+        .collect();
+    let Some(stroke) = style.stroke.filter(|_| internal.contains(&true)) else {
+        draw.polygon(&vertices, &style);
+        return;
+    };
+
+    // the internal edges of compound bodies are left out of the outline, which is then drawn
+    // edge by edge over the fill
+    draw.polygon(
+        &vertices,
+        &PartStyle {
+            stroke: None,
+            ..style
+        },
+    );
+    let stroke = stroke.with_alpha(stroke.get_alpha() * style.opacity);
+    for index in (0..vertices.len()).filter(|index| !internal[*index]) {
+        let end = &vertices[(index + 1) % vertices.len()];
+        draw.segment(&vertices[index], end, stroke, style.line_width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collision::aabb_tree::AabbTree;
+    use crate::collision::detector::Detector;
+    use crate::constraint::constraint::Constraint;
+    use crate::constraint::constraint_option::ConstraintOption;
+    use crate::core::render::FILL_PALETTE;
+    use crate::render::layers::STROKE_WIREFRAMES;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Shape {
+        Polygon(usize, PartStyle),
+        Circle(Real, PartStyle),
+        Segment(Color),
+        Point(Color),
+        Text(String),
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        shapes: Vec<Shape>,
+    }
+
+    impl DebugDraw for Recorder {
+        fn polygon(&mut self, vertices: &[Vector], style: &PartStyle) {
+            self.shapes.push(Shape::Polygon(vertices.len(), *style));
+        }
+
+        fn circle(&mut self, _: &Vector, radius: Real, style: &PartStyle) {
+            self.shapes.push(Shape::Circle(radius, *style));
+        }
+
+        fn segment(&mut self, _: &Vector, _: &Vector, color: Color, _: Real) {
+            self.shapes.push(Shape::Segment(color));
+        }
+
+        fn point(&mut self, _: &Vector, color: Color) {
+            self.shapes.push(Shape::Point(color));
+        }
+
+        fn text(&mut self, _: &Vector, text: &str, _: Color) {
+            self.shapes.push(Shape::Text(text.to_string()));
+        }
+    }
+
+    fn test_world() -> (Composite, Body, Body) {
+        let body_a = test_box(0., 0., 10., 10.);
+        let mut body_b = test_box(8., 0., 10., 10.);
+        body_b.set_circle_radius(5.);
+        let constraint = Constraint::create(&[
+            ConstraintOption::BodyA(body_a.clone()),
+            ConstraintOption::BodyB(body_b.clone()),
+        ]);
+        let mut world = Composite::new();
+        world.add_body(&body_a);
+        world.add_body(&body_b);
+        world.add_constraint(&constraint);
+        (world, body_a, body_b)
+    }
+
+    #[test]
+    fn debug_draw_should_draw_the_bodies_and_constraints_by_default() {
+        // Arrange
+        let (world, _, _) = test_world();
+        let mut recorder = Recorder::default();
+        let wireframe = PartStyle {
+            fill: None,
+            stroke: Some(STROKE_WIREFRAMES),
+            line_width: 1.,
+            opacity: 1.,
+        };
+
+        // Act
+        world.debug_draw(&mut recorder, None, &Layers::default());
+
+        // Assert
+        assert_eq!(
+            recorder.shapes,
+            vec![
+                Shape::Polygon(4, wireframe),
+                Shape::Circle(5., wireframe),
+                Shape::Segment(CONSTRAINT),
+            ]
+        );
+    }
+
+    #[test]
+    fn debug_draw_should_draw_every_layer_enabled() {
+        // Arrange
+        let (world, body_a, body_b) = test_world();
+        let mut detector = Detector::new(AabbTree::new(None, None));
+        let mut pairs = Pairs::new();
+        pairs.update(&detector.collisions(&world.all_bodies()), 0.);
+        let mut recorder = Recorder::default();
+
+        // Act
+        world.debug_draw(&mut recorder, Some(&pairs), &Layers::all());
+
+        // Assert
+        let count = |predicate: fn(&Shape) -> bool| {
+            recorder
+                .shapes
+                .iter()
+                .filter(|shape| predicate(shape))
+                .count()
+        };
+        let filled = |shape: &Shape| match shape {
+            Shape::Polygon(_, style) | Shape::Circle(_, style) => {
+                style.fill == Some(FILL_PALETTE[0])
+            }
+            _ => false,
+        };
+        assert_eq!(count(filled), 2);
+        assert_eq!(
+            count(
+                |shape| matches!(shape, Shape::Polygon(4, style) if style.stroke == Some(BOUNDS))
+            ),
+            2
+        );
+        assert_eq!(count(|shape| *shape == Shape::Segment(AXES)), 4);
+        assert_eq!(count(|shape| *shape == Shape::Segment(VELOCITY)), 2);
+        assert_eq!(count(|shape| *shape == Shape::Segment(CONSTRAINT)), 1);
+        assert!(count(|shape| *shape == Shape::Point(CONTACT)) > 0);
+        assert_eq!(count(|shape| *shape == Shape::Segment(CONTACT)), 1);
+        assert_eq!(
+            recorder.shapes[recorder.shapes.len() - 2..],
+            [
+                Shape::Text(body_a.get_id().to_string()),
+                Shape::Text(body_b.get_id().to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn debug_draw_should_outline_only_the_external_edges() {
+        // Arrange
+        let mut body = test_box(0., 0., 10., 10.);
+        let mut vertices = body.get_vertices();
+        vertices[1].set_is_interal(true);
+        body.set_vertices(&vertices);
+        body.set_is_sleeping(true);
+        let mut world = Composite::new();
+        world.add_body(&body);
+        let mut recorder = Recorder::default();
+
+        // Act
+        world.debug_draw(&mut recorder, None, &Layers::default());
+
+        // Assert
+        let faded = STROKE_WIREFRAMES.with_alpha(0.5);
+        assert_eq!(
+            recorder.shapes,
+            vec![
+                Shape::Polygon(
+                    4,
+                    PartStyle {
+                        fill: None,
+                        stroke: None,
+                        line_width: 1.,
+                        opacity: 0.5,
+                    }
+                ),
+                Shape::Segment(faded),
+                Shape::Segment(faded),
+                Shape::Segment(faded),
+            ]
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::body::body::Body;
+use crate::core::color::Color;
+use crate::core::real::Real;
+
+// The colors and sizes of the debug layers, as in matter.js.
+pub const BACKGROUND: Color = Color::rgb(0x14, 0x15, 0x1f);
+pub const BACKGROUND_WIREFRAMES: Color = Color::rgb(0x0f, 0x0f, 0x13);
+pub const STROKE_WIREFRAMES: Color = Color::rgb(0xbb, 0xbb, 0xbb);
+pub const CONSTRAINT: Color = Color::WHITE;
+pub const CONTACT: Color = Color::ORANGE;
+pub const BOUNDS: Color = Color::new(255, 255, 255, 20);
+pub const AXES: Color = Color::INDIAN_RED;
+pub const VELOCITY: Color = Color::CORNFLOWER_BLUE;
+pub const AXIS_LENGTH: Real = 20.;
+pub const NORMAL_LENGTH: Real = 8.;
+pub const SLEEPING_OPACITY: Real = 0.5;
+
+// How a part is drawn, without a stroke when its line width is 0.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PartStyle {
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub line_width: Real,
+    pub opacity: Real,
+}
+
+// What a renderer draws. By default, as in matter.js, the bodies are drawn as wireframes with the
+// constraints and with the sleeping bodies faded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Layers {
+    pub wireframes: bool,
+    pub bodies: bool,
+    pub internal_edges: bool,
+    pub constraints: bool,
+    pub contacts: bool,
+    pub bounds: bool,
+    pub axes: bool,
+    pub velocity: bool,
+    pub sleeping: bool,
+    // The ids of the parts as text, left out by the raster renderer which has no font.
+    pub ids: bool,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Layers {
+            wireframes: true,
+            bodies: true,
+            internal_edges: false,
+            constraints: true,
+            contacts: false,
+            bounds: false,
+            axes: false,
+            velocity: false,
+            sleeping: true,
+            ids: false,
+        }
+    }
+}
+
+impl Layers {
+    // Every layer, filled instead of wireframes.
+    pub fn all() -> Self {
+        Layers {
+            wireframes: false,
+            bodies: true,
+            internal_edges: true,
+            constraints: true,
+            contacts: true,
+            bounds: true,
+            axes: true,
+            velocity: true,
+            sleeping: true,
+            ids: true,
+        }
+    }
+
+    // The style of the part, wireframes are drawn in one color whatever the styles.
+    pub fn get_style(&self, body: &Body, part: &Body) -> PartStyle {
+        let render = part.get_render().inherit(&body.get_render());
+        let mut opacity = render.get_opacity();
+        if self.sleeping && body.get_is_sleeping() {
+            opacity *= SLEEPING_OPACITY;
+        }
+
+        if self.wireframes {
+            return PartStyle {
+                fill: None,
+                stroke: Some(STROKE_WIREFRAMES),
+                line_width: 1.,
+                opacity: opacity,
+            };
+        }
+
+        let (fill, stroke, line_width) = render.get_styles(body.get_is_static());
+        PartStyle {
+            fill: Some(fill),
+            stroke: Some(stroke).filter(|_| line_width > 0.),
+            line_width: line_width,
+            opacity: opacity,
+        }
+    }
+}
+
+// The visible parts of the visible bodies with their bodies, from the lowest layer up and in
+// the order of the bodies within a layer. Compound bodies are drawn by their parts.
+pub fn visible_parts(bodies: &[Body]) -> Vec<(Body, Body)> {
+    let mut bodies: Vec<&Body> = bodies
+        .iter()
+        .filter(|body| body.get_render().get_visible())
+        .collect();
+    bodies.sort_by_key(|body| body.get_render().get_layer());
+
+    bodies
+        .into_iter()
+        .flat_map(|body| {
+            let parts = body.get_parts();
+            let start = if parts.len() > 1 { 1 } else { 0 };
+            parts
+                .into_iter()
+                .skip(start)
+                .filter(|part| part.get_render().get_visible())
+                .map(|part| (body.clone(), part))
+                .collect::<Vec<(Body, Body)>>()
+        })
+        .collect()
+}
@@ -0,0 +1,595 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use crate::collision::pairs::Pairs;
+use crate::composite::composite::Composite;
+use crate::core::color::Color;
+use crate::core::real::Real;
+use crate::core::xy::XY;
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+use super::debug_draw::{self, DebugDraw};
+use super::layers::{Layers, PartStyle, BACKGROUND, BACKGROUND_WIREFRAMES};
+
+// An RGBA image drawn on the CPU. Shapes are sampled at the pixel centres, without anti-aliasing,
+// so the same frame always gives the same pixels.
+#[derive(Clone)]
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Canvas {
+            width: width,
+            height: height,
+            pixels: vec![0; width as usize * height as usize * 4],
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    // The rows of RGBA bytes, top to bottom.
+    pub fn get_pixels(&self) -> &Vec<u8> {
+        &self.pixels
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        [
+            self.pixels[index],
+            self.pixels[index + 1],
+            self.pixels[index + 2],
+            self.pixels[index + 3],
+        ]
+    }
+    // endregion: Getters
+
+    // MARK: Actions
+    // region: Actions
+    pub fn clear(&mut self, color: [u8; 4]) {
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    // Fills the polygon by the even-odd rule, in pixel coordinates.
+    pub fn fill_polygon(&mut self, points: &[Vector], color: [u8; 4], opacity: Real) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let (min_y, max_y) = points.iter().fold(
+            (Real::INFINITY, -Real::INFINITY),
+            |(min_y, max_y), point| (min_y.min(point.get_y()), max_y.max(point.get_y())),
+        );
+        let mut crossings: Vec<Real> = Vec::new();
+        for row in self.row_range(min_y, max_y) {
+            let y = row as Real + 0.5;
+            crossings.clear();
+            for (index, start) in points.iter().enumerate() {
+                let end = &points[(index + 1) % points.len()];
+                if (start.get_y() <= y) != (end.get_y() <= y) {
+                    let fraction = (y - start.get_y()) / (end.get_y() - start.get_y());
+                    crossings.push(start.get_x() + (end.get_x() - start.get_x()) * fraction);
+                }
+            }
+            crossings.sort_by(|a, b| a.total_cmp(b));
+
+            for span in crossings.chunks_exact(2) {
+                for column in self.column_range(span[0], span[1]) {
+                    self.blend(column, row, color, opacity);
+                }
+            }
+        }
+    }
+
+    pub fn fill_circle(&mut self, centre: &Vector, radius: Real, color: [u8; 4], opacity: Real) {
+        self.fill_ring(centre, 0., radius, color, opacity);
+    }
+
+    pub fn stroke_circle(
+        &mut self,
+        centre: &Vector,
+        radius: Real,
+        line_width: Real,
+        color: [u8; 4],
+        opacity: Real,
+    ) {
+        let half_width = line_width * 0.5;
+        self.fill_ring(
+            centre,
+            (radius - half_width).max(0.),
+            radius + half_width,
+            color,
+            opacity,
+        );
+    }
+
+    // Strokes the segment as a rectangle of the line width, at least a pixel wide.
+    pub fn stroke_segment(
+        &mut self,
+        start: &Vector,
+        end: &Vector,
+        line_width: Real,
+        color: [u8; 4],
+        opacity: Real,
+    ) {
+        let direction = *end - *start;
+        if direction.magnitude() == 0. {
+            return;
+        }
+
+        let offset = direction.normalised().perpendicular(false) * (line_width.max(1.) * 0.5);
+        self.fill_polygon(
+            &[
+                *start - offset,
+                *end - offset,
+                *end + offset,
+                *start + offset,
+            ],
+            color,
+            opacity,
+        );
+    }
+
+    pub fn write_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(writer, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()
+            .and_then(|mut writer| writer.write_image_data(&self.pixels))
+            .map_err(io::Error::other)
+    }
+
+    // Writes the frames as a looping animated GIF, with the delay between frames in milliseconds.
+    // The frames have to be of the same size, at most 65535 pixels wide and high.
+    pub fn write_gif(path: impl AsRef<Path>, frames: &[Canvas], delay: u32) -> io::Result<()> {
+        let Some(first) = frames.first() else {
+            return Ok(());
+        };
+        if frames
+            .iter()
+            .any(|frame| frame.width != first.width || frame.height != first.height)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the frames of a GIF have to be of the same size",
+            ));
+        }
+        let (Ok(width), Ok(height)) = (u16::try_from(first.width), u16::try_from(first.height))
+        else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the frames of a GIF are at most 65535 pixels wide and high",
+            ));
+        };
+
+        let writer = BufWriter::new(File::create(path)?);
+        let mut encoder =
+            gif::Encoder::new(writer, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        for canvas in frames.iter() {
+            let mut pixels = canvas.pixels.clone();
+            let mut frame = gif::Frame::from_rgba_speed(width, height, &mut pixels, 10);
+            frame.delay = (delay / 10) as u16;
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+
+    fn fill_ring(
+        &mut self,
+        centre: &Vector,
+        inner_radius: Real,
+        outer_radius: Real,
+        color: [u8; 4],
+        opacity: Real,
+    ) {
+        let inner_squared = inner_radius * inner_radius;
+        let outer_squared = outer_radius * outer_radius;
+        for row in self.row_range(centre.get_y() - outer_radius, centre.get_y() + outer_radius) {
+            let y = row as Real + 0.5 - centre.get_y();
+            for column in
+                self.column_range(centre.get_x() - outer_radius, centre.get_x() + outer_radius)
+            {
+                let x = column as Real + 0.5 - centre.get_x();
+                let distance_squared = x * x + y * y;
+                if distance_squared <= outer_squared
+                    && (inner_squared == 0. || distance_squared >= inner_squared)
+                {
+                    self.blend(column, row, color, opacity);
+                }
+            }
+        }
+    }
+
+    // The rows whose centres lie between the coordinates, clipped to the canvas.
+    fn row_range(&self, min: Real, max: Real) -> std::ops::Range<u32> {
+        Canvas::pixel_range(min, max, self.height)
+    }
+
+    fn column_range(&self, min: Real, max: Real) -> std::ops::Range<u32> {
+        Canvas::pixel_range(min, max, self.width)
+    }
+
+    fn pixel_range(min: Real, max: Real, size: u32) -> std::ops::Range<u32> {
+        let start = (min - 0.5).ceil().clamp(0., size as Real) as u32;
+        let end = ((max - 0.5).floor() + 1.).clamp(0., size as Real) as u32;
+        start..end.max(start)
+    }
+
+    // Draws the color over the pixel, the alpha of the color scaled by the opacity.
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 4], opacity: Real) {
+        let alpha = color[3] as Real / 255. * opacity.clamp(0., 1.);
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let pixel = &mut self.pixels[index..index + 4];
+
+        let alpha_below = pixel[3] as Real / 255.;
+        let alpha_out = alpha + alpha_below * (1. - alpha);
+        if alpha_out == 0. {
+            return;
+        }
+        for channel in 0..3 {
+            let value = (color[channel] as Real * alpha
+                + pixel[channel] as Real * alpha_below * (1. - alpha))
+                / alpha_out;
+            pixel[channel] = value.round() as u8;
+        }
+        pixel[3] = (alpha_out * 255.).round() as u8;
+    }
+    // endregion: Actions
+}
+
+// Draws frames of a world into RGBA canvases, without any window or GPU. The view is the region
+// of the world shown, fitted into the image and centred as in the SVG renderer.
+pub struct RasterRenderer {
+    width: u32,
+    height: u32,
+    view: Bounds,
+    layers: Layers,
+    frame: usize,
+    canvas: Canvas,
+}
+
+impl RasterRenderer {
+    // The view defaults to the region of the world from the origin to the size of the image.
+    pub fn new(width: u32, height: u32, view: Option<Bounds>) -> Self {
+        RasterRenderer {
+            width: width,
+            height: height,
+            view: view.unwrap_or_else(|| {
+                Bounds::from_points(&[
+                    Vector::create(0., 0.),
+                    Vector::create(width as Real, height as Real),
+                ])
+            }),
+            layers: Layers::default(),
+            frame: 0,
+            canvas: Canvas::new(0, 0),
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get_view(&self) -> Bounds {
+        self.view
+    }
+
+    pub fn get_layers(&self) -> Layers {
+        self.layers
+    }
+
+    // The number of the next frame written.
+    pub fn get_frame(&self) -> usize {
+        self.frame
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+    pub fn set_view(&mut self, view: &Bounds) {
+        self.view = *view;
+    }
+
+    pub fn set_layers(&mut self, layers: &Layers) {
+        self.layers = *layers;
+    }
+
+    // Fits the view around the bounds, with the padding in world units on every side.
+    pub fn look_at(&mut self, bounds: &Bounds, padding: Option<Real>) {
+        let mut view = *bounds;
+        view.expand(padding.unwrap_or(0.));
+        self.view = view;
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+
+    // Writes the next frame into the directory as frame_00000.png, frame_00001.png and so on.
+    pub fn write_frame(
+        &mut self,
+        directory: impl AsRef<Path>,
+        world: &Composite,
+        pairs: Option<&Pairs>,
+    ) -> io::Result<PathBuf> {
+        fs::create_dir_all(directory.as_ref())?;
+        let path = directory
+            .as_ref()
+            .join(format!("frame_{:05}.png", self.frame));
+        self.render(world, pairs).write_png(&path)?;
+        self.frame += 1;
+        Ok(path)
+    }
+
+    // The frame of the world with the layers drawn. The contacts are taken from the active pairs.
+    pub fn render(&mut self, world: &Composite, pairs: Option<&Pairs>) -> Canvas {
+        let layers = self.layers;
+        self.canvas = Canvas::new(self.width, self.height);
+        self.canvas.clear(
+            if layers.wireframes {
+                BACKGROUND_WIREFRAMES
+            } else {
+                BACKGROUND
+            }
+            .to_rgba(),
+        );
+
+        debug_draw::debug_draw(self, world, pairs, &layers);
+
+        std::mem::replace(&mut self.canvas, Canvas::new(0, 0))
+    }
+
+    // Pixels per world unit, the view fits the image without stretching.
+    fn get_scale(&self) -> Real {
+        (self.width as Real / self.view.get_width())
+            .min(self.height as Real / self.view.get_height())
+    }
+
+    fn to_pixels(&self, point: &Vector) -> Vector {
+        let scale = self.get_scale();
+        let margin = Vector::create(
+            self.width as Real - self.view.get_width() * scale,
+            self.height as Real - self.view.get_height() * scale,
+        ) * 0.5;
+        (*point - Vector::create(self.view.min.x, self.view.min.y)) * scale + margin
+    }
+    // endregion: Actions
+}
+
+// The primitives are drawn onto the canvas being rendered, with the line widths scaled from
+// world units to pixels.
+impl DebugDraw for RasterRenderer {
+    fn polygon(&mut self, vertices: &[Vector], style: &PartStyle) {
+        let points: Vec<Vector> = vertices
+            .iter()
+            .map(|vertex| self.to_pixels(vertex))
+            .collect();
+        if let Some(fill) = style.fill {
+            self.canvas
+                .fill_polygon(&points, fill.to_rgba(), style.opacity);
+        }
+        if let Some(stroke) = style.stroke {
+            let line_width = style.line_width * self.get_scale();
+            for index in 0..points.len() {
+                let end = &points[(index + 1) % points.len()];
+                self.canvas.stroke_segment(
+                    &points[index],
+                    end,
+                    line_width,
+                    stroke.to_rgba(),
+                    style.opacity,
+                );
+            }
+        }
+    }
+
+    fn circle(&mut self, centre: &Vector, radius: Real, style: &PartStyle) {
+        let centre = self.to_pixels(centre);
+        let radius = radius * self.get_scale();
+        if let Some(fill) = style.fill {
+            self.canvas
+                .fill_circle(&centre, radius, fill.to_rgba(), style.opacity);
+        }
+        if let Some(stroke) = style.stroke {
+            let line_width = style.line_width * self.get_scale();
+            self.canvas
+                .stroke_circle(&centre, radius, line_width, stroke.to_rgba(), style.opacity);
+        }
+    }
+
+    fn segment(&mut self, start: &Vector, end: &Vector, color: Color, line_width: Real) {
+        let (start, end) = (self.to_pixels(start), self.to_pixels(end));
+        let line_width = line_width * self.get_scale();
+        self.canvas
+            .stroke_segment(&start, &end, line_width, color.to_rgba(), 1.);
+    }
+
+    fn point(&mut self, point: &Vector, color: Color) {
+        let point = self.to_pixels(point);
+        let radius = 1.75 * self.get_scale();
+        self.canvas.fill_circle(&point, radius, color.to_rgba(), 1.);
+    }
+
+    // The canvas has no font, the text is left out.
+    fn text(&mut self, _: &Vector, _: &str, _: Color) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::body::Body;
+    use crate::core::render::{Render, FILL_PALETTE};
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    fn test_world(bodies: &[Body]) -> Composite {
+        let mut world = Composite::new();
+        for body in bodies.iter() {
+            world.add_body(body);
+        }
+        world
+    }
+
+    fn test_renderer() -> RasterRenderer {
+        let mut renderer = RasterRenderer::new(40, 20, None);
+        renderer.set_layers(&Layers {
+            wireframes: false,
+            ..Layers::default()
+        });
+        renderer
+    }
+
+    #[test]
+    fn fill_polygon_should_fill_the_pixels_with_centres_inside() {
+        // Arrange
+        let mut canvas = Canvas::new(10, 10);
+
+        // Act
+        canvas.fill_polygon(
+            &[
+                Vector::create(2., 2.),
+                Vector::create(6., 2.),
+                Vector::create(6., 5.),
+                Vector::create(2., 5.),
+            ],
+            [255, 0, 0, 255],
+            1.,
+        );
+
+        // Assert
+        let count = canvas
+            .get_pixels()
+            .chunks_exact(4)
+            .filter(|pixel| pixel[3] == 255)
+            .count();
+        assert_eq!(count, 12);
+        assert_eq!(canvas.get_pixel(2, 2), [255, 0, 0, 255]);
+        assert_eq!(canvas.get_pixel(6, 2), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_circle_should_blend_by_the_opacity() {
+        // Arrange
+        let mut canvas = Canvas::new(10, 10);
+        canvas.clear([0, 0, 0, 255]);
+
+        // Act
+        canvas.fill_circle(&Vector::create(5., 5.), 3., [255, 255, 255, 255], 0.5);
+
+        // Assert
+        assert_eq!(canvas.get_pixel(5, 5), [128, 128, 128, 255]);
+        assert_eq!(canvas.get_pixel(0, 0), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn render_should_fill_the_visible_bodies_in_the_view() {
+        // Arrange
+        let mut body = test_box(10., 10., 8., 8.);
+        let mut render = body.get_render();
+        render.set_stroke_style(Some(Color::RED));
+        render.set_line_width(Some(1.));
+        body.set_render(&render);
+        let mut hidden = test_box(30., 10., 8., 8.);
+        hidden.set_render(&Render::new(false, 1., hidden.get_render().get_sprite()));
+        let world = test_world(&[body, hidden]);
+
+        // Act
+        let result = test_renderer().render(&world, None);
+
+        // Assert
+        assert_eq!(result.get_pixel(10, 10), FILL_PALETTE[0].to_rgba());
+        assert_eq!(result.get_pixel(6, 10), Color::RED.to_rgba());
+        assert_eq!(result.get_pixel(30, 10), BACKGROUND.to_rgba());
+        assert_eq!(result.get_pixel(1, 1), BACKGROUND.to_rgba());
+    }
+
+    #[test]
+    fn render_should_honour_the_opacity_and_the_view() {
+        // Arrange
+        let mut body = test_box(10., 10., 8., 8.);
+        body.set_render(&Render::new(true, 0.5, body.get_render().get_sprite()));
+        let world = test_world(&[body]);
+        let mut renderer = test_renderer();
+        renderer.set_view(&Bounds::from_points(&[
+            Vector::create(0., 5.),
+            Vector::create(20., 15.),
+        ]));
+
+        // Act
+        let result = renderer.render(&world, None);
+
+        // Assert
+        assert_eq!(result.get_pixel(20, 10), [131, 86, 52, 255]);
+        assert_eq!(result.get_pixel(4, 10), BACKGROUND.to_rgba());
+    }
+
+    #[test]
+    fn write_frame_and_write_gif_should_encode_the_frames() {
+        // Arrange
+        let world = test_world(&[test_box(10., 10., 8., 8.)]);
+        let directory = std::env::temp_dir().join(format!("raster_{}", world.get_id()));
+        let mut renderer = test_renderer();
+
+        // Act
+        let result = renderer.write_frame(&directory, &world, None).unwrap();
+        let frames = vec![renderer.render(&world, None); 2];
+        let result_gif = directory.join("frames.gif");
+        Canvas::write_gif(&result_gif, &frames, 100).unwrap();
+
+        // Assert
+        let decoder = png::Decoder::new(File::open(&result).unwrap());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!(result.file_name().unwrap(), "frame_00000.png");
+        assert_eq!(reader.info().width, 40);
+        assert_eq!(reader.info().height, 20);
+        assert!(fs::read(&result_gif).unwrap().starts_with(b"GIF89a"));
+        assert_eq!(renderer.get_frame(), 1);
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn write_gif_should_reject_frames_of_different_or_oversized_sizes() {
+        // Arrange
+        let path = std::env::temp_dir().join(format!("raster_{}.gif", Composite::new().get_id()));
+        let frames = vec![Canvas::new(40, 20), Canvas::new(20, 40)];
+        let frames_oversized = vec![Canvas::new(70000, 1)];
+
+        // Act
+        let result = Canvas::write_gif(&path, &frames, 100);
+        let result_oversized = Canvas::write_gif(&path, &frames_oversized, 100);
+
+        // Assert
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            result_oversized.unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+        assert!(!path.exists());
+    }
+}
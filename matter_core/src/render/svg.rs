@@ -0,0 +1,437 @@
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::collision::pairs::Pairs;
+use crate::composite::composite::Composite;
+use crate::core::color::Color;
+use crate::core::real::Real;
+use crate::core::xy::XY;
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+
+use super::debug_draw::{self, DebugDraw};
+use super::layers::{Layers, PartStyle, BACKGROUND, BACKGROUND_WIREFRAMES};
+
+// Draws frames of a world into SVG documents, without any window or GPU. The view is the region
+// of the world shown, scaled to the width and height of the image.
+pub struct SvgRenderer {
+    width: u32,
+    height: u32,
+    view: Bounds,
+    layers: Layers,
+    frame: usize,
+    svg: String,
+}
+
+impl SvgRenderer {
+    // The view defaults to the region of the world from the origin to the size of the image.
+    pub fn new(width: u32, height: u32, view: Option<Bounds>) -> Self {
+        SvgRenderer {
+            width: width,
+            height: height,
+            view: view.unwrap_or_else(|| {
+                Bounds::from_points(&[
+                    Vector::create(0., 0.),
+                    Vector::create(width as Real, height as Real),
+                ])
+            }),
+            layers: Layers::default(),
+            frame: 0,
+            svg: String::new(),
+        }
+    }
+
+    // MARK: Getters
+    // region: Getters
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get_view(&self) -> Bounds {
+        self.view
+    }
+
+    pub fn get_layers(&self) -> Layers {
+        self.layers
+    }
+
+    // The number of the next frame written.
+    pub fn get_frame(&self) -> usize {
+        self.frame
+    }
+    // endregion: Getters
+
+    // MARK: Setters
+    // region: Setters
+    pub fn set_view(&mut self, view: &Bounds) {
+        self.view = *view;
+    }
+
+    pub fn set_layers(&mut self, layers: &Layers) {
+        self.layers = *layers;
+    }
+
+    // Fits the view around the bounds, with the padding in world units on every side.
+    pub fn look_at(&mut self, bounds: &Bounds, padding: Option<Real>) {
+        let mut view = *bounds;
+        view.expand(padding.unwrap_or(0.));
+        self.view = view;
+    }
+    // endregion: Setters
+
+    // MARK: Actions
+    // region: Actions
+
+    // Writes the next frame into the directory as frame_00000.svg, frame_00001.svg and so on.
+    pub fn write_frame(
+        &mut self,
+        directory: impl AsRef<Path>,
+        world: &Composite,
+        pairs: Option<&Pairs>,
+    ) -> io::Result<PathBuf> {
+        let path = directory
+            .as_ref()
+            .join(format!("frame_{:05}.svg", self.frame));
+        fs::write(&path, self.render(world, pairs))?;
+        self.frame += 1;
+        Ok(path)
+    }
+
+    // The SVG document of the world, with a group for each layer drawn. The contacts are taken
+    // from the active pairs.
+    pub fn render(&mut self, world: &Composite, pairs: Option<&Pairs>) -> String {
+        let layers = self.layers;
+        let view = self.view;
+        self.svg.clear();
+
+        writeln!(
+            self.svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+            self.width,
+            self.height,
+            number(view.min.x),
+            number(view.min.y),
+            number(view.get_width()),
+            number(view.get_height()),
+        )
+        .unwrap();
+        writeln!(
+            self.svg,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            number(view.min.x),
+            number(view.min.y),
+            number(view.get_width()),
+            number(view.get_height()),
+            if layers.wireframes {
+                BACKGROUND_WIREFRAMES
+            } else {
+                BACKGROUND
+            }
+            .to_css(),
+        )
+        .unwrap();
+
+        debug_draw::debug_draw(self, world, pairs, &layers);
+
+        writeln!(self.svg, "</svg>").unwrap();
+        std::mem::take(&mut self.svg)
+    }
+    // endregion: Actions
+}
+
+// The primitives are written into the document being rendered.
+impl DebugDraw for SvgRenderer {
+    fn polygon(&mut self, vertices: &[Vector], style: &PartStyle) {
+        let mut path = String::new();
+        for (index, vertex) in vertices.iter().enumerate() {
+            let command = if index == 0 { "M" } else { "L" };
+            write!(
+                path,
+                "{}{} {} ",
+                command,
+                number(vertex.get_x()),
+                number(vertex.get_y())
+            )
+            .unwrap();
+        }
+        writeln!(self.svg, r#"<path d="{}Z" {}/>"#, path, paint(style)).unwrap();
+    }
+
+    fn circle(&mut self, centre: &Vector, radius: Real, style: &PartStyle) {
+        writeln!(
+            self.svg,
+            r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+            number(centre.get_x()),
+            number(centre.get_y()),
+            number(radius),
+            paint(style),
+        )
+        .unwrap();
+    }
+
+    fn segment(&mut self, start: &Vector, end: &Vector, color: Color, line_width: Real) {
+        writeln!(
+            self.svg,
+            r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+            number(start.get_x()),
+            number(start.get_y()),
+            number(end.get_x()),
+            number(end.get_y()),
+            color.to_css(),
+            number(line_width),
+        )
+        .unwrap();
+    }
+
+    fn point(&mut self, point: &Vector, color: Color) {
+        writeln!(
+            self.svg,
+            r#"<rect x="{}" y="{}" width="3.5" height="3.5" fill="{}"/>"#,
+            number(point.get_x() - 1.5),
+            number(point.get_y() - 1.5),
+            color.to_css(),
+        )
+        .unwrap();
+    }
+
+    fn text(&mut self, position: &Vector, text: &str, color: Color) {
+        let text = text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        writeln!(
+            self.svg,
+            r#"<text x="{}" y="{}" fill="{}" font-size="10">{}</text>"#,
+            number(position.get_x()),
+            number(position.get_y()),
+            color.to_css(),
+            text,
+        )
+        .unwrap();
+    }
+
+    fn begin_layer(&mut self, name: &str) {
+        writeln!(self.svg, r#"<g id="{}">"#, name).unwrap();
+    }
+
+    fn end_layer(&mut self) {
+        writeln!(self.svg, "</g>").unwrap();
+    }
+}
+
+// The fill, stroke and opacity attributes of the style.
+fn paint(style: &PartStyle) -> String {
+    let fill = style
+        .fill
+        .map(|fill| fill.to_css())
+        .unwrap_or_else(|| "none".to_string());
+    let stroke = match style.stroke {
+        Some(stroke) => format!(
+            r#"stroke="{}" stroke-width="{}""#,
+            stroke.to_css(),
+            number(style.line_width)
+        ),
+        None => r#"stroke="none""#.to_string(),
+    };
+    format!(
+        r#"fill="{}" {} opacity="{}""#,
+        fill,
+        stroke,
+        number(style.opacity)
+    )
+}
+
+// Rounded to 3 decimals without trailing zeros, so the documents stay small and diffable.
+fn number(value: Real) -> String {
+    let rounded = (value * 1000.).round() / 1000.;
+    let text = format!("{:.3}", rounded);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::body::body::Body;
+    use crate::constraint::constraint::Constraint;
+    use crate::constraint::constraint_option::ConstraintOption;
+    use crate::core::render::Render;
+    use crate::test_utils::body_test_utils::test_box;
+
+    use super::*;
+
+    fn test_world() -> (Composite, Body, Body) {
+        let body_a = test_box(0., 0., 10., 10.);
+        let body_b = test_box(50., 0., 10., 10.);
+        let constraint = Constraint::create(&[
+            ConstraintOption::BodyA(body_a.clone()),
+            ConstraintOption::BodyB(body_b.clone()),
+        ]);
+        let mut world = Composite::new();
+        world.add_body(&body_a);
+        world.add_body(&body_b);
+        world.add_constraint(&constraint);
+        (world, body_a, body_b)
+    }
+
+    #[test]
+    fn render_should_draw_the_bodies_and_constraints_in_the_view() {
+        // Arrange
+        let (world, _, _) = test_world();
+        let mut renderer = SvgRenderer::new(200, 100, None);
+        renderer.look_at(&world.get_bounds(), Some(5.));
+
+        // Act
+        let result = renderer.render(&world, None);
+
+        // Assert
+        assert!(result.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100" viewBox="-10 -10 70 20">"#
+        ));
+        assert!(result.contains(
+            r##"<path d="M-5 -5 L5 -5 L5 5 L-5 5 Z" fill="none" stroke="#bbbbbb" stroke-width="1" opacity="1"/>"##
+        ));
+        assert!(result.contains(
+            r##"<line x1="0" y1="0" x2="50" y2="0" stroke="#ffffff" stroke-width="1"/>"##
+        ));
+        assert!(!result.contains(r#"<g id="bounds">"#));
+        assert!(result.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn render_should_only_draw_the_layers_enabled() {
+        // Arrange
+        let (world, _, _) = test_world();
+        let mut renderer = SvgRenderer::new(200, 100, None);
+        let mut layers = Layers::all();
+        layers.constraints = false;
+        renderer.set_layers(&layers);
+
+        // Act
+        let result = renderer.render(&world, Some(&Pairs::new()));
+
+        // Assert
+        for id in ["bodies", "bounds", "axes", "velocity", "contacts"] {
+            assert!(result.contains(&format!(r#"<g id="{}">"#, id)));
+        }
+        assert!(!result.contains(r#"<g id="constraints">"#));
+        assert!(result.contains(
+            r##"<path d="M-5 -5 L5 -5 L5 5 L-5 5 Z" fill="#f19648" stroke="none" opacity="1"/>"##
+        ));
+        assert!(result.contains(
+            r##"<line x1="0" y1="0" x2="0" y2="20" stroke="#cd5c5c" stroke-width="1"/>"##
+        ));
+    }
+
+    #[test]
+    fn render_should_leave_out_internal_edges_unless_asked() {
+        // Arrange
+        let (world, mut body, _) = test_world();
+        let mut vertices = body.get_vertices();
+        vertices[1].set_is_interal(true);
+        body.set_vertices(&vertices);
+        let mut renderer = SvgRenderer::new(200, 100, None);
+
+        // Act
+        let result = renderer.render(&world, None);
+        renderer.set_layers(&Layers {
+            internal_edges: true,
+            ..Layers::default()
+        });
+        let result_internal = renderer.render(&world, None);
+
+        // Assert
+        assert!(result.contains(
+            r#"<path d="M-5 -5 L5 -5 L5 5 L-5 5 Z" fill="none" stroke="none" opacity="1"/>"#
+        ));
+        assert!(result.contains(
+            r##"<line x1="-5" y1="-5" x2="5" y2="-5" stroke="#bbbbbb" stroke-width="1"/>"##
+        ));
+        assert!(!result.contains(r#"<line x1="5" y1="-5" x2="5" y2="5""#));
+        assert!(result_internal.contains(
+            r##"<path d="M-5 -5 L5 -5 L5 5 L-5 5 Z" fill="none" stroke="#bbbbbb" stroke-width="1" opacity="1"/>"##
+        ));
+    }
+
+    #[test]
+    fn render_should_fade_sleeping_bodies_and_skip_hidden_ones() {
+        // Arrange
+        let (world, mut body_a, mut body_b) = test_world();
+        body_a.set_is_sleeping(true);
+        let render = body_b.get_render();
+        body_b.set_render(&Render::new(
+            false,
+            render.get_opacity(),
+            render.get_sprite(),
+        ));
+        let mut renderer = SvgRenderer::new(200, 100, None);
+
+        // Act
+        let result = renderer.render(&world, None);
+
+        // Assert
+        assert!(result.contains(r#"opacity="0.5""#));
+        assert!(!result.contains("M45 -5"));
+    }
+
+    #[test]
+    fn write_frame_should_write_numbered_files() {
+        // Arrange
+        let (world, _, _) = test_world();
+        let directory = std::env::temp_dir().join(format!("svg_{}", world.get_id()));
+        fs::create_dir_all(&directory).unwrap();
+        let mut renderer = SvgRenderer::new(200, 100, None);
+
+        // Act
+        let result_first = renderer.write_frame(&directory, &world, None).unwrap();
+        let result_second = renderer.write_frame(&directory, &world, None).unwrap();
+
+        // Assert
+        assert_eq!(result_first.file_name().unwrap(), "frame_00000.svg");
+        assert_eq!(result_second.file_name().unwrap(), "frame_00001.svg");
+        assert_eq!(
+            fs::read_to_string(&result_second).unwrap(),
+            renderer.render(&world, None)
+        );
+        fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn render_should_draw_the_styles_of_the_parts_by_layer() {
+        // Arrange
+        let (world, mut body_a, mut body_b) = test_world();
+        let mut render_a = body_a.get_render();
+        render_a.set_layer(1);
+        render_a.set_fill_style(Some(Color::BLUE));
+        render_a.set_stroke_style(Some(Color::RED));
+        render_a.set_line_width(Some(2.));
+        body_a.set_render(&render_a);
+        let mut render_b = body_b.get_render();
+        render_b.set_fill_style(Some(Color::GREEN));
+        body_b.set_render(&render_b);
+        let mut renderer = SvgRenderer::new(200, 100, None);
+        renderer.set_layers(&Layers {
+            wireframes: false,
+            ..Layers::default()
+        });
+
+        // Act
+        let result = renderer.render(&world, None);
+
+        // Assert
+        let fill_a = result.find(r##"fill="#0000ff""##).unwrap();
+        let fill_b = result.find(r##"fill="#008000""##).unwrap();
+        assert!(fill_b < fill_a);
+        assert!(result.contains(r##"stroke="#ff0000" stroke-width="2""##));
+        assert!(!result.contains(r##"stroke="#cccccc""##));
+    }
+}
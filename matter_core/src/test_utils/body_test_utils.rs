@@ -2,17 +2,65 @@ use std::rc::{Rc, Weak};
 
 use crate::body::body::{Body, BodyContent};
 use crate::core::position::Position;
+use crate::core::real::Real;
 use crate::core::velocity::Velocity;
-use crate::core::xy::XY;
+use crate::core::xy::{XYNew, XY};
+use crate::geometry::bounds::Bounds;
+use crate::geometry::vector::Vector;
+use crate::geometry::vertices::Vertices;
 
 use super::common_test_utils::assert_float;
 
-pub fn assert_velocity(result: &Velocity, expected_x: f64, expected_y: f64) {
+pub fn assert_velocity(result: &Velocity, expected_x: Real, expected_y: Real) {
     assert_float(result.get_x(), expected_x);
     assert_float(result.get_y(), expected_y);
 }
 
-pub fn assert_position(result: &Position, expected_x: f64, expected_y: f64) {
+pub fn assert_position(result: &Position, expected_x: Real, expected_y: Real) {
     assert_float(result.get_x(), expected_x);
     assert_float(result.get_y(), expected_y);
 }
+
+// FNV-1a over the exact bits of the body state, two builds that step a scene the same way
+// produce the same hash
+#[cfg(all(feature = "deterministic", not(feature = "f32")))]
+pub fn hash_body_state(body: &Body) -> u64 {
+    let mut values = vec![
+        body.get_position().get_x(),
+        body.get_position().get_y(),
+        body.get_angle(),
+        body.get_velocity_prop().get_x(),
+        body.get_velocity_prop().get_y(),
+        body.get_angular_velocity_prop(),
+    ];
+    for vertex in body.get_vertices().iter() {
+        values.push(vertex.get_x());
+        values.push(vertex.get_y());
+    }
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for value in values {
+        for byte in (value.to_bits() as u64).to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
+}
+
+// A box body centred on the given point, like Bodies.rectangle in matter.js.
+pub fn test_box(x: Real, y: Real, width: Real, height: Real) -> Body {
+    let mut body = Body::default_body();
+    body.set_vertices(&Vertices::create(
+        vec![
+            Vector::create(0., 0.),
+            Vector::create(width, 0.),
+            Vector::create(width, height),
+            Vector::create(0., height),
+        ],
+        None,
+    ));
+    body.set_bounds(&Bounds::create(Some(body.get_vertices())));
+    body.set_position(Position::new(x, y), None);
+    body
+}
@@ -2,7 +2,7 @@ use uuid::Uuid;
 
 use crate::{
     body::body::Body,
-    core::xy::XY,
+    core::{real::Real, xy::XY},
     geometry::{
         bounds::{Bounds, BoundsPart},
         vector,
@@ -14,7 +14,7 @@ use crate::{
 use super::super::geometry;
 use super::{super::geometry::vector::Vector, common_test_utils::assert_float};
 
-pub fn assert_xy(result: &impl XY, expected_x: f64, expected_y: f64) {
+pub fn assert_xy(result: &impl XY, expected_x: Real, expected_y: Real) {
     assert_float(result.get_x(), expected_x);
     assert_float(result.get_y(), expected_y);
 }
@@ -22,8 +22,8 @@ pub fn assert_xy(result: &impl XY, expected_x: f64, expected_y: f64) {
 pub fn assert_vertex(
     result: &Vertex,
     expected_body: Option<&Body>,
-    expected_x: f64,
-    expected_y: f64,
+    expected_x: Real,
+    expected_y: Real,
     expected_index: usize,
     expected_is_internal: bool,
 ) {
@@ -38,7 +38,7 @@ pub fn assert_vertex(
     assert_eq!(result.get_is_internal(), expected_is_internal);
 }
 
-pub fn assert_bounds(result: &Bounds, min_x: f64, min_y: f64, max_x: f64, max_y: f64) {
+pub fn assert_bounds(result: &Bounds, min_x: Real, min_y: Real, max_x: Real, max_y: Real) {
     assert_float(result.min.x, min_x);
     assert_float(result.min.y, min_y);
     assert_float(result.max.x, max_x);